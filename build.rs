@@ -0,0 +1,98 @@
+// Work out the tables in src/word_tables.rs from the built-in word lists, so that starting a game
+// doesn't have to. Without these, every game starts by building a hash table of the 40,000-word
+// frequency list, just to put a few thousand words in order.
+//
+// The word lists are plain Rust, so they're compiled right into this script too.
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+#[path = "src/kids_word_list.rs"]
+mod kids_word_list;
+#[path = "src/scrabble_word_list.rs"]
+mod scrabble_word_list;
+#[path = "src/word_frequency_list.rs"]
+mod word_frequency_list;
+#[path = "src/wordle_solutions.rs"]
+mod wordle_solutions;
+
+use scrabble_word_list::SCRABBLE_WORD_LIST;
+use word_frequency_list::WORD_FREQUENCY_LIST;
+
+fn main() {
+    // A build without the embedded lists (see Cargo.toml) doesn't have anything to make tables
+    // from.
+    if std::env::var_os("CARGO_FEATURE_EMBEDDED_LISTS").is_none() {
+        return;
+    }
+
+    for list in [
+        "kids_word_list",
+        "scrabble_word_list",
+        "word_frequency_list",
+        "wordle_solutions",
+    ] {
+        println!("cargo:rerun-if-changed=src/{}.rs", list);
+    }
+
+    // Everything is stored as a guess ID, the word's place in the guess list, which is sorted.
+    let ids: HashMap<&str, u16> = SCRABBLE_WORD_LIST
+        .iter()
+        .enumerate()
+        .map(|(id, &word)| (word, id as u16))
+        .collect();
+    let frequencies: HashMap<&str, u32> = WORD_FREQUENCY_LIST.iter().copied().collect();
+
+    // The same orders Solver used to work out for itself: the frequency list's own order for
+    // the common words, and most common first, then alphabetically, for the other lists.
+    let common = WORD_FREQUENCY_LIST
+        .iter()
+        .filter_map(|(word, _)| ids.get(word).copied())
+        .collect::<Vec<_>>();
+    let by_frequency = |words: &[&str]| {
+        let mut words = words.to_vec();
+        words.sort_by_key(|w| {
+            (
+                u32::MAX - frequencies.get(w).copied().unwrap_or_default(),
+                *w,
+            )
+        });
+        words.iter().map(|w| ids[w]).collect::<Vec<_>>()
+    };
+    let guess_frequencies = SCRABBLE_WORD_LIST
+        .iter()
+        .map(|w| frequencies.get(w).copied().unwrap_or_default())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        common.iter().collect::<HashSet<_>>().len(),
+        common.len(),
+        "the frequency list has a word in it twice"
+    );
+
+    let mut out = String::new();
+    write_table(&mut out, "COMMON_GUESS_IDS", "u16", &common);
+    write_table(
+        &mut out,
+        "SOLUTION_IDS_BY_FREQUENCY",
+        "u16",
+        &by_frequency(wordle_solutions::WORDLE_SOLUTION_LIST),
+    );
+    write_table(
+        &mut out,
+        "KIDS_IDS_BY_FREQUENCY",
+        "u16",
+        &by_frequency(kids_word_list::KIDS_WORD_LIST),
+    );
+    write_table(&mut out, "GUESS_FREQUENCIES", "u32", &guess_frequencies);
+
+    let path = PathBuf::from(std::env::var_os("OUT_DIR").unwrap()).join("word_tables.rs");
+    std::fs::write(path, out).unwrap();
+}
+
+fn write_table<T: std::fmt::Display>(out: &mut String, name: &str, kind: &str, values: &[T]) {
+    let _ = write!(out, "pub static {}: [{}; {}] = [", name, kind, values.len());
+    for value in values {
+        let _ = write!(out, "{},", value);
+    }
+    let _ = writeln!(out, "];");
+}
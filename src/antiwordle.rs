@@ -0,0 +1,77 @@
+use crate::guess_quality::*;
+use crate::knowledge::*;
+use crate::score::WORD_LENGTH;
+use crate::solver::*;
+use std::collections::HashMap;
+
+// Antiwordle turns Wordle around: the goal is to go as long as possible without guessing the
+// answer. The catch is that every clue is forced on you. Gray letters can't be used again,
+// yellow letters have to be used, and green letters have to stay where they are. So a good
+// guess is one that's unlikely to be the answer, and that doesn't give away much, since
+// everything it gives away is something later guesses have to follow.
+
+// How one guess would do in Antiwordle.
+#[derive(Clone, Copy)]
+pub struct AntiwordleQuality<'a> {
+    pub guess: &'a str,
+
+    // The chance that this guess is the answer, which would end the game.
+    pub hit_chance: f64,
+
+    // The usual measures of what the score would tell you. Here, the less the better: a guess
+    // that leaves lots of answers open is one that doesn't force many letters on later guesses.
+    pub quality: GuessQuality<'a>,
+}
+
+// Whether Antiwordle's rules allow a word after the clues so far: none of the gray letters, at
+// least as many of each yellow or green letter as have been found, and every green letter in its
+// place.
+pub fn is_antiwordle_legal(knowledge: &Knowledge, word: &str) -> bool {
+    let letters = word.chars().collect::<Vec<_>>();
+    letters.len() == WORD_LENGTH
+        && letters
+            .iter()
+            .all(|&c| knowledge.status(c) != LetterStatus::Excluded)
+        && letters
+            .iter()
+            .enumerate()
+            .all(|(position, &c)| knowledge.found_at(position).is_none_or(|found| found == c))
+        && ('a'..='z').all(|letter| {
+            letters.iter().filter(|&&c| c == letter).count() >= knowledge.min_count(letter)
+        })
+}
+
+// Rank every guess Antiwordle allows, best first: the least likely to be the answer, and then
+// the one expected to leave the most answers open.
+pub fn rank_antiwordle_guesses(solver: &Solver) -> Vec<AntiwordleQuality<'static>> {
+    let chances: HashMap<&str, f64> = solver.answer_chances().into_iter().collect();
+    let legal = solver
+        .valid_guesses()
+        .iter()
+        .copied()
+        .filter(|w| is_antiwordle_legal(solver.knowledge(), w))
+        .collect::<Vec<_>>();
+    let mut ranked = rank_guesses(
+        &legal,
+        solver.remaining_targets(),
+        solver.duplicate_policy(),
+    )
+    .into_iter()
+    .map(|quality| AntiwordleQuality {
+        guess: quality.guess,
+        hit_chance: chances.get(quality.guess).copied().unwrap_or(0.0),
+        quality,
+    })
+    .collect::<Vec<_>>();
+    ranked.sort_by(|a, b| {
+        a.hit_chance
+            .total_cmp(&b.hit_chance)
+            .then_with(|| {
+                b.quality
+                    .expected_remaining
+                    .total_cmp(&a.quality.expected_remaining)
+            })
+            .then_with(|| a.guess.cmp(b.guess))
+    });
+    ranked
+}
@@ -0,0 +1,42 @@
+use clap::Parser;
+use wordle_guesser::bot::*;
+use wordle_guesser::*;
+
+#[derive(Parser)]
+#[clap(
+    version,
+    about = "Runs WordleGuesser as a Telegram bot. Set TELEGRAM_BOT_TOKEN to the token you got from @BotFather."
+)]
+struct BotArgs {
+    /// Hard Mode: only suggest guesses that fit with every score entered so far.
+    #[clap(short, long)]
+    hard: bool,
+
+    /// Use the Wordle solution list instead of the most common English words.
+    #[clap(short, long)]
+    solutions: bool,
+}
+
+fn main() {
+    let args = BotArgs::parse();
+
+    let token = match std::env::var("TELEGRAM_BOT_TOKEN") {
+        Ok(token) => token,
+        Err(_) => {
+            eprintln!("Set TELEGRAM_BOT_TOKEN to your bot's token before starting it.");
+            std::process::exit(2);
+        }
+    };
+
+    let target_list = if args.solutions {
+        TargetList::Solutions
+    } else {
+        TargetList::Common(5000)
+    };
+
+    let mut sessions = BotSessions::new(target_list, args.hard);
+    if let Err(e) = run_telegram_bot(&token, &mut sessions) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,239 @@
+use crate::error::*;
+use crate::score::*;
+use crate::solver::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+// A chat bot front end for the solver. People send it their guesses and scores, one per line,
+// like "raise .y..G", and it replies with how many words are still possible and what to guess
+// next. Each chat gets its own game.
+//
+// The message handling is kept separate from the Telegram plumbing below, so it only ever deals
+// in text and could sit behind any other chat service just as easily.
+pub struct BotSessions {
+    target_list: TargetList,
+    hard: bool,
+    duplicates: DuplicatePolicy,
+    games: HashMap<i64, Solver>,
+}
+
+const BOT_HELP: &str = "Send me your Wordle guesses and the scores you got, one per line, like:
+
+raise .y..G
+
+where . is a gray letter, y is yellow, and G is green. I'll tell you how many words are still \
+possible and suggest what to guess next. Send /new to start over on a new puzzle.";
+
+impl BotSessions {
+    pub fn new(target_list: TargetList, hard: bool) -> BotSessions {
+        BotSessions {
+            target_list,
+            hard,
+            duplicates: DuplicatePolicy::Standard,
+            games: HashMap::new(),
+        }
+    }
+
+    // Score new games by some other rule for repeated letters. Games already going keep the
+    // rule they started with.
+    pub fn set_duplicate_policy(&mut self, policy: DuplicatePolicy) {
+        self.duplicates = policy;
+    }
+
+    // Handle one incoming message and return the reply to send back.
+    pub fn handle_message(&mut self, chat_id: i64, text: &str) -> String {
+        let text = text.trim();
+        if text.starts_with("/start") || text.starts_with("/help") {
+            return BOT_HELP.to_string();
+        }
+
+        if text.starts_with("/new") {
+            self.games.remove(&chat_id);
+            return "Starting a new puzzle. Send me your first guess and score.".to_string();
+        }
+
+        let (target_list, hard, duplicates) = (self.target_list, self.hard, self.duplicates);
+        let solver = self.games.entry(chat_id).or_insert_with(|| {
+            let mut solver = Solver::new(target_list, hard);
+            solver.set_duplicate_policy(duplicates);
+            solver
+        });
+
+        // Check every line before applying any of them, so a typo doesn't leave the game
+        // half-updated.
+        let mut turns = Vec::new();
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty()) {
+            match parse_turn(line) {
+                Some(turn) => turns.push(turn),
+                None => {
+                    return format!(
+                        "I didn't understand \"{}\". Each line should be a {}-letter guess and its score, like \"raise .y..G\".",
+                        line, WORD_LENGTH
+                    )
+                }
+            }
+        }
+
+        // A score can still turn out not to fit once the lines before it are in, so they're
+        // played on a copy, which only replaces the game if every one of them goes in.
+        let mut next = solver.clone();
+        for (guess, score) in &turns {
+            if let Err(e) = next.apply_guess(guess, *score) {
+                return e.to_string();
+            }
+        }
+        *solver = next;
+
+        describe_solver(solver)
+    }
+}
+
+// Split a line like "raise .y..G" into the guess and its numeric score.
+fn parse_turn(line: &str) -> Option<(String, WordScore)> {
+    let mut parts = line.split_whitespace();
+    let guess = parts.next()?.to_lowercase();
+    let score = parse_score(parts.next()?).ok()?;
+    if parts.next().is_some()
+        || guess.len() != WORD_LENGTH
+        || !guess.chars().all(|c| c.is_ascii_alphabetic())
+    {
+        return None;
+    }
+    Some((guess, score))
+}
+
+// Summarize where the game stands, in a form that fits comfortably in a chat message.
+fn describe_solver(solver: &Solver) -> String {
+    let remaining_targets = solver.remaining_targets();
+    match remaining_targets.len() {
+        0 => {
+            return "There are no possible words left. Did you enter your guesses and scores correctly? Send /new to start over.".to_string();
+        }
+        1 => return format!("The word is: {}", remaining_targets[0]),
+        _ => {}
+    }
+
+    let mut reply = format!("{} possible words left", remaining_targets.len());
+    let max_shown = 20;
+    if remaining_targets.len() <= max_shown {
+        let _ = write!(reply, ": {}", remaining_targets.join(" "));
+    }
+
+    reply.push_str("\n\nSuggested guesses:");
+    for q in solver.rank_guesses().iter().take(5) {
+        let _ = write!(
+            reply,
+            "\n{}{} (average {:.1} left, max {})",
+            q.guess,
+            if q.has_winning { " *" } else { "" },
+            q.expected_remaining,
+            q.max_remaining
+        );
+    }
+
+    reply
+}
+
+// Just enough of the Telegram Bot API to receive messages and reply to them.
+#[derive(Deserialize)]
+struct TelegramResponse<T> {
+    ok: bool,
+    description: Option<String>,
+    result: Option<T>,
+}
+
+#[derive(Deserialize)]
+struct TelegramUpdate {
+    update_id: i64,
+    message: Option<TelegramMessage>,
+}
+
+#[derive(Deserialize)]
+struct TelegramMessage {
+    chat: TelegramChat,
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TelegramChat {
+    id: i64,
+}
+
+// How long to wait before asking again after getUpdates fails, doubling each time it fails in
+// a row, up to the longest.
+const FIRST_RETRY_DELAY: Duration = Duration::from_secs(1);
+const LONGEST_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+// Run the bot against the Telegram Bot API, long-polling for new messages. This only returns if
+// Telegram refuses a request, e.g. because the token is wrong. A dropped connection or a garbled
+// reply is just logged, and tried again after a wait.
+pub fn run_telegram_bot(token: &str, sessions: &mut BotSessions) -> Result<()> {
+    let api = format!("https://api.telegram.org/bot{}", token);
+    let mut next_update = 0i64;
+    let mut retry_delay = FIRST_RETRY_DELAY;
+
+    loop {
+        let response: TelegramResponse<Vec<TelegramUpdate>> =
+            match ureq::get(&format!("{}/getUpdates", api))
+                .query("timeout", "30")
+                .query("offset", &next_update.to_string())
+                .call()
+                .or_else(|e| match e {
+                    // Telegram turning the request down (a wrong token is a 401) still comes
+                    // with its reason in the usual reply, which ends the bot below. Being told
+                    // to slow down, or the server having trouble, is worth waiting out.
+                    ureq::Error::Status(code, response) if code != 429 && code < 500 => {
+                        Ok(response)
+                    }
+                    e => Err(format!("getUpdates failed: {}", e)),
+                })
+                .and_then(|response| {
+                    response
+                        .into_json()
+                        .map_err(|e| format!("getUpdates returned something unexpected: {}", e))
+                }) {
+                Ok(response) => {
+                    retry_delay = FIRST_RETRY_DELAY;
+                    response
+                }
+                Err(e) => {
+                    eprintln!("{}; trying again in {}s", e, retry_delay.as_secs());
+                    std::thread::sleep(retry_delay);
+                    retry_delay = (retry_delay * 2).min(LONGEST_RETRY_DELAY);
+                    continue;
+                }
+            };
+
+        if !response.ok {
+            return Err(Error::Network(format!(
+                "Telegram refused getUpdates: {}",
+                response.description.unwrap_or_default()
+            )));
+        }
+
+        for update in response.result.unwrap_or_default() {
+            next_update = next_update.max(update.update_id + 1);
+
+            let message = match update.message {
+                Some(message) => message,
+                None => continue,
+            };
+            let text = match message.text {
+                Some(text) => text,
+                None => continue,
+            };
+
+            let reply = sessions.handle_message(message.chat.id, &text);
+
+            // A reply that fails to send (say, the user blocked the bot) shouldn't take the
+            // bot down for everyone else.
+            if let Err(e) = ureq::post(&format!("{}/sendMessage", api))
+                .send_json(serde_json::json!({ "chat_id": message.chat.id, "text": reply }))
+            {
+                eprintln!("sendMessage to chat {} failed: {}", message.chat.id, e);
+            }
+        }
+    }
+}
@@ -0,0 +1,209 @@
+use crate::cli::solve::*;
+use crate::cli::*;
+use clap::Args;
+use std::time::Instant;
+use wordle_guesser::*;
+
+#[derive(Args)]
+pub struct AnalyzeArgs {
+    #[clap(flatten)]
+    pub lists: ListArgs,
+
+    #[clap(flatten)]
+    pub memory: MemoryArgs,
+
+    /// The guesses to analyze, e.g. your favorite opening words. Or "strategies", to play every
+    /// built-in strategy against the whole Wordle solution list and compare how they do. Or
+    /// "compare" and two opening words, to play each one against every solution, followed by
+    /// the usual suggestions, and see whether one is really better than the other.
+    #[clap(required = true)]
+    pub words: Vec<String>,
+}
+
+// Show how good some particular guesses are as an opening move, and where they rank among all
+// of the possible guesses.
+pub fn run(args: &AnalyzeArgs, quiet: bool) -> Result<Outcome> {
+    if args.words.len() == 1 && args.words[0].eq_ignore_ascii_case("strategies") {
+        return compare_strategies(args, quiet);
+    }
+    if let [command, first, second] = args.words.as_slice() {
+        if command.eq_ignore_ascii_case("compare") {
+            return compare_openers(&first.to_lowercase(), &second.to_lowercase(), args, quiet);
+        }
+    }
+
+    let solver = args.lists.solver()?;
+    let ranked = solver.rank_guesses();
+
+    let mut outcome = Outcome::Solved;
+    for word in &args.words {
+        let word = word.to_lowercase();
+        if !rate_guess(&word, &ranked, &solver, quiet, false)? {
+            eprintln!("'{}' isn't a valid guess.", word);
+            outcome = Outcome::InvalidInput;
+        }
+    }
+
+    Ok(outcome)
+}
+
+// Play every strategy against every word in the Wordle solution list, and show how they stack
+// up: the average, the spread of how many guesses the games took, how many went past six, and
+// how long the strategy took to play them all. This is the scoreboard for anyone working on a
+// better strategy.
+fn compare_strategies(args: &AnalyzeArgs, quiet: bool) -> Result<Outcome> {
+    let mut solver = Solver::with_word_lists(
+        TargetList::Solutions,
+        args.lists.is_hard(),
+        overlay::word_lists(false)?,
+    );
+    args.lists.apply_hard_mode(&mut solver);
+    solver.set_duplicate_policy(default_duplicate_policy());
+    args.memory.precompute(&mut solver, quiet)?;
+
+    if !quiet {
+        outln!(
+            "Playing every strategy against all {} solutions{}...\n",
+            solver.remaining_targets().len(),
+            describe_hard_mode(&solver)
+        );
+        out!("strategy  | average | worst | fail |");
+        for guesses in 1..=WORDLE_MAX_GUESSES {
+            out!(" {:>4}", guesses);
+        }
+        outln!(" {:>4} | seconds", format!("{}+", WORDLE_MAX_GUESSES + 1));
+        print_rule(78)?;
+    }
+
+    for strategy in all_strategies() {
+        let start = Instant::now();
+        let results = simulate(&solver, strategy.as_ref(), Some(0));
+        let elapsed = start.elapsed();
+        let summary = summarize(&results);
+
+        out!(
+            "{:9} | {:7.3} | {:5} | {:4} |",
+            strategy.name(),
+            summary.average_guesses,
+            summary.worst_guesses,
+            summary.failures
+        );
+        for guesses in 1..=WORDLE_MAX_GUESSES {
+            out!(" {:4}", summary.distribution[guesses]);
+        }
+        let more: usize = summary.distribution[WORDLE_MAX_GUESSES + 1..].iter().sum();
+        outln!(" {:4} | {:7.2}", more, elapsed.as_secs_f64());
+    }
+
+    Ok(Outcome::Solved)
+}
+
+// Below this chance of a difference happening by luck, it's called significant. That's the
+// usual 1 in 20.
+pub const SIGNIFICANCE_LEVEL: f64 = 0.05;
+
+// Play two openers against every word in the Wordle solution list, each followed by the
+// ranker, and compare them answer by answer. Openers that are close usually differ by a few
+// hundredths of a guess on average, which could easily be down to which answers happened to
+// suit which opener, so this checks whether the difference is bigger than luck would explain.
+fn compare_openers(first: &str, second: &str, args: &AnalyzeArgs, quiet: bool) -> Result<Outcome> {
+    let mut solver = Solver::with_word_lists(
+        TargetList::Solutions,
+        args.lists.is_hard(),
+        overlay::word_lists(args.lists.fold_accents)?,
+    );
+    args.lists.apply_hard_mode(&mut solver);
+    solver.set_duplicate_policy(default_duplicate_policy());
+    args.memory.precompute(&mut solver, quiet)?;
+    for word in [first, second] {
+        if !solver.valid_guesses().contains(&word) {
+            eprintln!("'{}' isn't a valid guess.", word);
+            return Ok(Outcome::InvalidInput);
+        }
+    }
+
+    let mut games = Vec::new();
+    for word in [first, second] {
+        games.push(
+            Policy::new(Some(word), strategy_by_name("ranker")?)
+                .evaluate(&solver)?
+                .games,
+        );
+    }
+    let summaries = games.iter().map(|g| summarize(g)).collect::<Vec<_>>();
+    let result = head_to_head(&games[0], &games[1]);
+
+    if quiet {
+        outln!(
+            "{} {:.4} {} {:.4} {} {} {} {:.4} {:.4}",
+            first,
+            summaries[0].average_guesses,
+            second,
+            summaries[1].average_guesses,
+            result.wins,
+            result.losses,
+            result.ties,
+            result.t_test_p,
+            result.sign_test_p
+        );
+        return Ok(Outcome::Solved);
+    }
+
+    outln!(
+        "Opening with each word, then playing the ranker's suggestions, against all {} solutions{}:\n",
+        summaries[0].games,
+        describe_hard_mode(&solver)
+    );
+    for (word, summary) in [first, second].iter().zip(&summaries) {
+        outln!(
+            "{:5}  average {:.4} guesses, worst {}, failed {}",
+            word,
+            summary.average_guesses,
+            summary.worst_guesses,
+            summary.failures
+        );
+    }
+    outln!(
+        "\n{} took fewer guesses for {} answers, {} took fewer for {}, and they tied on {}.",
+        first,
+        result.wins,
+        second,
+        result.losses,
+        result.ties
+    );
+    outln!(
+        "The difference in averages is {:+.4} guesses, give or take {:.4}.",
+        result.mean_difference,
+        result.standard_error
+    );
+    outln!(
+        "Paired t-test: p = {:.4}    Sign test: p = {:.4}",
+        result.t_test_p,
+        result.sign_test_p
+    );
+
+    let (better, worse) = if result.mean_difference < 0.0 {
+        (first, second)
+    } else {
+        (second, first)
+    };
+    if result.t_test_p < SIGNIFICANCE_LEVEL {
+        outln!(
+            "\n{} really is better than {}: a difference this big would only happen by luck {} of the time.",
+            better,
+            worse,
+            if result.t_test_p < 0.001 {
+                "less than 0.1%".to_string()
+            } else {
+                format!("{:.1}%", result.t_test_p * 100.0)
+            }
+        );
+    } else {
+        outln!(
+            "\nThere's no telling them apart: a difference this big would happen by luck {:.0}% of the time.",
+            result.t_test_p * 100.0
+        );
+    }
+
+    Ok(Outcome::Solved)
+}
@@ -0,0 +1,97 @@
+use crate::cli::*;
+use wordle_guesser::*;
+
+// Help with Antiwordle, where the goal is to avoid the answer for as long as you can. Each
+// turn suggests the allowed guesses least likely to end the game, and reads back the guess and
+// its score, until the score comes back all green.
+pub fn run(lists: &ListArgs, num_shown: usize, quiet: bool) -> Result<Outcome> {
+    // Antiwordle's rules take the place of hard mode's, so --hard doesn't apply.
+    let mut solver = Solver::with_word_lists(
+        lists.target_list(),
+        false,
+        overlay::word_lists(lists.fold_accents)?,
+    );
+    solver.set_duplicate_policy(default_duplicate_policy());
+
+    loop {
+        let ranked = rank_antiwordle_guesses(&solver);
+        if !quiet {
+            outln!(
+                "\nThere are {} possible answers, and {} words you're allowed to guess.",
+                solver.remaining_targets().len(),
+                ranked.len()
+            );
+            outln!("\nSAFEST GUESSES (sorted by the chance of hitting the answer, then the most answers left open)");
+            print_rule(102)?;
+        }
+        for q in ranked.iter().take(num_shown) {
+            outln!(
+                "{} | {:.1}% chance of being the answer, average {:.1} answers left",
+                q.guess,
+                q.hit_chance * 100.0,
+                q.quality.expected_remaining
+            );
+        }
+
+        let guess = loop {
+            prompt(quiet, "\nPlease enter the word you'll guess: ")?;
+            let input_str = match read_input_line()? {
+                Some(s) => s.to_lowercase(),
+                None => return Ok(Outcome::Unsolved),
+            };
+            if ranked.iter().any(|q| q.guess == input_str) {
+                break input_str;
+            }
+            if quiet {
+                eprintln!("'{}' isn't an allowed guess.", input_str);
+                return Ok(Outcome::InvalidInput);
+            }
+            if solver.valid_guesses().contains(&input_str.as_str()) {
+                outln!("'{}' breaks the rules: it can't use any gray letters, and it has to use every yellow letter and keep every green one in place.", input_str);
+            } else {
+                outln!("'{}' isn't in the word list.", input_str);
+            }
+        };
+
+        let score = loop {
+            prompt(
+                quiet,
+                "Enter the score you got for that word, in \".y.GG\" format: ",
+            )?;
+            let input_str = match read_input_line()? {
+                Some(s) => s,
+                None => return Ok(Outcome::Unsolved),
+            };
+            match parse_score(&input_str) {
+                Ok(score) => break score,
+                Err(e) if quiet => {
+                    eprintln!("{}", e);
+                    return Ok(Outcome::InvalidInput);
+                }
+                Err(e) => outln!("Sorry, {}.", e),
+            }
+        };
+
+        if score == ALL_GREEN {
+            if !quiet {
+                outln!(
+                    "\nThat's the answer, so the game is over. You lasted {} guesses.",
+                    solver.history().len() + 1
+                );
+            }
+            return Ok(Outcome::Solved);
+        }
+
+        match solver.apply_guess(&guess, score) {
+            Ok(()) => {}
+            Err(e @ (Error::InconsistentScore(..) | Error::EmptyCandidateSet(..))) => {
+                if quiet {
+                    eprintln!("{}", e);
+                    return Ok(Outcome::from_error(&e));
+                }
+                outln!("\nThat can't be right: {}. Let's try that again.", e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
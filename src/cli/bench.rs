@@ -0,0 +1,47 @@
+use crate::cli::*;
+use clap::Args;
+use std::time::Instant;
+
+#[derive(Args)]
+pub struct BenchArgs {
+    #[clap(flatten)]
+    pub lists: ListArgs,
+
+    /// How many times to rank the full list of guesses. More rounds gives steadier numbers.
+    #[clap(long, default_value = "5")]
+    pub rounds: u32,
+}
+
+// Time the expensive parts: setting up the word lists, and ranking every guess against every
+// possible answer, which is what happens before the first suggestions appear. Build with
+// --release for meaningful numbers.
+pub fn run(args: &BenchArgs, quiet: bool) -> Result<Outcome> {
+    let start = Instant::now();
+    let solver = args.lists.solver()?;
+    let setup = start.elapsed();
+
+    let pairs = solver.valid_guesses().len() * solver.remaining_targets().len();
+    let rounds = args.rounds.max(1);
+    let start = Instant::now();
+    for _ in 0..rounds {
+        solver.rank_guesses();
+    }
+    let ranking = start.elapsed() / rounds;
+
+    if !quiet {
+        outln!(
+            "{} guesses x {} possible answers, {} rounds",
+            solver.valid_guesses().len(),
+            solver.remaining_targets().len(),
+            rounds
+        );
+    }
+    outln!("setup:   {:8.2} ms", setup.as_secs_f64() * 1000.0);
+    outln!(
+        "ranking: {:8.2} ms ({:.1} million word pairs per second)",
+        ranking.as_secs_f64() * 1000.0,
+        pairs as f64 / ranking.as_secs_f64() / 1_000_000.0
+    );
+
+    Ok(Outcome::Solved)
+}
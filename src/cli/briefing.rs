@@ -0,0 +1,159 @@
+use crate::cli::*;
+use clap::Args;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use wordle_guesser::*;
+
+// A quick read before playing, for someone who'd rather keep the solver out of the game itself:
+// what to open with, how well it should go, and what to play second after the scores it's
+// likeliest to get. It's the first two turns of a solve session, worked out ahead of time.
+
+#[derive(Args)]
+pub struct BriefingArgs {
+    #[clap(flatten)]
+    pub lists: ListArgs,
+
+    /// How many of the opener's likeliest scores to plan a second guess for.
+    #[clap(short, long, value_name = "N", default_value = "5")]
+    pub replies: usize,
+}
+
+// One of the scores the opener could get, and what to do about it.
+struct Reply {
+    score: WordScore,
+    chance: f64,
+    remaining: usize,
+    next: Option<GuessQuality<'static>>,
+}
+
+pub fn run(args: &BriefingArgs, quiet: bool) -> Result<Outcome> {
+    let solver = args.lists.solver()?;
+    let opener = match cache::rank_guesses(&solver).first() {
+        Some(&q) => q,
+        None => {
+            eprintln!("There aren't any guesses to open with.");
+            return Ok(Outcome::InvalidInput);
+        }
+    };
+    if quiet {
+        outln!("{}", opener.guess);
+        return Ok(Outcome::Solved);
+    }
+
+    // How likely each score is. Common words are likelier answers, so this goes by the
+    // frequency list, the same as 'best now' in a solve session, even without --weighted.
+    let mut groups: HashMap<WordScore, (f64, usize)> = HashMap::new();
+    for (target, chance) in solver.answer_chances() {
+        let group = groups
+            .entry(solver.score(opener.guess, target))
+            .or_default();
+        group.0 += chance;
+        group.1 += 1;
+    }
+    let mut likeliest = groups.into_iter().collect::<Vec<_>>();
+    likeliest.sort_by(|a, b| b.1 .0.total_cmp(&a.1 .0).then(a.0.cmp(&b.0)));
+    let all_planned = likeliest.len() <= args.replies;
+    likeliest.truncate(args.replies);
+
+    let replies = likeliest
+        .into_par_iter()
+        .map(|(score, (chance, remaining))| {
+            let next = if score == ALL_GREEN {
+                None
+            } else {
+                let mut next = solver.clone();
+                next.apply_guess(opener.guess, score)?;
+                next.best_guess()
+            };
+            Ok(Reply {
+                score,
+                chance,
+                remaining,
+                next,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    print_opener(&solver, &args.lists, &opener)?;
+    print_replies(opener.guess, &replies, all_planned)?;
+    Ok(Outcome::Solved)
+}
+
+fn print_opener(solver: &Solver, lists: &ListArgs, opener: &GuessQuality) -> Result<()> {
+    outln!(
+        "Today's briefing: {} possible answers, {} mode.\n",
+        solver.remaining_targets().len(),
+        if lists.is_hard() { "hard" } else { "normal" }
+    );
+    outln!("Open with {}.", opener.guess.to_uppercase());
+    let worst = if plain_output() {
+        describe_score(opener.guess, opener.score_with_max_remaining)
+    } else {
+        format_score(opener.score_with_max_remaining).to_string()
+    };
+    outln!(
+        "  It leaves {:.1} words on average, and {} at most (if it gets {}).",
+        opener.expected_remaining,
+        opener.max_remaining,
+        worst
+    );
+    outln!(
+        "  It can get {} different scores, for {:.2} bits of information.",
+        opener.partitions,
+        opener.entropy
+    );
+    if opener.has_winning {
+        outln!(
+            "  It could be the answer itself: a 1 in {:.0} chance of winning on the first guess.",
+            1.0 / opener.win_chance
+        );
+    }
+    Ok(())
+}
+
+fn print_replies(opener: &str, replies: &[Reply], all_planned: bool) -> Result<()> {
+    outln!("\nThe likeliest scores, and what to play second:\n");
+    // The counts line up in a column, except in plain output, where each is in a sentence.
+    let width = if plain_output() { 0 } else { 4 };
+    for reply in replies {
+        let score = if plain_output() {
+            describe_score(opener, reply.score)
+        } else {
+            format_score(reply.score).to_string()
+        };
+        let plan = match &reply.next {
+            None => "that's the answer, and the game's won".to_string(),
+            Some(next) if reply.remaining == 1 => {
+                format!("{:width$} left, and it's {}", 1, next.guess.to_uppercase())
+            }
+            Some(next) => format!(
+                "{:width$} left, then play {}, leaving {:.1} on average and {} at most",
+                reply.remaining,
+                next.guess.to_uppercase(),
+                next.expected_remaining,
+                next.max_remaining
+            ),
+        };
+        if plain_output() {
+            outln!(
+                "If it gets {} ({:.0}% likely): {}.",
+                score,
+                reply.chance * 100.0,
+                plan
+            );
+        } else {
+            outln!("  {} {:3.0}%  {}", score, reply.chance * 100.0, plan);
+        }
+    }
+
+    if all_planned {
+        return Ok(());
+    }
+    let covered: f64 = replies.iter().map(|r| r.chance).sum();
+    outln!(
+        "\nBetween them, those are {:.0}% of the likely answers. For anything else, run",
+        covered * 100.0
+    );
+    outln!("wordle_guesser and type in the score.");
+    Ok(())
+}
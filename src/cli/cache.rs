@@ -0,0 +1,290 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+use std::fs;
+use std::path::PathBuf;
+use wordle_guesser::*;
+
+// Ranking every guess takes a noticeable moment early in a game, and it's the same work every
+// time someone opens with the same words. So the rankings get saved in a cache directory,
+// ~/.cache/wordleguesser, and read back the next time the same situation comes up -- whether
+// that's the very first turn, or a game that got the same scores as yesterday's.
+//
+// The ranking only depends on which guesses are allowed and which answers are still possible, so
+// that's what the cache is keyed on. The cache is only a speed-up: if anything goes wrong with
+// it, the ranking is just worked out again, and the cache directory can be deleted at any time.
+
+// Rankings that take less work than this aren't worth saving; working them out again is about
+// as quick as reading them back.
+const MIN_PAIRS_CACHED: usize = 2_000_000;
+
+// Bump this if the format or the ranking changes, so that old files get ignored.
+const CACHE_VERSION: &str = "wordleguesser-rank 2";
+
+// The first turn's rankings with the built-in word lists, for the usual 5000 common words and
+// for the Wordle solution list. (Hard mode doesn't make any difference until after the first
+// guess.) These are the same for everyone, so they come with the program, and the first
+// suggestions show up straight away even when the cache is empty.
+//
+// They only have the top BUILT_IN_ROWS guesses by each measure, since that's as far down as
+// anything shows, and the whole ranking would make the program a megabyte and a half bigger.
+// Anything that needs a guess from further down gets the rest with complete_ranking. And they're
+// only the words: working out the numbers for a few hundred guesses is quick, and then nothing
+// here has to change when GuessQuality does. The files are named like cache files, and written
+// by the test at the bottom: to make new ones after changing the word lists or the ranking, run
+// `cargo test --release -- --ignored`, and point these at the new files.
+//
+// A build without the embedded lists has no built-in lists for these to go with, so it leaves
+// them out too.
+#[cfg(feature = "embedded-lists")]
+const BUILT_IN_RANKINGS: &[(u64, &str)] = &[
+    (
+        0x4a0a8dc7cdb1c4b1,
+        include_str!("openings/4a0a8dc7cdb1c4b1.txt"),
+    ),
+    (
+        0xa60003a32bd9d468,
+        include_str!("openings/a60003a32bd9d468.txt"),
+    ),
+];
+#[cfg(not(feature = "embedded-lists"))]
+const BUILT_IN_RANKINGS: &[(u64, &str)] = &[];
+
+// The built-in rankings start with this instead of CACHE_VERSION, since they're just the words.
+// After it comes how many guesses there are in all, and then the words, best first.
+const BUILT_IN_VERSION: &str = "wordleguesser-openings 2";
+
+fn cache_dir() -> Option<PathBuf> {
+    if let Some(dir) = std::env::var_os("XDG_CACHE_HOME") {
+        return Some(PathBuf::from(dir).join("wordleguesser"));
+    }
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+    Some(PathBuf::from(home).join(".cache").join("wordleguesser"))
+}
+
+// A hash of the word lists that doesn't change from one build to the next (unlike the standard
+// library's hasher, which makes no promises). This is 64-bit FNV-1a.
+fn fnv_hash(lists: &[&[&str]]) -> u64 {
+    let mut hash = 0xcbf29ce484222325u64;
+    let mut add = |byte: u8| {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    };
+    for list in lists {
+        for word in list.iter() {
+            word.bytes().for_each(&mut add);
+            add(b' ');
+        }
+        add(b'\n');
+    }
+    hash
+}
+
+// What the cache is keyed on. Rankings under a different duplicate-letter rule, or weighted by
+// how likely the answers are, are different rankings, but the usual settings leave the key as
+// it always was, so that the built-in rankings still match.
+fn ranking_key(solver: &Solver) -> u64 {
+    let mut lists = vec![solver.valid_guesses(), solver.remaining_targets()];
+    if solver.duplicate_policy() == DuplicatePolicy::AllYellow {
+        lists.push(&["all-yellow"]);
+    }
+    if solver.is_weighted() {
+        lists.push(&["weighted"]);
+    }
+    fnv_hash(&lists)
+}
+
+// Rank the solver's guesses, using a built-in or cached ranking if there is one. A built-in one
+// only has the top of the ranking; see BUILT_IN_RANKINGS.
+pub fn rank_guesses(solver: &Solver) -> Vec<GuessQuality<'static>> {
+    let guesses = solver.valid_guesses();
+    let targets = solver.remaining_targets();
+    if guesses.len() * targets.len() < MIN_PAIRS_CACHED {
+        return solver.rank_guesses();
+    }
+
+    let key = ranking_key(solver);
+    if let Some(ranked) = BUILT_IN_RANKINGS
+        .iter()
+        .find(|(built_in_key, _)| *built_in_key == key)
+        .and_then(|(_, text)| parse_built_in_ranking(text, solver))
+    {
+        return ranked;
+    }
+
+    let path = match cache_dir() {
+        Some(dir) => dir.join(format!("{:016x}.txt", key)),
+        None => return solver.rank_guesses(),
+    };
+
+    if let Some(ranked) = fs::read_to_string(&path)
+        .ok()
+        .and_then(|text| parse_ranking(&text, guesses))
+    {
+        return ranked;
+    }
+
+    let ranked = solver.rank_guesses();
+    if let Some(dir) = path.parent() {
+        // Written to a temporary file first, so that another run can't read half of it.
+        let temp = path.with_extension("tmp");
+        let _ = fs::create_dir_all(dir)
+            .and_then(|_| fs::write(&temp, format_ranking(&ranked)))
+            .and_then(|_| fs::rename(&temp, &path));
+    }
+    ranked
+}
+
+// Fill in the rest of a ranking that only has the top of it, from a built-in one, if a word
+// from further down is wanted: to say where it comes, it has to be in there. The rest go after
+// the ones that are already there, in order.
+pub fn complete_ranking(ranked: &mut Vec<GuessQuality<'static>>, solver: &Solver, word: &str) {
+    let guesses = solver.valid_guesses();
+    if ranked.len() >= guesses.len()
+        || ranked.iter().any(|q| q.guess == word)
+        || !guesses.contains(&word)
+    {
+        return;
+    }
+    let have: HashSet<&str> = ranked.iter().map(|q| q.guess).collect();
+    ranked.extend(
+        solver
+            .rank_guesses()
+            .into_iter()
+            .filter(|q| !have.contains(q.guess)),
+    );
+}
+
+// One line per guess, with every number needed to rebuild its GuessQuality. Floating-point
+// numbers print in a form that reads back as exactly the same number.
+fn format_ranking(ranked: &[GuessQuality]) -> String {
+    let mut text = format!("{}\n{}\n", CACHE_VERSION, ranked.len());
+    for q in ranked {
+        let _ = writeln!(
+            text,
+            "{} {} {} {} {} {} {} {} {}",
+            q.guess,
+            q.has_winning as u8,
+            q.expected_remaining,
+            q.max_remaining,
+            q.score_with_max_remaining,
+            q.entropy,
+            q.unique_answers,
+            q.partitions,
+            q.win_chance
+        );
+    }
+    text
+}
+
+// Read a ranking back. The guesses have to come from the solver's own list, so that they live
+// as long as it does. Anything at all out of place means the file can't be trusted.
+fn parse_ranking(text: &str, guesses: &[&'static str]) -> Option<Vec<GuessQuality<'static>>> {
+    let by_name: HashMap<&str, &'static str> = guesses.iter().map(|&w| (w, w)).collect();
+
+    let mut lines = text.lines();
+    if lines.next()? != CACHE_VERSION {
+        return None;
+    }
+    let count: usize = lines.next()?.parse().ok()?;
+    if count != guesses.len() {
+        return None;
+    }
+
+    let ranked = lines
+        .map(|line| {
+            let mut fields = line.split(' ');
+            let guess = *by_name.get(fields.next()?)?;
+            let q = GuessQuality {
+                guess,
+                has_winning: fields.next()? == "1",
+                expected_remaining: fields.next()?.parse().ok()?,
+                max_remaining: fields.next()?.parse().ok()?,
+                score_with_max_remaining: fields.next()?.parse().ok()?,
+                entropy: fields.next()?.parse().ok()?,
+                unique_answers: fields.next()?.parse().ok()?,
+                partitions: fields.next()?.parse().ok()?,
+                win_chance: fields.next()?.parse().ok()?,
+            };
+            fields.next().is_none().then_some(q)
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    (ranked.len() == count).then_some(ranked)
+}
+
+// Read a built-in ranking back, and work out the numbers for the guesses in it.
+fn parse_built_in_ranking(text: &str, solver: &Solver) -> Option<Vec<GuessQuality<'static>>> {
+    let guesses = solver.valid_guesses();
+    let by_name: HashMap<&str, &'static str> = guesses.iter().map(|&w| (w, w)).collect();
+
+    let mut lines = text.lines();
+    if lines.next()? != BUILT_IN_VERSION {
+        return None;
+    }
+    let count: usize = lines.next()?.parse().ok()?;
+    if count != guesses.len() {
+        return None;
+    }
+    let rows = lines
+        .map(|line| by_name.get(line).copied())
+        .collect::<Option<Vec<_>>>()?;
+    Some(solver.rank_some_guesses(&rows))
+}
+
+#[cfg(all(test, feature = "embedded-lists"))]
+mod tests {
+    use super::*;
+
+    // How far down the ranking by each measure the built-in rankings go.
+    const BUILT_IN_ROWS: usize = 200;
+
+    // The guesses that make it into a built-in ranking: the top BUILT_IN_ROWS by the ranking
+    // itself, by each of the other measures, and of the ones that could be the answer, in the
+    // ranking's order.
+    fn top_rows<'a>(ranked: &[GuessQuality<'a>]) -> Vec<GuessQuality<'a>> {
+        let mut keep: HashSet<&str> = HashSet::new();
+        for metric in ALL_METRICS {
+            let mut sorted = ranked.to_vec();
+            sorted.sort_by(|a, b| metric.compare(a, b));
+            keep.extend(sorted.iter().take(BUILT_IN_ROWS).map(|q| q.guess));
+        }
+        keep.extend(
+            ranked
+                .iter()
+                .filter(|q| q.has_winning)
+                .take(BUILT_IN_ROWS)
+                .map(|q| q.guess),
+        );
+        ranked
+            .iter()
+            .filter(|q| keep.contains(q.guess))
+            .copied()
+            .collect()
+    }
+
+    // Write the built-in rankings, as they'd come out now. This takes a while in a debug build,
+    // so it only runs when asked to.
+    #[test]
+    #[ignore]
+    fn write_built_in_rankings() {
+        for target_list in [TargetList::Common(5000), TargetList::Solutions] {
+            let solver = Solver::new(target_list, false);
+            let ranked = solver.rank_guesses();
+            let rows = top_rows(&ranked);
+            let mut text = format!("{}\n{}\n", BUILT_IN_VERSION, ranked.len());
+            for q in &rows {
+                let _ = writeln!(text, "{}", q.guess);
+            }
+            let parsed = parse_built_in_ranking(&text, &solver).unwrap();
+            assert!(parsed
+                .iter()
+                .map(|q| q.guess)
+                .eq(rows.iter().map(|q| q.guess)));
+
+            let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("src/cli/openings")
+                .join(format!("{:016x}.txt", ranking_key(&solver)));
+            fs::write(&path, text).unwrap();
+        }
+    }
+}
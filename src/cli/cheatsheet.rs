@@ -0,0 +1,269 @@
+use crate::cli::*;
+use clap::Args;
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use wordle_guesser::*;
+
+#[derive(Args)]
+pub struct CheatSheetArgs {
+    #[clap(flatten)]
+    pub lists: ListArgs,
+
+    /// Write the cheat sheet to this file instead of printing it. If the name ends in .pdf,
+    /// it's written as a one-page PDF, ready to print; otherwise it's plain text.
+    #[clap(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// The opening word the cheat sheet is for. The default is the best one there is.
+    pub opener: Option<String>,
+}
+
+// How many entries go side by side on each line of the sheet.
+const COLUMNS: usize = 3;
+
+// What to play second after one of the scores the opener can get.
+struct Entry {
+    score: WordScore,
+    remaining: usize,
+    next: Option<GuessQuality<'static>>,
+}
+
+// A sheet for playing away from the computer: the opener, and then for every score it can get,
+// what to play second. That's as far as a sheet of paper can reasonably go, and by the third
+// guess there are usually few enough words left to work out the rest in your head.
+pub fn run(args: &CheatSheetArgs, quiet: bool) -> Result<Outcome> {
+    let solver = args.lists.solver()?;
+    let opener = match &args.opener {
+        Some(word) => {
+            let word = word.to_lowercase();
+            match solver.valid_guesses().iter().find(|&&g| g == word) {
+                Some(&opener) => opener,
+                None => {
+                    eprintln!("'{}' isn't a valid guess.", word);
+                    return Ok(Outcome::InvalidInput);
+                }
+            }
+        }
+        None => match cache::rank_guesses(&solver).first() {
+            Some(q) => q.guess,
+            None => {
+                eprintln!("There aren't any guesses to open with.");
+                return Ok(Outcome::InvalidInput);
+            }
+        },
+    };
+
+    // The suggestions always fit hard mode's rules, but a sheet that breaks them is no use at
+    // all, so every one of them is checked to be sure.
+    let violations = Mutex::new(Vec::new());
+    let mut groups: BTreeMap<WordScore, usize> = BTreeMap::new();
+    for target in solver.remaining_targets() {
+        *groups.entry(solver.score(opener, target)).or_default() += 1;
+    }
+    let mut entries = groups
+        .into_par_iter()
+        .map(|(score, remaining)| {
+            let next = if score == ALL_GREEN {
+                None
+            } else {
+                let mut next = solver.clone();
+                next.apply_guess(opener, score)?;
+                let best = next.best_guess();
+                if let Some(q) = &best {
+                    if let Some(reason) = next.hard_mode_violation(q.guess) {
+                        violations.lock().unwrap().push(HardModeViolation {
+                            history: vec![(opener, score)],
+                            guess: q.guess,
+                            reason,
+                        });
+                    }
+                }
+                best
+            };
+            Ok(Entry {
+                score,
+                remaining,
+                next,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // The scores go in the order you'd look them up in: reading left to right, with gray before
+    // yellow before green, like words in a dictionary.
+    entries.sort_by_key(|e| format_score(e.score).replace('G', "z"));
+
+    warn_about_hard_mode("the cheat sheet", &violations.into_inner().unwrap());
+
+    let lines = render_sheet(opener, &solver, &entries);
+    match &args.output {
+        None => {
+            for line in &lines {
+                outln!("{}", line);
+            }
+        }
+        Some(path) => {
+            let is_pdf = path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("pdf"));
+            let contents = if is_pdf {
+                render_pdf(&lines)
+            } else {
+                lines
+                    .iter()
+                    .map(|line| format!("{}\n", line))
+                    .collect::<String>()
+                    .into_bytes()
+            };
+            fs::write(path, contents).map_err(|e| {
+                io::Error::new(
+                    e.kind(),
+                    format!("couldn't write {}: {}", path.display(), e),
+                )
+            })?;
+            if !quiet {
+                outln!(
+                    "Wrote the cheat sheet for {} to {}.",
+                    opener.to_uppercase(),
+                    path.display()
+                );
+            }
+        }
+    }
+    Ok(Outcome::Solved)
+}
+
+// The sheet as lines of text, with the entries in columns that read downwards, so that the
+// scores are in order down each column like a phone book.
+fn render_sheet(opener: &str, solver: &Solver, entries: &[Entry]) -> Vec<String> {
+    let mut lines = vec![
+        format!(
+            "WORDLE CHEAT SHEET: open with {}{}",
+            opener.to_uppercase(),
+            describe_hard_mode(solver)
+        ),
+        String::new(),
+        "Find the score you got, and play the word next to it. The number after it is how"
+            .to_string(),
+        format!(
+            "many of the {} possible answers are left, and a * means the word might be the",
+            solver.remaining_targets().len()
+        ),
+        "answer itself. A score that isn't here can't happen, so check the colors again."
+            .to_string(),
+        String::new(),
+    ];
+
+    let cells = entries
+        .iter()
+        .map(|e| {
+            let next = match &e.next {
+                None => "you won!".to_string(),
+                Some(q) if e.remaining == 1 => q.guess.to_uppercase(),
+                Some(q) => format!(
+                    "{}{} {}",
+                    q.guess.to_uppercase(),
+                    if q.has_winning { "*" } else { " " },
+                    e.remaining
+                ),
+            };
+            format!("{}  {}", format_score(e.score), next)
+        })
+        .collect::<Vec<_>>();
+
+    let rows = cells.len().div_ceil(COLUMNS);
+    for row in 0..rows {
+        let mut line = String::new();
+        for cell in cells.iter().skip(row).step_by(rows.max(1)) {
+            let _ = write!(line, "{:<26}", cell);
+        }
+        lines.push(line.trim_end().to_string());
+    }
+    lines
+}
+
+// The page layout for the PDF, in points: US Letter, with the text in a fixed-width font so the
+// columns line up the same as in the text version.
+const PAGE_WIDTH: u32 = 612;
+const PAGE_HEIGHT: u32 = 792;
+const MARGIN: u32 = 54;
+const FONT_SIZE: u32 = 9;
+const LINE_HEIGHT: u32 = 11;
+
+// A bare-bones PDF of the lines of text, one or more pages of it. A PDF is a list of numbered
+// objects and a table of where each one starts in the file, and for plain text in one of the
+// fonts every PDF reader has built in, that's all it takes; there's no need for a library.
+fn render_pdf(lines: &[String]) -> Vec<u8> {
+    let lines_per_page = ((PAGE_HEIGHT - 2 * MARGIN) / LINE_HEIGHT) as usize;
+    let pages = lines.chunks(lines_per_page.max(1)).collect::<Vec<_>>();
+
+    // Objects 1 and 2 are the catalog and the page list, 3 is the font, and then each page has
+    // two: the page itself and the text drawn on it.
+    let page_ids = (0..pages.len()).map(|i| 4 + 2 * i).collect::<Vec<_>>();
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids
+                .iter()
+                .map(|id| format!("{} 0 R", id))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Courier >>".to_string(),
+    ];
+    for (page, &id) in pages.iter().zip(&page_ids) {
+        let mut text = format!(
+            "BT /F1 {} Tf {} TL {} {} Td\n",
+            FONT_SIZE,
+            LINE_HEIGHT,
+            MARGIN,
+            PAGE_HEIGHT - MARGIN - FONT_SIZE
+        );
+        for line in page.iter() {
+            let escaped = line
+                .replace('\\', "\\\\")
+                .replace('(', "\\(")
+                .replace(')', "\\)");
+            let _ = writeln!(text, "({}) Tj T*", escaped);
+        }
+        text.push_str("ET");
+
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH,
+            PAGE_HEIGHT,
+            id + 1
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}\nendstream",
+            text.len(),
+            text
+        ));
+    }
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        let _ = write!(pdf, "{} 0 obj\n{}\nendobj\n", i + 1, object);
+    }
+    let xref = pdf.len();
+    let _ = write!(pdf, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(pdf, "{:010} 00000 n ", offset);
+    }
+    let _ = write!(
+        pdf,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    );
+    pdf.into_bytes()
+}
@@ -0,0 +1,106 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use wordle_guesser::*;
+
+// A file that results get added to as soon as they're worked out, one line each, so that a run
+// that takes hours can be stopped (or lose its computer to a reboot) and picked up again later
+// without starting over. The first line says what run the results belong to, so results for
+// some other word list or settings don't get mixed in by mistake.
+pub struct Checkpoint {
+    file: File,
+}
+
+impl Checkpoint {
+    // Start a new checkpoint file, or with `resume`, carry on with the one that's there, and
+    // hand back the lines already saved in it. Resuming from a file that doesn't exist yet just
+    // starts a new one, so it's always safe to ask to resume.
+    //
+    // Starting over on top of results from this same run would throw away what could be days
+    // of work over a forgotten --resume, so that takes `force`. Results from some other run
+    // are fair game, the same as any other output file.
+    pub fn open(
+        path: &Path,
+        header: &str,
+        resume: bool,
+        force: bool,
+    ) -> Result<(Checkpoint, Vec<String>)> {
+        let header = format!("# {}", header);
+        let annotate =
+            |e: io::Error| io::Error::new(e.kind(), format!("{}: {}", path.display(), e));
+
+        let mut saved = Vec::new();
+        if !resume && !force {
+            if let Ok(text) = fs::read_to_string(path) {
+                let mut lines = text.lines();
+                let results = match lines.next() {
+                    Some(first) if first == header => {
+                        lines.filter(|line| !line.trim().is_empty()).count()
+                    }
+                    _ => 0,
+                };
+                if results > 0 {
+                    return Err(Error::Config(format!(
+                        "{} already has results from this run ({} saved); add --resume to carry on from them, or --force to start over",
+                        path.display(),
+                        results
+                    )));
+                }
+            }
+        }
+        if resume {
+            match fs::read_to_string(path) {
+                Ok(text) => {
+                    let mut lines = text.lines();
+                    if lines.next() != Some(header.as_str()) {
+                        return Err(Error::Checkpoint(format!(
+                            "{} is from a different run; it doesn't start with \"{}\"",
+                            path.display(),
+                            header
+                        )));
+                    }
+                    saved = lines
+                        .filter(|line| !line.trim().is_empty())
+                        .map(str::to_string)
+                        .collect();
+
+                    // If the run was stopped partway through writing a line, that line is
+                    // thrown away and worked out again.
+                    if !text.ends_with('\n') {
+                        saved.pop();
+                    }
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+                Err(e) => return Err(annotate(e).into()),
+            }
+        }
+
+        // The file is written afresh even when resuming, to get rid of any half-written line.
+        // It goes to a new file first, so that there's never a moment when the old results
+        // aren't on disk somewhere.
+        let new_path = path.with_extension("new");
+        let mut file = File::create(&new_path).map_err(annotate)?;
+        writeln!(file, "{}", header)?;
+        for line in &saved {
+            writeln!(file, "{}", line)?;
+        }
+        file.sync_all()?;
+        drop(file);
+        fs::rename(&new_path, path).map_err(annotate)?;
+
+        let file = OpenOptions::new()
+            .append(true)
+            .open(path)
+            .map_err(annotate)?;
+        Ok((Checkpoint { file }, saved))
+    }
+
+    // Add some results to the file, and make sure they've made it to the disk.
+    pub fn save(&mut self, lines: impl IntoIterator<Item = String>) -> Result<()> {
+        for line in lines {
+            writeln!(self.file, "{}", line)?;
+        }
+        self.file.sync_data()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,91 @@
+use crate::cli::ColorChoice;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use wordle_guesser::{Error, Result};
+
+// Persistent preferences, so you don't have to retype the same flags every day. These are
+// loaded from ~/.config/wordleguesser.toml (or wherever --config points), and look like:
+//
+//     hard = true
+//     common = 8000
+//     suggestions = 25
+//     strategy = "entropy"
+//     color = "never"
+//     answers_url = "https://example.com/answers.txt"
+//
+// Every setting is optional. Anything given on the command line takes priority over the file,
+// and the switches have --no-* versions for turning off what the file turns on.
+#[derive(Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub hard: Option<bool>,
+    pub hard_after: Option<usize>,
+    pub common: Option<u32>,
+    pub solutions: Option<bool>,
+    pub fold_accents: Option<bool>,
+    pub weighted: Option<bool>,
+    pub suggestions: Option<usize>,
+    pub suggest_filter: Option<String>,
+    pub easy_typing: Option<bool>,
+    pub soft_hard: Option<u32>,
+
+    // The same as --strategy, --color, and --fold-accents.
+    pub strategy: Option<String>,
+    pub color: Option<ColorChoice>,
+    pub language: Option<Language>,
+
+    // Where the fetch subcommand downloads the word lists from.
+    pub guesses_url: Option<String>,
+    pub answers_url: Option<String>,
+}
+
+// The language of the word lists, for lists put in place of the built-in English ones. The only
+// thing it changes is whether accents count: Spanish, French, and Portuguese versions of Wordle
+// treat an accented letter the same as the plain one, the same as --fold-accents.
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    English,
+    Spanish,
+    French,
+    Portuguese,
+}
+
+impl Language {
+    pub fn folds_accents(self) -> bool {
+        self != Language::English
+    }
+}
+
+// Where the config file lives if you don't say otherwise. We follow the XDG convention of
+// ~/.config on every platform, since that's where most command-line tools look.
+pub fn default_config_path() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+    Some(
+        PathBuf::from(home)
+            .join(".config")
+            .join("wordleguesser.toml"),
+    )
+}
+
+// Read and parse a config file. A missing file is only an error if the user explicitly asked
+// for it; the default location is allowed to not exist, since most people won't have one.
+pub fn load_config(path: &Path, must_exist: bool) -> Result<Config> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && !must_exist => {
+            return Ok(Config::default());
+        }
+        Err(e) => {
+            return Err(Error::Config(format!(
+                "couldn't read {}: {}",
+                path.display(),
+                e
+            )))
+        }
+    };
+
+    toml::from_str(&text)
+        .map_err(|e| Error::Config(format!("couldn't parse {}: {}", path.display(), e)))
+}
@@ -0,0 +1,273 @@
+use crate::cli::dashboard::{describe, game_state, RequestTurn, StateRequest};
+use crate::cli::json::StateResponse;
+use crate::cli::*;
+use clap::Args;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+// Starting up takes a moment: the word lists get sorted out, and the first ranking for anything
+// but the usual lists has to be worked out. For one game that's nothing, but a script or an
+// editor plugin that asks after every guess pays it every time. So the daemon does all that
+// once and stays running, and `ask` hands it the game so far over a unix socket, and gets the
+// answer straight back.
+//
+// The conversation is a line of JSON each way, the same as the dashboard's /api/state: the
+// turns so far in, the possible answers and the suggestions out. A connection can ask as many
+// times as it likes before it hangs up, and each one has a thread of its own, so one that's
+// kept open (by an editor, say) doesn't keep anyone else waiting.
+
+// Answers already worked out, kept for when the same game's asked about again, up to this many.
+// After that the lot is thrown away and it starts over, which is simpler than working out which
+// to keep, and the usual questions are back again soon enough.
+const MAX_REMEMBERED: usize = 10_000;
+
+#[derive(Args)]
+pub struct DaemonArgs {
+    #[clap(flatten)]
+    pub lists: ListArgs,
+
+    #[clap(flatten)]
+    pub memory: MemoryArgs,
+
+    /// Listen on this socket, instead of the usual one.
+    #[clap(long, value_name = "PATH")]
+    pub socket: Option<PathBuf>,
+}
+
+#[derive(Args)]
+pub struct AskArgs {
+    /// The guesses so far and their scores, like: crane .y..G slate ..GG.
+    #[clap(value_name = "GUESS SCORE")]
+    pub turns: Vec<String>,
+
+    /// How many suggestions to show.
+    #[clap(short = 'n', long, default_value = "10")]
+    pub suggestions: usize,
+
+    /// Print the daemon's answer as it is, in JSON.
+    #[clap(long)]
+    pub json: bool,
+
+    /// Ask the daemon on this socket, instead of the usual one.
+    #[clap(long, value_name = "PATH")]
+    pub socket: Option<PathBuf>,
+}
+
+// Where the daemon listens if it's not told otherwise: the runtime directory if there is one,
+// since that's what it's for, and otherwise next to the downloaded lists.
+fn default_socket_path() -> Option<PathBuf> {
+    match std::env::var_os("XDG_RUNTIME_DIR") {
+        Some(dir) if !dir.is_empty() => Some(PathBuf::from(dir).join("wordleguesser.sock")),
+        _ => overlay::overlay_dir().map(|dir| dir.join("daemon.sock")),
+    }
+}
+
+fn socket_path(socket: &Option<PathBuf>) -> Result<PathBuf> {
+    socket.clone().or_else(default_socket_path).ok_or_else(|| {
+        Error::Config("couldn't find your home directory to put the socket in".to_string())
+    })
+}
+
+// Get everything ready, and then answer questions until stopped.
+pub fn run(args: &DaemonArgs, quiet: bool) -> Result<Outcome> {
+    let path = socket_path(&args.socket)?;
+
+    // A socket left behind by a daemon that didn't get to clean up is in the way, but one with
+    // a daemon still behind it isn't ours to take.
+    if path.exists() {
+        if UnixStream::connect(&path).is_ok() {
+            return Err(Error::Config(format!(
+                "there's already a daemon listening on {}",
+                path.display()
+            )));
+        }
+        fs::remove_file(&path)?;
+    }
+
+    let mut start = args.lists.solver()?;
+    args.memory.precompute(&mut start, quiet)?;
+    // The first turn's ranking is the slow one, and the one every game asks for.
+    cache::rank_guesses(&start);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let listener = UnixListener::bind(&path).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("couldn't listen on {}: {}", path.display(), e),
+        )
+    })?;
+    if !quiet {
+        outln!("Listening on {} -- press Ctrl+C to stop.", path.display());
+    }
+
+    let remembered = Mutex::new(HashMap::new());
+    std::thread::scope(|scope| {
+        for stream in listener.incoming() {
+            // One bad connection shouldn't take the whole daemon down.
+            match stream {
+                Ok(stream) => {
+                    let (start, remembered) = (&start, &remembered);
+                    scope.spawn(move || {
+                        if let Err(e) = handle_connection(stream, start, remembered) {
+                            eprintln!("{}", e);
+                        }
+                    });
+                }
+                Err(e) => eprintln!("{}", e),
+            }
+        }
+    });
+    Ok(Outcome::Solved)
+}
+
+fn handle_connection(
+    stream: UnixStream,
+    start: &Solver,
+    remembered: &Mutex<HashMap<String, String>>,
+) -> io::Result<()> {
+    let mut writer = stream.try_clone()?;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let json = match serde_json::from_str::<StateRequest>(&line) {
+            Ok(request) => {
+                // The same game typed a little differently is still the same game. The same
+                // turns under another rule for repeated letters aren't, though.
+                let key = request
+                    .turns
+                    .iter()
+                    .map(|t| format!("{} {}", t.guess.trim().to_lowercase(), t.score.trim()))
+                    .chain(request.duplicates.iter().map(|d| d.trim().to_lowercase()))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                // The lock isn't held while the answer's worked out, so other connections
+                // aren't kept waiting on it. Two asking the same new question at once just
+                // both work it out.
+                let known = remembered.lock().unwrap().get(&key).cloned();
+                match known {
+                    Some(json) => json,
+                    None => {
+                        let json = serde_json::to_string(&game_state(start, &request))
+                            .expect("the state is always valid JSON");
+                        let mut remembered = remembered.lock().unwrap();
+                        if remembered.len() >= MAX_REMEMBERED {
+                            remembered.clear();
+                        }
+                        remembered.insert(key, json.clone());
+                        json
+                    }
+                }
+            }
+            Err(e) => serde_json::to_string(&StateResponse {
+                error: Some(format!("The request didn't make sense: {}", e)),
+                ..describe(start)
+            })
+            .expect("the state is always valid JSON"),
+        };
+        writeln!(writer, "{}", json)?;
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+// Ask a running daemon about a game, and print what it says.
+pub fn ask(args: &AskArgs, quiet: bool) -> Result<Outcome> {
+    if !args.turns.len().is_multiple_of(2) {
+        eprintln!("Each guess needs a score after it, like: crane .y..G slate ..GG.");
+        return Ok(Outcome::InvalidInput);
+    }
+    let request = StateRequest {
+        turns: args
+            .turns
+            .chunks(2)
+            .map(|turn| RequestTurn {
+                guess: turn[0].clone(),
+                score: turn[1].clone(),
+            })
+            .collect(),
+        // Otherwise it's whatever rule the daemon was started with.
+        duplicates: (default_duplicate_policy() == DuplicatePolicy::AllYellow)
+            .then(|| "all-yellow".to_string()),
+    };
+
+    let path = socket_path(&args.socket)?;
+    let mut stream = UnixStream::connect(&path).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!(
+                "couldn't reach the daemon at {}: {} (is 'wordle_guesser daemon' running?)",
+                path.display(),
+                e
+            ),
+        )
+    })?;
+    writeln!(
+        stream,
+        "{}",
+        serde_json::to_string(&request).expect("the request is always valid JSON")
+    )?;
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+
+    if args.json {
+        out!("{}", line);
+        return Ok(Outcome::Solved);
+    }
+    let answer = serde_json::from_str::<StateResponse>(&line).map_err(|e| {
+        Error::Config(format!(
+            "the daemon's answer didn't make sense: {} ({})",
+            e,
+            line.trim()
+        ))
+    })?;
+    if let Some(error) = &answer.error {
+        eprintln!("{}", error);
+        return Ok(Outcome::InvalidInput);
+    }
+    if quiet {
+        if let Some(top) = answer.suggestions.first() {
+            outln!("{}", top.guess);
+        }
+        return Ok(Outcome::Solved);
+    }
+    print_answer(&answer, args.suggestions)?;
+    Ok(Outcome::Solved)
+}
+
+fn print_answer(answer: &StateResponse, num_shown: usize) -> Result<()> {
+    match answer.candidates_count {
+        0 => outln!("None of the words fit those scores."),
+        1 => outln!("The answer is {}.", answer.candidates[0]),
+        n => {
+            outln!("{} possible answers. The top suggestions:", n);
+            for s in answer.suggestions.iter().take(num_shown) {
+                if plain_output() {
+                    outln!(
+                        "{}{}: average {:.1} left, at most {} left",
+                        s.guess,
+                        if s.could_win { ", could win" } else { "" },
+                        s.expected_remaining,
+                        s.max_remaining
+                    );
+                } else {
+                    outln!(
+                        "{} {} | average {:.1} left, max {} left",
+                        if s.could_win { '*' } else { ' ' },
+                        s.guess,
+                        s.expected_remaining,
+                        s.max_remaining
+                    );
+                }
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,236 @@
+use crate::cli::json::*;
+use crate::cli::*;
+use clap::{ArgEnum, Args};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, BufReader};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+#[derive(Args)]
+pub struct DashboardArgs {
+    #[clap(flatten)]
+    pub lists: ListArgs,
+
+    /// The port to serve the dashboard on. It only listens on this computer, at
+    /// http://localhost:PORT.
+    #[clap(long, default_value = "8315")]
+    pub port: u16,
+}
+
+// The page itself: a board to fill in, the suggestions, and the possible answers. It's built
+// into the program, so there's nothing to install.
+const PAGE: &str = include_str!("dashboard.html");
+
+// How many of the suggestions and possible answers go back to the page each time.
+const SUGGESTIONS_SENT: usize = 15;
+const CANDIDATES_SENT: usize = 300;
+
+// Requests bigger than this aren't from the page, and get turned away. That goes for the
+// request line and headers together, too, which a browser keeps well under this.
+const MAX_REQUEST_BODY: usize = 64 * 1024;
+const MAX_HEADER_BYTES: u64 = 16 * 1024;
+
+// How long a connection can sit without sending anything before it's dropped. Browsers open
+// connections ahead of time in case they need them, and might never send anything on them.
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+// What the page sends: the whole board so far. Every request carries the whole game, so the
+// server doesn't need to remember anything between them, and editing an earlier row just works.
+// The daemon takes the same requests.
+#[derive(Serialize, Deserialize)]
+pub struct StateRequest {
+    pub turns: Vec<RequestTurn>,
+
+    // The rule for repeated letters in this game, "standard" or "all-yellow", if it isn't the
+    // one the server was started with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicates: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RequestTurn {
+    pub guess: String,
+    pub score: String,
+}
+
+// The same solver as the terminal, behind a web page: serve the page on this computer, and
+// answer its requests for the suggestions after each change to the board. This is a plain
+// HTTP/1.1 server on a TcpListener, one request per connection, with each connection on a
+// thread of its own so that one that's slow to send its request doesn't hold up the rest.
+pub fn run(args: &DashboardArgs, quiet: bool) -> Result<Outcome> {
+    let start = args.lists.solver()?;
+    let listener = TcpListener::bind(("127.0.0.1", args.port)).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("couldn't listen on port {}: {}", args.port, e),
+        )
+    })?;
+    if !quiet {
+        outln!(
+            "The dashboard is at http://localhost:{}/ -- press Ctrl+C to stop it.",
+            args.port
+        );
+    }
+
+    std::thread::scope(|scope| {
+        for stream in listener.incoming() {
+            // One bad connection shouldn't take the whole dashboard down.
+            match stream {
+                Ok(stream) => {
+                    let start = &start;
+                    scope.spawn(move || {
+                        if let Err(e) = handle_connection(stream, start) {
+                            eprintln!("{}", e);
+                        }
+                    });
+                }
+                Err(e) => eprintln!("{}", e),
+            }
+        }
+    });
+    Ok(Outcome::Solved)
+}
+
+fn handle_connection(mut stream: TcpStream, start: &Solver) -> io::Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+
+    // The timeout is for each read, so a client sending a byte at a time could keep a line
+    // going forever. Everything up to the body has to fit in MAX_HEADER_BYTES instead.
+    let mut head = reader.by_ref().take(MAX_HEADER_BYTES);
+    let mut request_line = String::new();
+    head.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if head.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    if head.limit() == 0 {
+        return respond(
+            &mut stream,
+            "431 Request Header Fields Too Large",
+            "text/plain",
+            "Too big.",
+        );
+    }
+    if content_length > MAX_REQUEST_BODY {
+        return respond(
+            &mut stream,
+            "413 Payload Too Large",
+            "text/plain",
+            "Too big.",
+        );
+    }
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body)?;
+
+    match (method.as_str(), path.as_str()) {
+        ("GET", "/") => respond(&mut stream, "200 OK", "text/html; charset=utf-8", PAGE),
+        ("POST", "/api/state") => {
+            let response = match serde_json::from_slice::<StateRequest>(&body) {
+                Ok(request) => game_state(start, &request),
+                Err(e) => StateResponse {
+                    error: Some(format!("The request didn't make sense: {}", e)),
+                    ..describe(start)
+                },
+            };
+            let json = serde_json::to_string(&response).expect("the state is always valid JSON");
+            respond(&mut stream, "200 OK", "application/json", &json)
+        }
+        _ => respond(&mut stream, "404 Not Found", "text/plain", "Not found."),
+    }
+}
+
+fn respond(stream: &mut TcpStream, status: &str, content_type: &str, body: &str) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+// Play the page's board from the start, the same checks as typing it into the terminal, and
+// describe where it leaves the game.
+pub fn game_state(start: &Solver, request: &StateRequest) -> StateResponse {
+    let mut solver = start.clone();
+    if let Some(rule) = &request.duplicates {
+        match Duplicates::from_str(rule, true) {
+            Ok(rule) => solver.set_duplicate_policy(rule.policy()),
+            Err(_) => {
+                return StateResponse {
+                    error: Some(format!(
+                    "'{}' isn't a rule for repeated letters: it's \"standard\" or \"all-yellow\".",
+                    rule
+                )),
+                    ..describe(start)
+                }
+            }
+        }
+    }
+    for turn in &request.turns {
+        let guess = turn.guess.trim().to_lowercase();
+        // In hard mode the solver's own list of guesses shrinks as it goes, so words are
+        // looked up in the list it started with.
+        let error = if let Some(reason) = solver.hard_mode_violation(&guess) {
+            Some(format!(
+                "The game won't take '{}' in hard mode: {}.",
+                guess, reason
+            ))
+        } else if !start.valid_guesses().contains(&guess.as_str()) {
+            Some(format!("'{}' isn't a valid guess.", guess))
+        } else {
+            parse_score(&turn.score)
+                .and_then(|score| solver.apply_guess(&guess, score))
+                .err()
+                .map(|e| e.to_string())
+        };
+        if let Some(error) = error {
+            return StateResponse {
+                error: Some(error),
+                ..describe(&solver)
+            };
+        }
+    }
+    describe(&solver)
+}
+
+// Where the game stands, with no error. The daemon answers requests that don't make sense with
+// this for the start of the game, the same as the page gets.
+pub fn describe(solver: &Solver) -> StateResponse {
+    let candidates = solver.remaining_targets();
+    let suggestions = if candidates.is_empty() {
+        Vec::new()
+    } else {
+        cache::rank_guesses(solver)
+            .iter()
+            .take(SUGGESTIONS_SENT)
+            .map(JsonSuggestion::from)
+            .collect()
+    };
+    StateResponse {
+        schema_version: SCHEMA_VERSION,
+        candidates_count: candidates.len(),
+        candidates: candidates
+            .iter()
+            .take(CANDIDATES_SENT)
+            .map(|w| w.to_string())
+            .collect(),
+        suggestions,
+        error: None,
+    }
+}
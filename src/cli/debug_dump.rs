@@ -0,0 +1,116 @@
+use crate::cli::json::*;
+use crate::cli::solve::SolveArgs;
+use crate::cli::*;
+use serde::Serialize;
+use serde_json::json;
+use std::fs;
+use std::io;
+use std::path::Path;
+use wordle_guesser::*;
+
+// Everything about a solve session that someone would need to see exactly what happened, for
+// attaching to a bug report: the command line and the settings it came to, every line that
+// was typed in, and for each turn, how many words there were, what was suggested, and what was
+// played. Feeding the same lines back in with the same options plays the session out again.
+// See json.rs for the promises made about its layout.
+#[derive(Serialize)]
+pub struct DebugDump {
+    schema_version: u32,
+    version: &'static str,
+    command_line: Vec<String>,
+    options: serde_json::Value,
+    inputs: Vec<String>,
+    turns: Vec<JsonTurn>,
+
+    // How it ended: the exit code, or the error that stopped it.
+    outcome: Option<i32>,
+    error: Option<String>,
+}
+
+// How many of the suggestions are kept for each turn.
+const DUMPED_SUGGESTIONS: usize = 10;
+
+impl DebugDump {
+    // Start recording a session, including every line read from here on.
+    pub fn start(args: &SolveArgs) -> DebugDump {
+        start_input_log();
+
+        // These are the settings after the profile and the config file have had their say,
+        // which the command line alone doesn't show.
+        let options = json!({
+            "hard": args.lists.hard,
+            "hard_after": args.lists.hard_after,
+            "common": args.lists.common,
+            "solutions": args.lists.solutions,
+            "kids": args.lists.kids,
+            "fold_accents": args.lists.fold_accents,
+            "weighted": args.lists.weighted,
+            "duplicates": match default_duplicate_policy() {
+                DuplicatePolicy::Standard => "standard",
+                DuplicatePolicy::AllYellow => "all-yellow",
+            },
+            "suggestions": args.suggestions,
+            "no_spoilers": args.no_spoilers,
+            "strategy": args.strategy,
+            "suggest_filter": args.suggest_filter,
+            "pareto": args.pareto,
+            "easy_typing": args.easy_typing,
+            "soft_hard": args.soft_hard,
+            "no_cache": args.no_cache,
+        });
+
+        DebugDump {
+            schema_version: SCHEMA_VERSION,
+            version: env!("CARGO_PKG_VERSION"),
+            command_line: std::env::args().collect(),
+            options,
+            inputs: Vec::new(),
+            turns: Vec::new(),
+            outcome: None,
+            error: None,
+        }
+    }
+
+    // Record a turn that was played: the guess and its score, and what was suggested before it.
+    pub fn record_turn(
+        &mut self,
+        game: &str,
+        candidates_before: usize,
+        candidates_after: usize,
+        ranked: &[GuessQuality<'static>],
+        guess: &str,
+        score: WordScore,
+    ) {
+        self.turns.push(JsonTurn {
+            game: game.to_string(),
+            candidates_before,
+            candidates_after,
+            top_suggestions: ranked
+                .iter()
+                .take(DUMPED_SUGGESTIONS)
+                .map(JsonSuggestion::from)
+                .collect(),
+            guess: guess.to_string(),
+            score: format_score(score).to_string(),
+        });
+    }
+
+    // Write the dump out, with how the session ended. This happens even when it ended in an
+    // error, since that's when it's wanted most.
+    pub fn write(mut self, path: &Path, result: &Result<Outcome>) -> Result<()> {
+        self.inputs = take_input_log();
+        match result {
+            Ok(outcome) => self.outcome = Some(*outcome as i32),
+            Err(e) => self.error = Some(e.to_string()),
+        }
+
+        let text = serde_json::to_string_pretty(&self).expect("the dump is always valid JSON");
+        fs::write(path, text + "\n").map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("couldn't write {}: {}", path.display(), e),
+            )
+        })?;
+        Ok(())
+    }
+}
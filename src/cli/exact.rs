@@ -0,0 +1,203 @@
+use crate::cli::*;
+use checkpoint::Checkpoint;
+use clap::Args;
+use rayon::prelude::*;
+use std::collections::HashSet;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Instant;
+use wordle_guesser::*;
+
+#[derive(Args)]
+pub struct ExactArgs {
+    #[clap(flatten)]
+    pub lists: ListArgs,
+
+    /// Write each opener's result to this file as soon as it's worked out, one per line: the
+    /// word, the total number of guesses to find every answer, and the average.
+    #[clap(long, value_name = "FILE", default_value = "openers.txt")]
+    pub output: PathBuf,
+
+    /// Only try this many of the most promising guesses at each step after the opener. This is
+    /// much faster, but then each result is only the best those guesses can do, which might
+    /// not be the best there is.
+    #[clap(long, value_name = "N")]
+    pub breadth: Option<usize>,
+
+    /// Carry on from the results already in the output file, from a run that was stopped
+    /// before it finished, instead of starting over.
+    #[clap(long)]
+    pub resume: bool,
+
+    /// Start over even if the output file already has results from this same run, throwing
+    /// them away.
+    #[clap(long, conflicts_with = "resume")]
+    pub force: bool,
+
+    /// How many of the best openers to list at the end.
+    #[clap(short = 'n', long, value_name = "N", default_value = "15")]
+    pub shown: usize,
+
+    /// Only work out these openers, instead of every valid guess.
+    pub words: Vec<String>,
+}
+
+// Work out exactly how few guesses each opener takes on average with perfect play after it,
+// for every valid guess (or just the ones asked for), and write them to a file as they finish.
+// The most promising openers go first, so the interesting part of the table fills in early.
+// This can take days for every guess on the full lists, so having the results on disk as they
+// come in means nothing is lost by stopping it early, and --resume carries on from there.
+pub fn run(args: &ExactArgs, quiet: bool) -> Result<Outcome> {
+    if args.lists.is_hard() {
+        eprintln!("The exact search doesn't know about hard mode's rules yet.");
+        return Ok(Outcome::InvalidInput);
+    }
+
+    let solver = args.lists.solver()?;
+    let openers: Vec<&str> = if args.words.is_empty() {
+        solver.rank_guesses().iter().map(|q| q.guess).collect()
+    } else {
+        let words = args
+            .words
+            .iter()
+            .map(|w| w.to_lowercase())
+            .collect::<Vec<_>>();
+        match words
+            .iter()
+            .find(|w| !solver.valid_guesses().contains(&w.as_str()))
+        {
+            Some(word) => {
+                eprintln!("'{}' isn't a valid guess.", word);
+                return Ok(Outcome::InvalidInput);
+            }
+            None => solver
+                .valid_guesses()
+                .iter()
+                .copied()
+                .filter(|g| words.iter().any(|w| w == g))
+                .collect(),
+        }
+    };
+
+    let num_answers = solver.remaining_targets().len();
+    let header = format!(
+        "{} openers against {} answers{}",
+        openers.len(),
+        num_answers,
+        match args.breadth {
+            Some(breadth) => format!(", trying {} guesses at each step after", breadth),
+            None => String::new(),
+        }
+    );
+    let (checkpoint, saved) = Checkpoint::open(&args.output, &header, args.resume, args.force)?;
+    let mut values = saved
+        .iter()
+        .map(|line| parse_saved_value(line, &openers, num_answers))
+        .collect::<Result<Vec<_>>>()?;
+    let finished = values.iter().map(|v| v.opener).collect::<HashSet<_>>();
+    let remaining = openers
+        .iter()
+        .filter(|opener| !finished.contains(*opener))
+        .collect::<Vec<_>>();
+
+    if !quiet {
+        if !values.is_empty() {
+            outln!(
+                "Picking up from the {} openers already in {}.",
+                values.len(),
+                args.output.display()
+            );
+        }
+        outln!(
+            "Working out {} openers against {} answers, writing each one to {} as it's done.\n",
+            remaining.len(),
+            num_answers,
+            args.output.display()
+        );
+    }
+
+    let search = ExactSearch::new(&solver, args.breadth);
+    let start = Instant::now();
+    let checkpoint = Mutex::new(checkpoint);
+    let done = Mutex::new(0usize);
+    let new_values = remaining
+        .par_iter()
+        .map(|opener| {
+            let value = search
+                .opener_value(opener)
+                .expect("the openers are all valid guesses");
+
+            checkpoint.lock().unwrap().save([format!(
+                "{} {} {:.4}",
+                value.opener,
+                value.total_guesses,
+                value.average_guesses()
+            )])?;
+
+            let mut done = done.lock().unwrap();
+            *done += 1;
+            if !quiet {
+                outln!(
+                    "{}  {:.4}  ({} of {}, {:.1} seconds so far)",
+                    value.opener,
+                    value.average_guesses(),
+                    *done,
+                    remaining.len(),
+                    start.elapsed().as_secs_f64()
+                );
+            }
+            Ok(value)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    values.extend(new_values);
+
+    values.sort_by(|a, b| {
+        a.total_guesses
+            .cmp(&b.total_guesses)
+            .then(a.opener.cmp(b.opener))
+    });
+    if !quiet {
+        outln!(
+            "\nThe best openers{}:\n",
+            if args.breadth.is_some() {
+                ", as far as the search went"
+            } else {
+                ", with perfect play after them"
+            }
+        );
+    }
+    for value in values.iter().take(args.shown) {
+        outln!(
+            "{}  {:.4}  ({} guesses in total)",
+            value.opener,
+            value.average_guesses(),
+            value.total_guesses
+        );
+    }
+    Ok(Outcome::Solved)
+}
+
+// Read back one line of the output file, as written above.
+fn parse_saved_value(
+    line: &str,
+    openers: &[&'static str],
+    num_answers: usize,
+) -> Result<OpenerValue> {
+    let bad_line = || Error::Checkpoint(format!("the saved line '{}' doesn't make sense", line));
+    let mut fields = line.split_whitespace();
+    let word = fields.next().ok_or_else(bad_line)?;
+    let opener = openers
+        .iter()
+        .copied()
+        .find(|o| *o == word)
+        .ok_or_else(bad_line)?;
+    let total_guesses = fields
+        .next()
+        .and_then(|total| total.parse().ok())
+        .ok_or_else(bad_line)?;
+    Ok(OpenerValue {
+        opener,
+        total_guesses,
+        num_answers,
+    })
+}
@@ -0,0 +1,115 @@
+use crate::cli::overlay::*;
+use crate::cli::*;
+use clap::Args;
+use std::fs;
+use std::io;
+
+// Where the lists come from if neither the command line nor the config file says otherwise.
+// These are community-maintained copies of the lists from Wordle's own code.
+pub const DEFAULT_GUESSES_URL: &str =
+    "https://raw.githubusercontent.com/tabatkins/wordle-list/main/words";
+pub const DEFAULT_ANSWERS_URL: &str = "https://gist.githubusercontent.com/cfreshman/a03ef2cba789d8cf00c08f767e0fad7b/raw/wordle-answers-alphabetical.txt";
+
+#[derive(Args)]
+pub struct FetchArgs {
+    /// Download the list of words that Wordle accepts as guesses from here, instead of the
+    /// usual place. This can also be set with guesses_url in the config file.
+    #[clap(long, value_name = "URL")]
+    pub guesses_url: Option<String>,
+
+    /// Download Wordle's list of solution words from here, instead of the usual place. This can
+    /// also be set with answers_url in the config file.
+    #[clap(long, value_name = "URL")]
+    pub answers_url: Option<String>,
+
+    /// Delete the downloaded lists, and go back to the ones built into the program.
+    #[clap(long, conflicts_with_all = &["guesses-url", "answers-url"])]
+    pub remove: bool,
+}
+
+impl FetchArgs {
+    pub fn apply_config(&mut self, config: &config::Config) {
+        if self.guesses_url.is_none() {
+            self.guesses_url = config.guesses_url.clone();
+        }
+        if self.answers_url.is_none() {
+            self.answers_url = config.answers_url.clone();
+        }
+    }
+}
+
+// Download one list and check that it really is a list of words before it replaces anything.
+pub fn download_list(url: &str) -> Result<Vec<String>> {
+    let text = ureq::get(url)
+        .call()
+        .map_err(|e| Error::Network(format!("couldn't download {}: {}", url, e)))?
+        .into_string()
+        .map_err(|e| Error::Network(format!("couldn't download {}: {}", url, e)))?;
+
+    let words = parse_word_list(&text);
+    if words.is_empty() {
+        return Err(Error::Network(format!(
+            "{} doesn't look like a word list: there aren't any {}-letter words in it",
+            url, WORD_LENGTH
+        )));
+    }
+    Ok(words)
+}
+
+// Download fresh word lists into the overlay directory, where they'll be used instead of the
+// built-in lists from then on.
+pub fn run(args: &FetchArgs, quiet: bool) -> Result<Outcome> {
+    let dir = overlay_dir().ok_or_else(|| {
+        Error::Config("couldn't find your home directory to save the lists in".to_string())
+    })?;
+
+    if args.remove {
+        for file_name in [GUESSES_FILE, ANSWERS_FILE] {
+            match fs::remove_file(dir.join(file_name)) {
+                Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+                _ => {}
+            }
+        }
+        if !quiet {
+            if cfg!(feature = "embedded-lists") {
+                outln!("Removed the downloaded lists. Using the built-in lists again.");
+            } else {
+                // There aren't any built-in lists to go back to, so the next run downloads them
+                // again.
+                outln!("Removed the downloaded lists. They'll be downloaded again next time.");
+            }
+        }
+        return Ok(Outcome::Solved);
+    }
+
+    // Download both before saving either, so a failure doesn't leave a mismatched pair.
+    let lists = [
+        (
+            "guesses",
+            GUESSES_FILE,
+            args.guesses_url.as_deref().unwrap_or(DEFAULT_GUESSES_URL),
+        ),
+        (
+            "answers",
+            ANSWERS_FILE,
+            args.answers_url.as_deref().unwrap_or(DEFAULT_ANSWERS_URL),
+        ),
+    ]
+    .into_iter()
+    .map(|(name, file_name, url)| {
+        if !quiet {
+            outln!("Downloading {} from {}", name, url);
+        }
+        Ok((name, file_name, download_list(url)?))
+    })
+    .collect::<Result<Vec<_>>>()?;
+
+    for (name, file_name, words) in lists {
+        let path = save_list(&dir, file_name, &words)?;
+        if !quiet {
+            outln!("Saved {} {} to {}", words.len(), name, path.display());
+        }
+    }
+
+    Ok(Outcome::Solved)
+}
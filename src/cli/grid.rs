@@ -0,0 +1,110 @@
+use crate::cli::*;
+use wordle_guesser::*;
+
+// Draw what the scores so far say about each place in the word, as a grid with one column per
+// letter: the letter found there, if it's been found, and the letters that can't go there. Below
+// that are the letters the word has to contain somewhere, and the ones it doesn't contain at
+// all. This is the same logic that narrows down the candidate list, laid out so you can check
+// it yourself.
+pub fn render_constraint_grid(knowledge: &Knowledge) -> String {
+    let letters = ('a'..='z').collect::<Vec<_>>();
+
+    let found = (0..WORD_LENGTH)
+        .map(|i| match knowledge.found_at(i) {
+            Some(letter) => letter.to_ascii_uppercase().to_string(),
+            None => "-".to_string(),
+        })
+        .collect::<Vec<_>>();
+
+    // Letters that aren't in the word at all can't go anywhere, so they'd just repeat in every
+    // column. Those are listed once at the bottom instead.
+    let not_here = (0..WORD_LENGTH)
+        .map(|i| {
+            let list = letters
+                .iter()
+                .filter(|&&letter| {
+                    knowledge.found_at(i).is_none()
+                        && knowledge.ruled_out_at(i, letter)
+                        && knowledge.status(letter) != LetterStatus::Excluded
+                })
+                .map(|letter| letter.to_ascii_uppercase())
+                .collect::<String>();
+            if list.is_empty() {
+                "-".to_string()
+            } else {
+                list
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let width = not_here.iter().map(|s| s.len()).max().unwrap_or(1).max(3) + 2;
+    let row = |label: &str, cells: &[String]| {
+        let mut line = format!("{:10}", label);
+        for cell in cells {
+            line.push_str(&format!("{:width$}", cell, width = width));
+        }
+        line.trim_end().to_string()
+    };
+
+    let must_have = letters
+        .iter()
+        .filter(|&&letter| knowledge.min_count(letter) > 0)
+        .map(|&letter| match knowledge.min_count(letter) {
+            1 => letter.to_ascii_uppercase().to_string(),
+            n => format!("{} (x{})", letter.to_ascii_uppercase(), n),
+        })
+        .collect::<Vec<_>>();
+    let not_in_word = letters
+        .iter()
+        .filter(|&&letter| knowledge.status(letter) == LetterStatus::Excluded)
+        .map(|letter| letter.to_ascii_uppercase().to_string())
+        .collect::<Vec<_>>();
+
+    // With --plain, the same thing as a sentence for each place, since a grid only makes sense
+    // to someone who can see the columns line up.
+    if plain_output() {
+        let mut lines = (0..WORD_LENGTH)
+            .map(|i| match (knowledge.found_at(i), not_here[i].as_str()) {
+                (Some(letter), _) => format!("Letter {}: {}.", i + 1, letter.to_ascii_uppercase()),
+                (None, "-") => format!("Letter {}: nothing known yet.", i + 1),
+                (None, list) => format!(
+                    "Letter {}: not {}.",
+                    i + 1,
+                    list.chars()
+                        .map(String::from)
+                        .collect::<Vec<_>>()
+                        .join(" or ")
+                ),
+            })
+            .collect::<Vec<_>>();
+        let listed = |list: &[String]| {
+            if list.is_empty() {
+                "none yet".to_string()
+            } else {
+                list.join(", ")
+            }
+        };
+        lines.push(format!("Must have: {}.", listed(&must_have)));
+        lines.push(format!("Not in it: {}.", listed(&not_in_word)));
+        return lines.join("\n");
+    }
+
+    let numbers = (1..=WORD_LENGTH).map(|i| i.to_string()).collect::<Vec<_>>();
+    [
+        row("", &numbers),
+        row("found", &found),
+        row("not here", &not_here),
+        String::new(),
+        format!("must have: {}", or_none(&must_have)),
+        format!("not in it: {}", or_none(&not_in_word)),
+    ]
+    .join("\n")
+}
+
+fn or_none(list: &[String]) -> String {
+    if list.is_empty() {
+        "(none yet)".to_string()
+    } else {
+        list.join(" ")
+    }
+}
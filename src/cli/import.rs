@@ -0,0 +1,155 @@
+use crate::cli::overlay::*;
+use crate::cli::*;
+use clap::Args;
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+
+// Wordle, and most of its clones, keep their word lists right in their JavaScript, as array
+// literals of strings: one of the answers, and one of everything else the game accepts. Saving
+// the page's script and pointing this at it gets exactly the lists that site uses, without
+// waiting for someone to update a copy of them.
+
+// Arrays shorter than this are something else, like the keyboard's rows, or the names of the
+// days of the week.
+const MIN_LIST_LENGTH: usize = 100;
+
+#[derive(Args)]
+pub struct ImportArgs {
+    /// The game's JavaScript, saved from the site (in a browser's developer tools, or with
+    /// "save page as").
+    #[clap(value_name = "FILE")]
+    pub file: PathBuf,
+
+    /// Just say what lists were found, without saving them.
+    #[clap(long)]
+    pub dry_run: bool,
+}
+
+// Read the lists out of a saved bundle, and save them into the overlay directory, where they're
+// used instead of the built-in lists from then on.
+pub fn run(args: &ImportArgs, quiet: bool) -> Result<Outcome> {
+    let text = fs::read_to_string(&args.file).map_err(|e| {
+        std::io::Error::new(
+            e.kind(),
+            format!("couldn't read {}: {}", args.file.display(), e),
+        )
+    })?;
+
+    let mut lists = find_word_arrays(&text);
+    if lists.is_empty() {
+        return Err(Error::Config(format!(
+            "couldn't find any lists of {}-letter words in {}",
+            WORD_LENGTH,
+            args.file.display()
+        )));
+    }
+
+    // The biggest list is the guesses. If there's another, it's the answers: older versions of
+    // Wordle kept those out of the list of guesses, so the guesses are the two together. Newer
+    // ones only have the guesses, and get each day's answer from the server.
+    lists.sort_by_key(|list| std::cmp::Reverse(list.len()));
+    let mut lists = lists.into_iter();
+    let mut guesses = lists.next().unwrap_or_default();
+    let answers = lists.next();
+    if let Some(answers) = &answers {
+        let mut seen = guesses.iter().cloned().collect::<HashSet<_>>();
+        guesses.extend(
+            answers
+                .iter()
+                .filter(|word| seen.insert(word.to_string()))
+                .cloned(),
+        );
+    }
+    guesses.sort();
+
+    let dir = overlay_dir().ok_or_else(|| {
+        Error::Config("couldn't find your home directory to save the lists in".to_string())
+    })?;
+    if !quiet {
+        outln!(
+            "Found {} guesses{} in {}.",
+            guesses.len(),
+            match &answers {
+                Some(answers) => format!(" and {} answers", answers.len()),
+                None => String::new(),
+            },
+            args.file.display()
+        );
+    }
+    if args.dry_run {
+        return Ok(Outcome::Solved);
+    }
+
+    let path = save_list(&dir, GUESSES_FILE, &guesses)?;
+    if !quiet {
+        outln!("Saved the guesses to {}", path.display());
+    }
+    match &answers {
+        Some(answers) => {
+            let path = save_list(&dir, ANSWERS_FILE, answers)?;
+            if !quiet {
+                outln!("Saved the answers to {}", path.display());
+            }
+        }
+        None => {
+            if !quiet {
+                outln!("There wasn't a separate list of answers, so the answers are unchanged.");
+            }
+        }
+    }
+
+    Ok(Outcome::Solved)
+}
+
+// Every array literal in the script that's nothing but strings, all of them words, and long
+// enough to be a word list. The words are lowercased, with duplicates taken out.
+fn find_word_arrays(text: &str) -> Vec<Vec<String>> {
+    let mut lists = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find('[') {
+        rest = &rest[start + 1..];
+        if let Some((strings, after)) = read_string_array(rest) {
+            rest = after;
+            let mut seen = HashSet::new();
+            let words = strings
+                .iter()
+                .map(|s| s.to_lowercase())
+                .filter(|word| seen.insert(word.clone()))
+                .collect::<Vec<_>>();
+            if words.len() >= MIN_LIST_LENGTH && words.iter().all(|word| is_valid_word(word)) {
+                lists.push(words);
+            }
+        }
+    }
+    lists
+}
+
+// Read the rest of an array literal, just after its opening bracket, if it's only strings.
+// Returns them and what comes after the closing bracket. Minified code has no spaces, but a
+// prettified copy does, so those are skipped. A string with an escape in it isn't a word anyway.
+fn read_string_array(text: &str) -> Option<(Vec<&str>, &str)> {
+    let mut strings = Vec::new();
+    let mut rest = text.trim_start();
+    loop {
+        if let Some(after) = rest.strip_prefix(']') {
+            return Some((strings, after));
+        }
+        let quote = rest
+            .chars()
+            .next()
+            .filter(|c| matches!(c, '"' | '\'' | '`'))?;
+        let end = rest[1..].find(quote)? + 1;
+        let string = &rest[1..end];
+        if string.contains('\\') {
+            return None;
+        }
+        strings.push(string);
+        rest = rest[end + 1..].trim_start();
+        if let Some(after) = rest.strip_prefix(',') {
+            rest = after.trim_start();
+        } else if !rest.starts_with(']') {
+            return None;
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use serde::{Deserialize, Serialize};
+use wordle_guesser::*;
+
+// The pieces of the JSON this program writes, kept in one place so that everything that writes
+// JSON describes a turn or a suggestion the same way, with the same key names.
+//
+// Anything reading the JSON can count on the key names staying the same. New keys can turn up
+// without warning, so readers should ignore keys they don't know. Anything else -- a key
+// renamed, removed, or changing what it means -- bumps SCHEMA_VERSION, which goes at the top of
+// every JSON document as "schema_version". The README describes the current version.
+//
+// They read back in too, for the parts of the program that get JSON from another one, like
+// `ask` from the daemon.
+pub const SCHEMA_VERSION: u32 = 1;
+
+// One of the suggested guesses, with the numbers it was ranked by.
+#[derive(Serialize, Deserialize)]
+pub struct JsonSuggestion {
+    pub guess: String,
+    pub expected_remaining: f64,
+    pub max_remaining: u16,
+    pub entropy: f64,
+    pub could_win: bool,
+    pub partitions: u16,
+    pub win_chance: f64,
+}
+
+impl From<&GuessQuality<'static>> for JsonSuggestion {
+    fn from(q: &GuessQuality<'static>) -> JsonSuggestion {
+        JsonSuggestion {
+            guess: q.guess.to_string(),
+            expected_remaining: q.expected_remaining,
+            max_remaining: q.max_remaining,
+            entropy: q.entropy,
+            could_win: q.has_winning,
+            partitions: q.partitions,
+            win_chance: q.win_chance,
+        }
+    }
+}
+
+// One turn of a game: what was suggested, and what was played and the score it got. The score
+// is in the usual ".y.GG" form.
+#[derive(Serialize, Deserialize)]
+pub struct JsonTurn {
+    pub game: String,
+    pub candidates_before: usize,
+    pub candidates_after: usize,
+    pub top_suggestions: Vec<JsonSuggestion>,
+    pub guess: String,
+    pub score: String,
+}
+
+// Where a game stands, for the dashboard and the daemon: how many possible answers are left, the
+// first of them, and the suggestions. If something in the request couldn't be played, or
+// didn't make sense at all, the error says why, and the rest is where things stood before it.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StateResponse {
+    pub schema_version: u32,
+    pub candidates_count: usize,
+    pub candidates: Vec<String>,
+    pub suggestions: Vec<JsonSuggestion>,
+    pub error: Option<String>,
+}
@@ -0,0 +1,66 @@
+use crate::cli::*;
+use wordle_guesser::*;
+
+// The on-screen keyboard from the game, showing what's known about each letter.
+const KEYBOARD_ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+
+// Draw the keyboard as three staggered lines of text. With color, the keys are colored like
+// they are in the game, and letters that aren't in the word are dimmed. Without color, found
+// letters get [brackets], letters in the wrong place get (parentheses), and letters that aren't
+// in the word are left off.
+pub fn render_keyboard(knowledge: &Knowledge, color: bool) -> String {
+    if plain_output() {
+        return describe_keyboard(knowledge);
+    }
+
+    let mut lines = Vec::new();
+    for (indent, row) in KEYBOARD_ROWS.iter().enumerate() {
+        let mut line = " ".repeat(indent * 2);
+        for letter in row.chars() {
+            let upper = letter.to_ascii_uppercase();
+            let status = knowledge.status(letter);
+            let key = if color {
+                match status {
+                    LetterStatus::Confirmed => format!("{} {} {}", GREEN_TILE, upper, RESET),
+                    LetterStatus::Present => format!("{} {} {}", YELLOW_TILE, upper, RESET),
+                    LetterStatus::Excluded => format!("{} {} {}", GRAY_TILE, upper, RESET),
+                    LetterStatus::Untested => format!(" {} ", upper),
+                }
+            } else {
+                match status {
+                    LetterStatus::Confirmed => format!("[{}]", upper),
+                    LetterStatus::Present => format!("({})", upper),
+                    LetterStatus::Excluded => "   ".to_string(),
+                    LetterStatus::Untested => format!(" {} ", upper),
+                }
+            };
+            line.push_str(&key);
+            line.push(' ');
+        }
+        lines.push(line.trim_end().to_string());
+    }
+    lines.join("\n")
+}
+
+// The keyboard with --plain, as a sentence for each color instead of a picture, in alphabetical
+// order since the layout of the keys means nothing when it's read out.
+fn describe_keyboard(knowledge: &Knowledge) -> String {
+    let with_status = |wanted: LetterStatus| {
+        let letters = ('a'..='z')
+            .filter(|&letter| knowledge.status(letter) == wanted)
+            .map(|letter| letter.to_ascii_uppercase().to_string())
+            .collect::<Vec<_>>();
+        if letters.is_empty() {
+            "none".to_string()
+        } else {
+            letters.join(", ")
+        }
+    };
+    [
+        format!("Green: {}.", with_status(LetterStatus::Confirmed)),
+        format!("Yellow: {}.", with_status(LetterStatus::Present)),
+        format!("Not in the word: {}.", with_status(LetterStatus::Excluded)),
+        format!("Not tried yet: {}.", with_status(LetterStatus::Untested)),
+    ]
+    .join("\n")
+}
@@ -0,0 +1,539 @@
+// Like print! and println!, but if the output has gone away (say, we're piped into `head`)
+// the error gets passed up with `?`, instead of panicking with a backtrace. These have to come
+// before the modules below so that they can use them.
+macro_rules! out {
+    ($($arg:tt)*) => {
+        std::io::Write::write_fmt(&mut std::io::stdout(), format_args!($($arg)*))?
+    };
+}
+
+macro_rules! outln {
+    () => {
+        out!("\n")
+    };
+    ($($arg:tt)*) => {{
+        out!($($arg)*);
+        out!("\n");
+    }};
+}
+
+pub mod analyze;
+pub mod antiwordle;
+pub mod bench;
+pub mod briefing;
+pub mod cache;
+pub mod cheatsheet;
+pub mod checkpoint;
+pub mod config;
+#[cfg(unix)]
+pub mod daemon;
+pub mod dashboard;
+pub mod debug_dump;
+pub mod exact;
+#[cfg(feature = "network")]
+pub mod fetch;
+pub mod grid;
+pub mod import;
+pub mod json;
+pub mod keyboard;
+pub mod nerdle;
+pub mod overlay;
+pub mod peaks;
+pub mod play;
+pub mod policy;
+pub mod quordle;
+pub mod report;
+pub mod results;
+pub mod reverse;
+pub mod setter;
+pub mod simulate;
+pub mod solve;
+pub mod squardle;
+pub mod stats;
+pub mod sweep;
+pub mod test_vectors;
+pub mod tournament;
+pub mod tutorial;
+pub mod wordlist;
+
+use clap::{ArgEnum, Args};
+use std::io;
+use std::io::prelude::*;
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+use std::sync::Mutex;
+use wordle_guesser::*;
+
+// How the program ends, reported as the process exit code so that scripts driving it can
+// tell what happened without having to parse the output.
+#[derive(Clone, Copy)]
+pub enum Outcome {
+    // Narrowed down to one word (or two, where you just guess one and then the other). For
+    // subcommands that aren't solving a puzzle, this just means everything went fine.
+    Solved = 0,
+
+    // Input ran out, or the player quit, before the word was found. Quitting on purpose still
+    // counts, so that a script can't mistake giving up for solving it.
+    Unsolved = 1,
+
+    // A guess, score, or option couldn't be understood. (This matches the exit code clap
+    // uses for bad command-line arguments.)
+    InvalidInput = 2,
+
+    // The guesses and scores entered don't match any possible word.
+    Contradiction = 3,
+}
+
+impl Outcome {
+    // The exit code for something that went wrong badly enough to stop the program.
+    pub fn from_error(error: &Error) -> Outcome {
+        match error {
+            Error::Io(_) | Error::Network(_) => Outcome::Unsolved,
+            Error::EmptyCandidateSet(..) => Outcome::Contradiction,
+            _ => Outcome::InvalidInput,
+        }
+    }
+}
+
+// ANSI escapes for drawing colored tiles, like the ones in the game.
+pub const GREEN_TILE: &str = "\x1b[30;42m";
+pub const YELLOW_TILE: &str = "\x1b[30;43m";
+pub const GRAY_TILE: &str = "\x1b[37;100m";
+pub const RESET: &str = "\x1b[0m";
+
+// When to use ANSI colors in the output.
+#[derive(Clone, Copy, PartialEq, Eq, ArgEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorChoice {
+    // Only when writing to a terminal, and NO_COLOR isn't set.
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    // Decide once, at startup, whether to color the output. Colors are only any use to a person
+    // looking at a terminal, so by default they're left out when the output goes to a file or
+    // another program. NO_COLOR is the usual way to turn them off everywhere (see
+    // https://no-color.org), but asking for them explicitly wins over that.
+    pub fn use_color(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                std::env::var_os("NO_COLOR").is_none_or(|v| v.is_empty())
+                    && io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+// The choices for --duplicates. This is separate from DuplicatePolicy so that the library
+// doesn't need to know about clap.
+#[derive(Clone, Copy, PartialEq, Eq, ArgEnum)]
+pub enum Duplicates {
+    Standard,
+    AllYellow,
+}
+
+impl Duplicates {
+    pub fn policy(self) -> DuplicatePolicy {
+        match self {
+            Duplicates::Standard => DuplicatePolicy::Standard,
+            Duplicates::AllYellow => DuplicatePolicy::AllYellow,
+        }
+    }
+}
+
+// The rule from --duplicates. Each game keeps its own (see Solver::set_duplicate_policy), but
+// the command line picks one rule for every game it starts, and the games get started all over
+// the place, so this is where they get it from (see ListArgs::solver).
+static DEFAULT_DUPLICATE_POLICY: AtomicU8 = AtomicU8::new(DuplicatePolicy::Standard as u8);
+
+pub fn set_default_duplicate_policy(policy: DuplicatePolicy) {
+    DEFAULT_DUPLICATE_POLICY.store(policy as u8, Ordering::Relaxed);
+}
+
+pub fn default_duplicate_policy() -> DuplicatePolicy {
+    if DEFAULT_DUPLICATE_POLICY.load(Ordering::Relaxed) == DuplicatePolicy::AllYellow as u8 {
+        DuplicatePolicy::AllYellow
+    } else {
+        DuplicatePolicy::Standard
+    }
+}
+
+// Whether to keep the output plain, for screen readers (see --plain). This is decided once at
+// startup and then changes how things get written all over the place, so it's global rather
+// than passed down to everything that prints.
+static PLAIN_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+pub fn set_plain_output(plain: bool) {
+    PLAIN_OUTPUT.store(plain, Ordering::Relaxed);
+}
+
+pub fn plain_output() -> bool {
+    PLAIN_OUTPUT.load(Ordering::Relaxed)
+}
+
+// A score in words, letter by letter: "C gray, R gray, A yellow, N gray, E green". A screen
+// reader makes nothing of ".y..G", but this it can read out.
+pub fn describe_score(guess: &str, score: WordScore) -> String {
+    guess
+        .chars()
+        .zip(color_names(score))
+        .map(|(letter, color)| format!("{} {}", letter.to_ascii_uppercase(), color))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+// Just the colors of a score, in words, for when there's no guess to go with them.
+pub fn describe_colors(score: WordScore) -> String {
+    color_names(score).collect::<Vec<_>>().join(", ")
+}
+
+fn color_names(score: WordScore) -> impl Iterator<Item = &'static str> {
+    format_score(score)
+        .chars()
+        .map(|code| match code {
+            'G' => "green",
+            'y' => "yellow",
+            _ => "gray",
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+}
+
+// A score the way it's shown to people: ".y..G" normally, or in words with --plain.
+pub fn show_score(guess: &str, score: WordScore) -> String {
+    if plain_output() {
+        describe_score(guess, score)
+    } else {
+        format_score(score).to_string()
+    }
+}
+
+// The line of ='s under a heading or a table's column names. With --plain there isn't one,
+// since a screen reader would read out every single equals sign.
+pub fn print_rule(width: usize) -> Result<()> {
+    if !plain_output() {
+        outln!("{}", "=".repeat(width));
+    }
+    Ok(())
+}
+
+// Show a prompt for the user, unless we're in quiet mode where the output is just data.
+pub fn prompt(quiet: bool, text: &str) -> Result<()> {
+    if !quiet {
+        out!("{}", text);
+        io::stdout().flush()?;
+    }
+    Ok(())
+}
+
+// Every line read by read_input_line, while something wants a record of them (see
+// --debug-dump). This is global since input gets read all over the place, and threading a
+// recorder through every prompt would be a lot of noise for something that's almost always off.
+static INPUT_LOG: Mutex<Option<Vec<String>>> = Mutex::new(None);
+
+pub fn start_input_log() {
+    *INPUT_LOG.lock().unwrap() = Some(Vec::new());
+}
+
+// Stop recording, and hand back what was read.
+pub fn take_input_log() -> Vec<String> {
+    INPUT_LOG.lock().unwrap().take().unwrap_or_default()
+}
+
+// Read one trimmed line of input. Returns None once we've hit the end of the input.
+pub fn read_input_line() -> Result<Option<String>> {
+    let mut input_str = String::new();
+    let bytes_read = io::stdin().read_line(&mut input_str)?;
+    if bytes_read == 0 {
+        Ok(None)
+    } else {
+        let line = input_str.trim().to_string();
+        if let Some(log) = INPUT_LOG.lock().unwrap().as_mut() {
+            log.push(line.clone());
+        }
+        Ok(Some(line))
+    }
+}
+
+// Lay out a list of words in lined-up columns, as many as fit across the terminal. Normally the
+// words go across each row in the order given, which keeps the most likely answers at the top.
+// Alphabetically, they're sorted and run down the columns instead, like `ls` does. With --plain
+// they're just one line separated by commas, since lining things up with spaces only helps
+// people who can see it.
+pub fn word_columns(words: &[&str], alphabetical: bool) -> Vec<String> {
+    if plain_output() {
+        let mut words = words.to_vec();
+        if alphabetical {
+            words.sort_unstable();
+        }
+        return vec![words.join(", ")];
+    }
+
+    let gap = 2;
+    let word_width = words.iter().map(|w| w.chars().count()).max().unwrap_or(0);
+    let num_columns = ((textwrap::termwidth() + gap) / (word_width + gap)).max(1);
+    let num_rows = words.len().div_ceil(num_columns);
+
+    let mut words = words.to_vec();
+    if alphabetical {
+        words.sort_unstable();
+    }
+
+    (0..num_rows)
+        .map(|row| {
+            let line = (0..num_columns)
+                .filter_map(|column| {
+                    let index = if alphabetical {
+                        column * num_rows + row
+                    } else {
+                        row * num_columns + column
+                    };
+                    words.get(index)
+                })
+                .map(|w| format!("{:width$}", w, width = word_width + gap))
+                .collect::<String>();
+            line.trim_end().to_string()
+        })
+        .collect()
+}
+
+// Which words are in play. These are shared by every subcommand that works from the word lists.
+#[derive(Args)]
+pub struct ListArgs {
+    /// Hard Mode: If you play Wordle with this turned on from its settings,
+    /// then once you correctly guess a letter, Wordle will require that you use it in all later guesses.
+    #[clap(short, long, overrides_with = "no-hard")]
+    pub hard: bool,
+
+    /// Normal mode, even if the config file turns hard mode on.
+    #[clap(long, overrides_with = "hard", conflicts_with = "hard-after")]
+    pub no_hard: bool,
+
+    /// A house rule for hard mode: the first N guesses can be anything, and only the guesses
+    /// after them have to fit the scores so far. This turns on hard mode.
+    #[clap(long, value_name = "N")]
+    pub hard_after: Option<usize>,
+
+    /// Normally, the 5000 most-common 5-letter English words are used as the starting point
+    /// for your guesses. You can increase the size of that list to get some less-common words, or increase
+    /// it to to only use the most common.
+    #[clap(short, long, conflicts_with = "solutions")]
+    pub common: Option<u32>,
+
+    /// Use the Wordle solution list. Normally, wordle_solver will come up with guesses that narrow down
+    /// a list of the most common 5-letter English words. If you specify this option, it will instead use
+    /// Wordle's actual set of solution words as the starting point. Guesses will be more accurate, but
+    /// doesn't this feel like cheating to you?
+    #[clap(short, long)]
+    pub solutions: bool,
+
+    /// Kid-friendly words: limit both the possible answers and the suggested guesses to a short
+    /// list of simple everyday words.
+    #[clap(short, long, conflicts_with_all = &["common", "solutions"])]
+    pub kids: bool,
+
+    /// Treat accented letters the same as unaccented ones, the way Spanish and French versions
+    /// of Wordle do. This is for word lists in other languages, put in place of the built-in
+    /// English ones (see the fetch subcommand).
+    #[clap(long, overrides_with = "no-fold-accents")]
+    pub fold_accents: bool,
+
+    /// Keep accented letters apart from unaccented ones, even if the config file says not to.
+    #[clap(long, overrides_with = "fold-accents")]
+    pub no_fold_accents: bool,
+
+    /// Judge guesses by how likely the words they'd leave are to be the answer, going by how
+    /// common they are, instead of just counting them. A guess that could leave ten obscure
+    /// words then counts as better than one that could leave three everyday ones.
+    #[clap(long, overrides_with = "no-weighted")]
+    pub weighted: bool,
+
+    /// Count every possible answer the same, even if the config file says --weighted.
+    #[clap(long, overrides_with = "weighted")]
+    pub no_weighted: bool,
+}
+
+impl ListArgs {
+    // Fill in anything that wasn't given on the command line with the value from the config
+    // file. A switch given either way on the command line wins: --no-hard plays normal mode even
+    // if the file says "hard = true".
+    pub fn apply_config(&mut self, config: &config::Config) {
+        if !self.hard && !self.no_hard {
+            self.hard = config.hard.unwrap_or(false);
+        }
+        if self.hard_after.is_none() && !self.no_hard {
+            self.hard_after = config.hard_after;
+        }
+        if !self.fold_accents && !self.no_fold_accents {
+            // An explicit fold_accents wins over what the language would have it be.
+            self.fold_accents = config
+                .fold_accents
+                .or(config.language.map(config::Language::folds_accents))
+                .unwrap_or(false);
+        }
+        if !self.weighted && !self.no_weighted {
+            self.weighted = config.weighted.unwrap_or(false);
+        }
+
+        // --common and --solutions conflict, so whichever one was given on the command line
+        // wins over either of them from the file.
+        if self.common.is_none() && !self.solutions && !self.kids {
+            self.solutions = config.solutions.unwrap_or(false);
+            if !self.solutions {
+                self.common = config.common;
+            }
+        }
+    }
+
+    // Whether hard mode is on at all, from the start or after some free guesses.
+    pub fn is_hard(&self) -> bool {
+        self.hard || self.hard_after.is_some()
+    }
+
+    // How many guesses can be anything before hard mode's rules kick in.
+    pub fn free_guesses(&self) -> usize {
+        self.hard_after.unwrap_or(0)
+    }
+
+    // Set up a solver for these hard mode settings.
+    pub fn apply_hard_mode(&self, solver: &mut Solver) {
+        solver.set_hard_after(self.free_guesses());
+    }
+
+    pub fn target_list(&self) -> TargetList {
+        if self.kids {
+            TargetList::Kids
+        } else if self.solutions {
+            TargetList::Solutions
+        } else {
+            TargetList::Common(self.common.unwrap_or(5000) as usize)
+        }
+    }
+
+    // A new game with these options, using the downloaded word lists if there are any.
+    pub fn solver(&self) -> Result<Solver> {
+        let mut solver = Solver::with_word_lists(
+            self.target_list(),
+            self.is_hard(),
+            overlay::word_lists(self.fold_accents)?,
+        );
+        if self.kids {
+            overlay::require_kids_words()?;
+        }
+        self.apply_hard_mode(&mut solver);
+        solver.set_weighted(self.weighted);
+        solver.set_duplicate_policy(default_duplicate_policy());
+        Ok(solver)
+    }
+}
+
+// How much memory the subcommands that play lots of games can use to speed them up.
+#[derive(Args)]
+pub struct MemoryArgs {
+    /// How much memory to use for scoring guesses ahead of time, like 512M or 2G. Looking
+    /// scores up makes ranking guesses against lots of possible answers several times faster.
+    /// A full table for the usual lists takes about 62 MiB, and 0 turns it off.
+    #[clap(long, value_name = "SIZE", default_value = "512M", parse(try_from_str = parse_size))]
+    pub max_memory: usize,
+}
+
+impl MemoryArgs {
+    // Work out as much of the table of scores as fits, and say what was done.
+    pub fn precompute(&self, solver: &mut Solver, quiet: bool) -> Result<()> {
+        let guesses = solver.valid_guesses().len();
+        let targets = solver.remaining_targets().len();
+        let plan = Precomputation::choose(guesses, targets, self.max_memory);
+        if !quiet {
+            let size = |rows: usize| describe_size(rows * targets);
+            match plan {
+                Precomputation::FullTable => outln!(
+                    "Scoring all {} guesses against all {} answers ahead of time ({}).",
+                    guesses,
+                    targets,
+                    size(guesses)
+                ),
+                Precomputation::PartialTable(rows) => outln!(
+                    "Scoring {} of the {} guesses against all {} answers ahead of time ({}; all of them would take {}).",
+                    rows,
+                    guesses,
+                    targets,
+                    size(rows),
+                    size(guesses)
+                ),
+                Precomputation::Nothing => outln!(
+                    "Not scoring anything ahead of time, since even one guess's scores would take {}.",
+                    size(1)
+                ),
+            }
+        }
+        solver.precompute_scores(plan);
+        Ok(())
+    }
+}
+
+// How a game's hard mode setting reads after "playing", for the headings of reports.
+pub fn describe_hard_mode(solver: &Solver) -> String {
+    match (solver.is_hard(), solver.hard_after()) {
+        (false, _) => String::new(),
+        (true, 0) => " in hard mode".to_string(),
+        (true, 1) => " in hard mode after a free first guess".to_string(),
+        (true, n) => format!(" in hard mode after {} free guesses", n),
+    }
+}
+
+// How many of a plan's hard mode violations to spell out. One is usually enough to see what's
+// going wrong, and a broken strategy can have thousands.
+const VIOLATIONS_SHOWN: usize = 5;
+
+// Warn about guesses that hard mode wouldn't accept, in a plan for playing in hard mode. These
+// go to stderr, so that the results still come out clean for anything reading them.
+pub fn warn_about_hard_mode(name: &str, violations: &[HardModeViolation]) {
+    if violations.is_empty() {
+        return;
+    }
+    eprintln!(
+        "\nWarning: {} makes {} guess{} that hard mode won't allow, so it can't really be played that way:",
+        name,
+        violations.len(),
+        if violations.len() == 1 { "" } else { "es" }
+    );
+    for violation in violations.iter().take(VIOLATIONS_SHOWN) {
+        eprintln!("  {}", violation);
+    }
+    if violations.len() > VIOLATIONS_SHOWN {
+        eprintln!("  ...and {} more", violations.len() - VIOLATIONS_SHOWN);
+    }
+}
+
+// A number of bytes in whichever unit reads best.
+fn describe_size(bytes: usize) -> String {
+    match bytes {
+        0..=1023 => format!("{} bytes", bytes),
+        1024..=1048575 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0)),
+    }
+}
+
+// Read a size in bytes, with an optional K, M, or G (or KB, MB, GB) on the end. These are the
+// usual powers of 1024.
+fn parse_size(text: &str) -> std::result::Result<usize, String> {
+    let upper = text.trim().to_uppercase();
+    let digits = upper.trim_end_matches('B');
+    let (number, multiplier) = match digits.chars().last() {
+        Some('K') => (&digits[..digits.len() - 1], 1 << 10),
+        Some('M') => (&digits[..digits.len() - 1], 1 << 20),
+        Some('G') => (&digits[..digits.len() - 1], 1 << 30),
+        _ => (digits, 1),
+    };
+    number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|n| *n >= 0.0)
+        .map(|n| (n * multiplier as f64) as usize)
+        .ok_or_else(|| format!("'{}' isn't a size, like 512M or 2G", text))
+}
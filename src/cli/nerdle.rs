@@ -0,0 +1,116 @@
+use crate::cli::*;
+use wordle_guesser::*;
+
+// With this many possible equations or fewer, they all get listed.
+const MAX_EQUATIONS_SHOWN: usize = 60;
+
+// Help solve a Nerdle puzzle, the same way as a Wordle one: suggest guesses, read back the
+// guess and its score, and narrow down the possible equations.
+pub fn run(num_shown: usize, quiet: bool) -> Result<Outcome> {
+    let equations = nerdle_equations();
+    let mut guesses = equations.iter().map(String::as_str).collect::<Vec<_>>();
+    let mut remaining = guesses.clone();
+
+    loop {
+        if remaining.len() == 1 {
+            if quiet {
+                outln!("{}", remaining[0]);
+            } else {
+                outln!("\nThe equation is {}", remaining[0]);
+            }
+            return Ok(Outcome::Solved);
+        }
+
+        if !quiet {
+            outln!("\nThere are {} possible equations.", remaining.len());
+            if remaining.len() <= MAX_EQUATIONS_SHOWN {
+                outln!();
+                for line in word_columns(&remaining, false) {
+                    outln!("{}", line);
+                }
+            }
+            if remaining.len() == equations.len() {
+                outln!("\nRanking every equation against every other one takes a little while...");
+            }
+        }
+
+        let ranked = rank_equations(&guesses, &remaining);
+        if !quiet {
+            outln!("\nSUGGESTED GUESSES (sorted by expected_remaining * max_remaining)");
+            print_rule(102)?;
+        }
+        for (q, worst_score) in ranked.iter().take(num_shown) {
+            outln!(
+                "{} {} | average {:.1} left, max {} left with {}",
+                if q.has_winning { '*' } else { ' ' },
+                q.guess,
+                q.expected_remaining,
+                q.max_remaining,
+                format_equation_score(*worst_score)
+            );
+        }
+
+        let guess = loop {
+            prompt(quiet, "\nPlease enter the equation you'll guess: ")?;
+            let input_str = match read_input_line()? {
+                Some(s) => s.replace(' ', ""),
+                None => return Ok(Outcome::Unsolved),
+            };
+            if equations.binary_search(&input_str).is_ok() {
+                break input_str;
+            }
+            if quiet {
+                eprintln!("'{}' isn't a valid equation.", input_str);
+                return Ok(Outcome::InvalidInput);
+            }
+            outln!(
+                "'{}' isn't a valid equation. It has to be {} symbols long, with a calculation on the left of the = and the answer on the right, like 12+35=47.",
+                input_str,
+                NERDLE_LENGTH
+            );
+        };
+
+        let score = loop {
+            prompt(
+                quiet,
+                "Enter the score you got for that equation, in \"..y.G.GG\" format: ",
+            )?;
+            let input_str = match read_input_line()? {
+                Some(s) => s,
+                None => return Ok(Outcome::Unsolved),
+            };
+            match parse_equation_score(&input_str) {
+                Ok(score) => break score,
+                Err(e) if quiet => {
+                    eprintln!("{}", e);
+                    return Ok(Outcome::InvalidInput);
+                }
+                Err(e) => outln!("Sorry, {}.", e),
+            }
+        };
+
+        let fits = remaining
+            .iter()
+            .copied()
+            .filter(|target| score_equation_pair(&guess, target) == score)
+            .collect::<Vec<_>>();
+        if fits.is_empty() {
+            if quiet {
+                eprintln!(
+                    "none of the possible equations would score {} for '{}'",
+                    format_equation_score(score),
+                    guess
+                );
+                return Ok(Outcome::Contradiction);
+            }
+            outln!(
+                "\nThat can't be right: none of the possible equations would score {} for '{}'. Let's try that again.",
+                format_equation_score(score),
+                guess
+            );
+            continue;
+        }
+        remaining = fits;
+        guesses.retain(|g| *g != guess);
+    }
+}
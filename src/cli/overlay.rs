@@ -0,0 +1,336 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use wordle_guesser::*;
+
+// The word lists built into the program go stale as Wordle changes its lists. Newer lists can be
+// dropped into an overlay directory, ~/.local/share/wordleguesser, and they're used instead of
+// the built-in ones:
+//
+//     guesses.txt      every word Wordle accepts as a guess
+//     answers.txt      Wordle's solution words
+//     frequencies.txt  words, most common first, each with a count of how common it is
+//     kids.txt         simple words for kids' games
+//
+// Any of them can be left out to keep using the built-in list. Words are separated by
+// whitespace, and anything that isn't a five-letter word is ignored. The counts in the frequency
+// list can be left out too, and then the words just count for less and less down the list. The
+// fetch subcommand downloads the first two, but they can all just as well be written by hand.
+//
+// Built without the embedded lists, the program has no lists of its own, so the first two have
+// to be there. If they aren't, the first run gets them (see first_run_list).
+pub const GUESSES_FILE: &str = "guesses.txt";
+pub const ANSWERS_FILE: &str = "answers.txt";
+pub const FREQUENCIES_FILE: &str = "frequencies.txt";
+pub const KIDS_FILE: &str = "kids.txt";
+
+// Where the overlay lists live. Like the config file, this follows the XDG convention on every
+// platform.
+pub fn overlay_dir() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?;
+    Some(
+        PathBuf::from(home)
+            .join(".local")
+            .join("share")
+            .join("wordleguesser"),
+    )
+}
+
+// Pull the five-letter words out of a downloaded or hand-written list, lowercased, skipping
+// duplicates and anything else. Letters don't have to be English ones, so lists for other
+// languages work too.
+pub fn parse_word_list(text: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    text.split_whitespace()
+        .map(|word| word.to_lowercase())
+        .filter(|word| is_valid_word(word))
+        .filter(|word| seen.insert(word.clone()))
+        .collect()
+}
+
+// Leak a list of words, so that they live as long as the built-in ones.
+fn leak_list(words: Vec<String>) -> &'static [&'static str] {
+    let words: Vec<&'static str> = words
+        .into_iter()
+        .map(|word| &*Box::leak(word.into_boxed_str()))
+        .collect();
+    Box::leak(words.into_boxed_slice())
+}
+
+// A list with the accents taken off of every word. Words that end up the same, like "été" and
+// "ete", become one word.
+fn fold_list(words: &[&str]) -> &'static [&'static str] {
+    let mut seen = HashSet::new();
+    leak_list(
+        words
+            .iter()
+            .map(|word| fold_accents(word))
+            .filter(|word| seen.insert(word.clone()))
+            .collect(),
+    )
+}
+
+// Read the text of one of the overlay files, if it's there.
+fn read_overlay_file(file_name: &str) -> Result<Option<(PathBuf, String)>> {
+    let path = match overlay_dir() {
+        Some(dir) => dir.join(file_name),
+        None => return Ok(None),
+    };
+
+    match fs::read_to_string(&path) {
+        Ok(text) => Ok(Some((path, text))),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(Error::Config(format!(
+            "couldn't read {}: {}",
+            path.display(),
+            e
+        ))),
+    }
+}
+
+// Read one of the overlay lists, if it's there. The words have to live as long as the built-in
+// ones, so they're leaked -- they're only loaded once, and they're needed until the program
+// exits anyway.
+fn load_list(file_name: &str) -> Result<Option<&'static [&'static str]>> {
+    let (path, text) = match read_overlay_file(file_name)? {
+        Some(file) => file,
+        None => return Ok(None),
+    };
+
+    let words = parse_word_list(&text);
+    if words.is_empty() {
+        return Err(Error::Config(format!(
+            "{} doesn't have any {}-letter words in it",
+            path.display(),
+            WORD_LENGTH
+        )));
+    }
+
+    Ok(Some(leak_list(words)))
+}
+
+// Read the frequency list, if there's one in the overlay directory. Each line is a word, and
+// then optionally its count; without counts, the words get made-up ones that go down the list,
+// so that the order's all that matters.
+fn load_frequencies() -> Result<Option<&'static [(&'static str, u32)]>> {
+    let (path, text) = match read_overlay_file(FREQUENCIES_FILE)? {
+        Some(file) => file,
+        None => return Ok(None),
+    };
+
+    let mut seen = HashSet::new();
+    let entries = text
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let word = fields.next()?.to_lowercase();
+            let count = fields.next().and_then(|count| count.parse::<u32>().ok());
+            (is_valid_word(&word) && seen.insert(word.clone())).then_some((word, count))
+        })
+        .collect::<Vec<_>>();
+    if entries.is_empty() {
+        return Err(Error::Config(format!(
+            "{} doesn't have any {}-letter words in it",
+            path.display(),
+            WORD_LENGTH
+        )));
+    }
+
+    let num_entries = entries.len();
+    let words = leak_list(entries.iter().map(|(word, _)| word.clone()).collect());
+    let list = words
+        .iter()
+        .zip(&entries)
+        .enumerate()
+        .map(|(i, (&word, (_, count)))| (word, count.unwrap_or((num_entries - i) as u32)))
+        .collect::<Vec<_>>();
+    Ok(Some(Box::leak(list.into_boxed_slice())))
+}
+
+// The guesses and answers, where neither came from the overlay directory.
+#[cfg(feature = "embedded-lists")]
+fn fill_in_lists(
+    guesses: Option<&'static [&'static str]>,
+    solutions: Option<&'static [&'static str]>,
+) -> Result<WordLists> {
+    Ok(match (guesses, solutions) {
+        (None, None) => BUILT_IN_WORD_LISTS,
+        (guesses, solutions) => WordLists {
+            guesses: guesses.unwrap_or(BUILT_IN_WORD_LISTS.guesses),
+            solutions: solutions.unwrap_or(BUILT_IN_WORD_LISTS.solutions),
+            built_in: false,
+        },
+    })
+}
+
+// Without the embedded lists, there's nothing to fall back on, so whichever list is missing has
+// to be got hold of now.
+#[cfg(not(feature = "embedded-lists"))]
+fn fill_in_lists(
+    guesses: Option<&'static [&'static str]>,
+    solutions: Option<&'static [&'static str]>,
+) -> Result<WordLists> {
+    let guesses = match guesses {
+        Some(guesses) => guesses,
+        None => first_run_list(GUESSES_FILE)?,
+    };
+    let solutions = match solutions {
+        Some(solutions) => solutions,
+        None => first_run_list(ANSWERS_FILE)?,
+    };
+    Ok(WordLists {
+        guesses,
+        solutions,
+        built_in: false,
+    })
+}
+
+// Get one of the lists a build without the embedded lists needs, and save it in the overlay
+// directory so that this only happens the once. With the network feature, it's downloaded from
+// the same place `fetch` gets it; otherwise, whoever's running the program gets asked where it
+// is.
+#[cfg(not(feature = "embedded-lists"))]
+fn first_run_list(file_name: &str) -> Result<&'static [&'static str]> {
+    let dir = overlay_dir().ok_or_else(|| {
+        Error::Config("couldn't find your home directory to keep the word lists in".to_string())
+    })?;
+    let description = match file_name {
+        GUESSES_FILE => "every word the game accepts as a guess",
+        _ => "the game's possible answers",
+    };
+
+    #[cfg(feature = "network")]
+    let words = {
+        let url = match file_name {
+            GUESSES_FILE => crate::cli::fetch::DEFAULT_GUESSES_URL,
+            _ => crate::cli::fetch::DEFAULT_ANSWERS_URL,
+        };
+        eprintln!(
+            "This copy of WordleGuesser doesn't have its word lists built in. Downloading a list of {} from {}",
+            description, url
+        );
+        crate::cli::fetch::download_list(url)?
+    };
+
+    #[cfg(not(feature = "network"))]
+    let words = ask_for_list(description, &dir.join(file_name))?;
+
+    let path = save_list(&dir, file_name, &words)?;
+    eprintln!("Saved {} words to {}", words.len(), path.display());
+    Ok(leak_list(words))
+}
+
+// Ask where to find a list, and read it. That can only be done with someone at the keyboard, so
+// otherwise, this just says where to put it.
+#[cfg(all(not(feature = "embedded-lists"), not(feature = "network")))]
+fn ask_for_list(description: &str, destination: &std::path::Path) -> Result<Vec<String>> {
+    use crate::cli::{prompt, read_input_line};
+    use std::io::IsTerminal;
+    if !std::io::stdin().is_terminal() {
+        return Err(Error::Config(format!(
+            "this copy of WordleGuesser doesn't have its word lists built in, and there's no list of {} in {}",
+            description,
+            destination.display()
+        )));
+    }
+
+    outln!("This copy of WordleGuesser doesn't have its word lists built in.");
+    loop {
+        prompt(
+            false,
+            &format!(
+                "Where's a list of {}? Type the path to a text file of words: ",
+                description
+            ),
+        )?;
+        let path = match read_input_line()? {
+            Some(path) if !path.is_empty() => PathBuf::from(path),
+            _ => {
+                return Err(Error::Config(format!(
+                    "there's no list of {} yet",
+                    description
+                )))
+            }
+        };
+        match fs::read_to_string(&path) {
+            Ok(text) => {
+                let words = parse_word_list(&text);
+                if !words.is_empty() {
+                    return Ok(words);
+                }
+                outln!(
+                    "{} doesn't have any {}-letter words in it.",
+                    path.display(),
+                    WORD_LENGTH
+                );
+            }
+            Err(e) => outln!("Couldn't read {}: {}", path.display(), e),
+        }
+    }
+}
+
+// The kids' list, which a build without the embedded lists only has if there's a kids.txt.
+// Call this after word_lists, which is what loads it.
+pub fn require_kids_words() -> Result<&'static [&'static str]> {
+    match kids_words() {
+        [] => Err(Error::Config(format!(
+            "there's no kids' word list: put one in {}",
+            overlay_dir()
+                .map(|dir| dir.join(KIDS_FILE).display().to_string())
+                .unwrap_or_else(|| KIDS_FILE.to_string())
+        ))),
+        words => Ok(words),
+    }
+}
+
+// Save a list in the overlay directory, one word per line, and say where it went.
+pub fn save_list(dir: &std::path::Path, file_name: &str, words: &[String]) -> Result<PathBuf> {
+    fs::create_dir_all(dir).map_err(|e| {
+        std::io::Error::new(
+            e.kind(),
+            format!("couldn't create {}: {}", dir.display(), e),
+        )
+    })?;
+    let path = dir.join(file_name);
+    fs::write(&path, words.join("\n") + "\n").map_err(|e| {
+        std::io::Error::new(
+            e.kind(),
+            format!("couldn't write {}: {}", path.display(), e),
+        )
+    })?;
+    Ok(path)
+}
+
+// The word lists to play with: the overlay lists where there are any, and the built-in lists
+// otherwise. With `fold_accents`, they're the same lists with the accents taken off.
+//
+// The frequency and kids' lists from the overlay directory get handed to the library here too,
+// since this is the first thing anything does with the word lists.
+pub fn word_lists(fold_accents: bool) -> Result<WordLists> {
+    static LISTS: OnceLock<WordLists> = OnceLock::new();
+    static FOLDED_LISTS: OnceLock<WordLists> = OnceLock::new();
+
+    let lists = match LISTS.get() {
+        Some(lists) => *lists,
+        None => {
+            if let Some(frequencies) = load_frequencies()? {
+                set_word_frequencies(frequencies);
+            }
+            if let Some(kids) = load_list(KIDS_FILE)? {
+                set_kids_words(kids);
+            }
+            let lists = fill_in_lists(load_list(GUESSES_FILE)?, load_list(ANSWERS_FILE)?)?;
+            *LISTS.get_or_init(|| lists)
+        }
+    };
+    if !fold_accents {
+        return Ok(lists);
+    }
+
+    Ok(*FOLDED_LISTS.get_or_init(|| WordLists {
+        guesses: fold_list(lists.guesses),
+        solutions: fold_list(lists.solutions),
+        built_in: false,
+    }))
+}
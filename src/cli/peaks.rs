@@ -0,0 +1,141 @@
+use crate::cli::*;
+use wordle_guesser::*;
+
+// With this many possible answers or fewer, they all get listed.
+const MAX_ANSWERS_SHOWN: usize = 60;
+
+// Help solve a Wordle Peaks puzzle. It goes the same way as a Wordle game, but with Peaks
+// scores, and the possible answers are narrowed down by the range of letters left for each
+// place. In hard mode, guesses have to stay inside those ranges too, once any free guesses
+// are over.
+pub fn run(lists: &ListArgs, num_shown: usize, quiet: bool) -> Result<Outcome> {
+    let solver = lists.solver()?;
+    // The scores compare letters a byte at a time, so only plain English words are used.
+    let mut guesses = solver
+        .valid_guesses()
+        .iter()
+        .copied()
+        .filter(|w| w.is_ascii())
+        .collect::<Vec<_>>();
+    let mut remaining = solver
+        .remaining_targets()
+        .iter()
+        .copied()
+        .filter(|w| w.is_ascii())
+        .collect::<Vec<_>>();
+    let mut ranges = PeaksRanges::new();
+    let mut turns = 0;
+
+    loop {
+        if remaining.len() == 1 {
+            if quiet {
+                outln!("{}", remaining[0]);
+            } else {
+                outln!("\nThe answer is {}", remaining[0].to_uppercase());
+            }
+            return Ok(Outcome::Solved);
+        }
+
+        if !quiet {
+            outln!(
+                "\nThere are {} possible answers, with letters {}.",
+                remaining.len(),
+                ranges.describe().join(" ")
+            );
+            if remaining.len() <= MAX_ANSWERS_SHOWN {
+                outln!();
+                for line in word_columns(&remaining, false) {
+                    outln!("{}", line);
+                }
+            }
+            outln!("\nSUGGESTED GUESSES (sorted by expected_remaining * max_remaining)");
+            print_rule(102)?;
+        }
+        for q in rank_peaks_guesses(&guesses, &remaining)
+            .iter()
+            .take(num_shown)
+        {
+            outln!(
+                "{} {} | average {:.1} left, max {} left with {}",
+                if q.has_winning { '*' } else { ' ' },
+                q.guess,
+                q.expected_remaining,
+                q.max_remaining,
+                format_peaks_score(q.score_with_max_remaining)
+            );
+        }
+
+        let guess = loop {
+            prompt(quiet, "\nPlease enter the word you'll guess: ")?;
+            let input_str = match read_input_line()? {
+                Some(s) => s.to_lowercase(),
+                None => return Ok(Outcome::Unsolved),
+            };
+            if guesses.contains(&input_str.as_str()) {
+                break input_str;
+            }
+            if quiet {
+                eprintln!("'{}' isn't an allowed guess.", input_str);
+                return Ok(Outcome::InvalidInput);
+            }
+            if lists.is_hard() && solver.valid_guesses().contains(&input_str.as_str()) {
+                outln!(
+                    "'{}' doesn't fit the letters found so far, which hard mode requires.",
+                    input_str
+                );
+            } else {
+                outln!("'{}' isn't in the word list.", input_str);
+            }
+        };
+
+        let score = loop {
+            prompt(
+                quiet,
+                "Enter the score you got for that word, with '<' for a letter that comes earlier, '>' for later, and 'G' for right, like \"<>G<<\": ",
+            )?;
+            let input_str = match read_input_line()? {
+                Some(s) => s,
+                None => return Ok(Outcome::Unsolved),
+            };
+            match parse_peaks_score(&input_str) {
+                Ok(score) => break score,
+                Err(e) if quiet => {
+                    eprintln!("{}", e);
+                    return Ok(Outcome::InvalidInput);
+                }
+                Err(e) => outln!("Sorry, {}.", e),
+            }
+        };
+
+        let mut narrowed = ranges;
+        let result = narrowed.record(&guess, score).and_then(|()| {
+            if remaining.iter().any(|w| narrowed.allows(w)) {
+                Ok(())
+            } else {
+                Err(Error::EmptyCandidateSet(
+                    guess.clone(),
+                    format_peaks_score(score),
+                ))
+            }
+        });
+        match result {
+            Ok(()) => {}
+            Err(e) if quiet => {
+                eprintln!("{}", e);
+                return Ok(Outcome::from_error(&e));
+            }
+            Err(e) => {
+                outln!("\nThat can't be right: {}. Let's try that again.", e);
+                continue;
+            }
+        }
+
+        ranges = narrowed;
+        remaining.retain(|w| ranges.allows(w));
+        guesses.retain(|g| *g != guess);
+        turns += 1;
+        if lists.is_hard() && turns >= lists.free_guesses() {
+            guesses.retain(|g| ranges.allows(g));
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use crate::cli::*;
+use clap::Args;
+use rand::seq::SliceRandom;
+use std::io;
+use std::path::{Path, PathBuf};
+use wordle_guesser::*;
+
+// Play mode turns the program into Wordle itself, for two people sharing one computer. The
+// first player picks the secret word, and the second player tries to guess it, with this
+// program scoring the guesses and drawing the board. If the first player doesn't pick a word,
+// the program picks one, so it also works for practicing on your own.
+
+#[derive(Args)]
+pub struct PlayArgs {
+    /// Read the secret word from this file, instead of having the first player type it in.
+    #[clap(long, value_name = "FILE")]
+    pub secret_file: Option<PathBuf>,
+
+    /// Kid-friendly game: random secret words come from a short list of simple everyday words,
+    /// and there are 8 tries instead of 6.
+    #[clap(short, long)]
+    pub kids: bool,
+
+    /// Seed the random choice of secret word, to get the same word again.
+    #[clap(long)]
+    pub seed: Option<u64>,
+
+    /// How many times the guesser can type "hint letter" to have one letter of the secret
+    /// revealed. Use 0 for no hints.
+    #[clap(long, value_name = "N", default_value = "1")]
+    pub hints: usize,
+}
+
+// Wordle gives you six tries. Kids get a couple extra.
+const MAX_GUESSES: usize = 6;
+const KIDS_MAX_GUESSES: usize = 8;
+
+// Draw one row of the board: the letters of the guess on colored tiles, followed by the score in
+// the usual ".y.GG" code, so it's readable even where the colors don't come through. Without
+// color, it's just the letters and the code, and with --plain it's the colors in words.
+pub fn render_board_row(guess: &str, score: WordScore, color: bool) -> String {
+    if plain_output() {
+        return format!("{}: {}", guess.to_uppercase(), describe_score(guess, score));
+    }
+
+    let mut row = String::new();
+    for (letter, code) in guess.chars().zip(format_score(score).chars()) {
+        let letter = letter.to_uppercase();
+        if !color {
+            row.push_str(&format!(" {} ", letter));
+            continue;
+        }
+
+        let tile = match code {
+            'G' => GREEN_TILE,
+            'y' => YELLOW_TILE,
+            _ => GRAY_TILE,
+        };
+        row.push_str(&format!("{} {} {}", tile, letter, RESET));
+    }
+    row.push_str("   ");
+    row.push_str(&format_score(score));
+    row
+}
+
+// Pick which letter of the secret to give away as a hint. Out of the places that haven't been
+// found yet, it's whichever one would narrow down the possible answers the most if you knew
+// it, which tends to be the letter that's hardest to find on your own. Returns None if every
+// letter has been found already.
+fn choose_hint(secret: &str, candidates: &[&str], found: &[bool; WORD_LENGTH]) -> Option<usize> {
+    (0..WORD_LENGTH).filter(|&i| !found[i]).min_by_key(|&i| {
+        candidates
+            .iter()
+            .filter(|w| nth_letter(w, i) == nth_letter(secret, i))
+            .count()
+    })
+}
+
+// One letter of a word. Words in other languages can have letters that take more than one byte,
+// so this goes by letters rather than indexing the bytes.
+fn nth_letter(word: &str, i: usize) -> Option<char> {
+    word.chars().nth(i)
+}
+
+// Get the secret word from the first player. It can come from a file or from the WORDLE_SECRET
+// environment variable, so it can be set up ahead of time, or else it's typed in without being
+// shown on the screen. Leaving it blank picks a random word from the list of possible answers.
+fn get_secret(
+    secret_file: Option<&Path>,
+    lists: &WordLists,
+    answers: &[&str],
+    seed: Option<u64>,
+) -> Result<String> {
+    let secret = if let Some(path) = secret_file {
+        std::fs::read_to_string(path).map_err(|e| {
+            io::Error::new(e.kind(), format!("couldn't read {}: {}", path.display(), e))
+        })?
+    } else if let Ok(secret) = std::env::var("WORDLE_SECRET") {
+        secret
+    } else {
+        rpassword::prompt_password(
+            "Player 1, type the secret word (it won't be shown), or leave it blank to have one picked at random: ",
+        )?
+    };
+
+    let secret = secret.trim().to_lowercase();
+    if secret.is_empty() {
+        // An answers.txt of your own, or a filter, can leave nothing to pick from.
+        return answers
+            .choose(&mut seeded_rng(seed))
+            .map(|answer| answer.to_string())
+            .ok_or_else(|| {
+                Error::Config(format!(
+                    "there are no answers to pick a secret word from; check {} (or {} for kids' games) in ~/.local/share/wordleguesser",
+                    overlay::ANSWERS_FILE,
+                    overlay::KIDS_FILE
+                ))
+            });
+    }
+
+    if !lists.guesses.contains(&secret.as_str()) {
+        return Err(Error::WordNotInDictionary(secret));
+    }
+    Ok(secret)
+}
+
+// Host a game: get the secret, then score the second player's guesses until they get it or
+// run out of tries. Kids games pick random words from the kids list, and allow more tries.
+pub fn run(args: &PlayArgs, color: bool) -> Result<Outcome> {
+    let lists = overlay::word_lists(false)?;
+    let (answers, max_guesses) = if args.kids {
+        (overlay::require_kids_words()?, KIDS_MAX_GUESSES)
+    } else {
+        (lists.solutions, MAX_GUESSES)
+    };
+
+    let secret = get_secret(args.secret_file.as_deref(), &lists, answers, args.seed)?;
+
+    outln!(
+        "\nPlayer 2, you have {} tries to guess the secret word.",
+        max_guesses
+    );
+
+    if args.hints > 0 {
+        outln!(
+            "Stuck? Type 'hint letter' to have a letter revealed. ({} left)",
+            args.hints
+        );
+    }
+
+    let mut board = Vec::new();
+    let mut knowledge = Knowledge::new();
+    let duplicates = default_duplicate_policy();
+
+    // For the hints: the answers that fit everything so far, and which letters of the secret
+    // have been found, either as greens or from earlier hints.
+    let mut candidates: Vec<&str> = answers.to_vec();
+    let mut found = [false; WORD_LENGTH];
+    let mut hints_left = args.hints;
+
+    while board.len() < max_guesses {
+        prompt(false, &format!("\nGuess {}: ", board.len() + 1))?;
+        let guess = match read_input_line()? {
+            Some(guess) => guess.to_lowercase(),
+            None => return Ok(Outcome::Unsolved),
+        };
+
+        if guess == "hint letter" || guess == "hint" {
+            if hints_left == 0 {
+                outln!("Sorry, no hints left.");
+                continue;
+            }
+            match choose_hint(&secret, &candidates, &found) {
+                Some(i) => {
+                    let letter = nth_letter(&secret, i);
+                    found[i] = true;
+                    candidates.retain(|w| nth_letter(w, i) == letter);
+                    hints_left -= 1;
+                    outln!(
+                        "Letter {} is {}. ({} hint{} left)",
+                        i + 1,
+                        letter.unwrap_or('?').to_uppercase(),
+                        hints_left,
+                        if hints_left == 1 { "" } else { "s" }
+                    );
+                }
+                None => outln!("You've already found every letter!"),
+            }
+            continue;
+        }
+
+        if !lists.guesses.contains(&guess.as_str()) {
+            outln!("'{}' isn't in the word list. Try again.", guess);
+            continue;
+        }
+
+        let score = duplicates.score(&guess, &secret);
+        board.push(render_board_row(&guess, score, color));
+        knowledge.record(&guess, score, duplicates);
+        candidates.retain(|w| duplicates.score(&guess, w) == score);
+        for (i, code) in format_score(score).bytes().enumerate() {
+            found[i] |= code == b'G';
+        }
+        outln!();
+        for row in &board {
+            outln!("{}", row);
+        }
+
+        if guess == secret {
+            outln!("\nGot it in {}!", board.len());
+            return Ok(Outcome::Solved);
+        }
+
+        outln!("\n{}", keyboard::render_keyboard(&knowledge, color));
+    }
+
+    outln!("\nOut of tries! The word was {}.", secret.to_uppercase());
+    Ok(Outcome::Unsolved)
+}
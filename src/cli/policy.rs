@@ -0,0 +1,103 @@
+use crate::cli::*;
+use clap::Args;
+use std::io;
+use std::path::PathBuf;
+use wordle_guesser::*;
+
+#[derive(Args)]
+pub struct PolicyArgs {
+    /// The policy file: an "open WORD" line, a "then STRATEGY" line, and lines of scores
+    /// followed by the guess to make after them. See the README for an example.
+    #[clap(value_name = "FILE", conflicts_with_all = &["open", "then"])]
+    pub file: Option<PathBuf>,
+
+    /// Instead of a file, grade this opening word...
+    #[clap(long, value_name = "WORD")]
+    pub open: Option<String>,
+
+    /// ...followed by this strategy: "ranker", "entropy", "worstcase", "random", "frequent",
+    /// or "greens". The default is "ranker".
+    #[clap(long, value_name = "STRATEGY")]
+    pub then: Option<String>,
+
+    /// Hard Mode: every guess in the policy has to use what's been found so far.
+    #[clap(short, long)]
+    pub hard: bool,
+}
+
+// Grade a policy exactly: play it against every word in the Wordle solution list, and report
+// the average, worst case, and spread, so a hand-made plan can be compared against the
+// built-in strategies.
+pub fn run(args: &PolicyArgs, quiet: bool) -> Result<Outcome> {
+    let policy = match &args.file {
+        Some(path) => {
+            let text = std::fs::read_to_string(path).map_err(|e| {
+                io::Error::new(e.kind(), format!("couldn't read {}: {}", path.display(), e))
+            })?;
+            Policy::parse(&text)?
+        }
+        None => Policy::new(
+            args.open.as_deref(),
+            strategy_by_name(args.then.as_deref().unwrap_or("ranker"))?,
+        ),
+    };
+
+    let mut solver = Solver::with_word_lists(
+        TargetList::Solutions,
+        args.hard,
+        overlay::word_lists(false)?,
+    );
+    solver.set_duplicate_policy(default_duplicate_policy());
+    let evaluation = policy.evaluate(&solver)?;
+    let summary = summarize(&evaluation.games);
+    warn_about_hard_mode("the policy", &evaluation.hard_mode_violations);
+
+    if quiet {
+        outln!(
+            "{:.4} {} {}",
+            summary.average_guesses,
+            summary.worst_guesses,
+            summary.failures
+        );
+        return Ok(Outcome::Solved);
+    }
+
+    outln!(
+        "Against all {} solutions{}:\n",
+        summary.games,
+        if args.hard { " in hard mode" } else { "" }
+    );
+    outln!(
+        "average {:.4} guesses ({} in total)",
+        summary.average_guesses,
+        evaluation
+            .games
+            .iter()
+            .map(|g| g.guesses.len())
+            .sum::<usize>()
+    );
+    outln!("worst   {} guesses", summary.worst_guesses);
+    outln!(
+        "failed  {} (more than {} guesses)\n",
+        summary.failures,
+        WORDLE_MAX_GUESSES
+    );
+    for (guesses, &count) in summary.distribution.iter().enumerate().skip(1) {
+        if count > 0 {
+            outln!("{:2} guesses: {}", guesses, count);
+        }
+    }
+
+    if evaluation.unused_decisions > 0 {
+        outln!(
+            "\n{} line{} of the policy never came up. Check the scores on those.",
+            evaluation.unused_decisions,
+            if evaluation.unused_decisions == 1 {
+                ""
+            } else {
+                "s"
+            }
+        );
+    }
+    Ok(Outcome::Solved)
+}
@@ -0,0 +1,219 @@
+use crate::cli::*;
+use clap::Args;
+use itertools::Itertools;
+use wordle_guesser::*;
+
+#[derive(Args)]
+pub struct QuordleArgs {
+    #[clap(flatten)]
+    pub lists: ListArgs,
+
+    /// How many boards are played at once. Quordle has 4, Octordle has 8.
+    #[clap(short, long, default_value_t = QUORDLE_BOARDS)]
+    pub boards: usize,
+
+    /// How many guesses you get. The default is the number of boards plus 5, which is what
+    /// Quordle and Octordle give you.
+    #[clap(short, long)]
+    pub max_guesses: Option<usize>,
+
+    /// How many of the top-ranked guesses to list each turn.
+    #[clap(short = 'n', long, default_value_t = 10)]
+    pub suggestions: usize,
+}
+
+// A board with this many possible answers or fewer has them listed.
+const MAX_LISTED: usize = 8;
+
+// Help solve a multi-board game like Quordle: every guess is scored on every board that isn't
+// solved yet, and the suggestions are the guesses that leave the fewest words across all of
+// them. (Quordle has no hard mode, so --hard doesn't do anything here.)
+pub fn run(args: &QuordleArgs, quiet: bool) -> Result<Outcome> {
+    let num_boards = args.boards.max(1);
+    let max_guesses = args
+        .max_guesses
+        .unwrap_or(num_boards + QUORDLE_MAX_GUESSES - QUORDLE_BOARDS);
+    let mut game = MultiBoard::new(
+        args.lists.target_list(),
+        overlay::word_lists(args.lists.fold_accents)?,
+        num_boards,
+    );
+    game.set_duplicate_policy(default_duplicate_policy());
+
+    loop {
+        if game.all_solved() {
+            if !quiet {
+                outln!(
+                    "\nAll {} boards solved in {} guesses!",
+                    num_boards,
+                    game.num_guesses()
+                );
+            }
+            return Ok(Outcome::Solved);
+        }
+
+        if !quiet {
+            outln!(
+                "\nGuess {} of {}.{}",
+                game.num_guesses() + 1,
+                max_guesses,
+                if game.num_guesses() >= max_guesses {
+                    " You're out of guesses, but let's finish anyway."
+                } else {
+                    ""
+                }
+            );
+            print_boards(&game)?;
+        }
+
+        let ranked = game.rank_guesses();
+        if !quiet {
+            outln!("\nSUGGESTED GUESSES (sorted by expected_remaining, added up over the boards)");
+            print_rule(102)?;
+        }
+        for q in ranked.iter().take(args.suggestions) {
+            outln!(
+                "{} {} | average {:.1} left in total ({}), max {} left on one board",
+                if q.winning_boards > 0 { '*' } else { ' ' },
+                q.guess,
+                q.total_expected_remaining,
+                q.boards
+                    .iter()
+                    .map(|b| format!("{:.1}", b.expected_remaining))
+                    .join(" + "),
+                q.worst_board_remaining
+            );
+        }
+
+        if !quiet {
+            print_plan(
+                &game,
+                &ranked,
+                max_guesses.saturating_sub(game.num_guesses()),
+            )?;
+        }
+
+        let guess = loop {
+            prompt(quiet, "\nPlease enter the guess you'll use: ")?;
+            let input_str = match read_input_line()? {
+                Some(s) if args.lists.fold_accents => fold_accents(&s.to_lowercase()),
+                Some(s) => s.to_lowercase(),
+                None => return Ok(Outcome::Unsolved),
+            };
+            if is_valid_word(&input_str) {
+                break input_str;
+            }
+            if quiet {
+                eprintln!("Guess '{}' is not exactly five letters.", input_str);
+                return Ok(Outcome::InvalidInput);
+            }
+            outln!(
+                "Your guess of '{}' was not exactly five letters.",
+                input_str
+            );
+        };
+
+        let mut scores = Vec::new();
+        for board in game.unsolved() {
+            let score = loop {
+                prompt(quiet, &format!("Score on board {}: ", board + 1))?;
+                let input_str = match read_input_line()? {
+                    Some(s) => s,
+                    None => return Ok(Outcome::Unsolved),
+                };
+                match parse_score(&input_str) {
+                    Ok(score) => break score,
+                    Err(e) if quiet => {
+                        eprintln!("{}", e);
+                        return Ok(Outcome::InvalidInput);
+                    }
+                    Err(e) => outln!("Sorry, {}.", e),
+                }
+            };
+            scores.push(score);
+        }
+
+        match game.apply_guess(&guess, &scores) {
+            Ok(()) => {}
+            Err(e @ (Error::InconsistentScore(..) | Error::EmptyCandidateSet(..))) => {
+                if quiet {
+                    eprintln!("{}", e);
+                    return Ok(Outcome::from_error(&e));
+                }
+                outln!(
+                    "\nThat can't be right: {}. Did you enter your guess and scores correctly? Let's try that again.",
+                    e
+                );
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+// Say whether to keep narrowing down all of the boards, or go for one of them, and why.
+fn print_plan(game: &MultiBoard, ranked: &[MultiBoardQuality], guesses_left: usize) -> Result<()> {
+    let num_unsolved = game.unsolved().len();
+    outln!(
+        "\nPLAN: {} guess{} left for {} board{}.",
+        guesses_left,
+        if guesses_left == 1 { "" } else { "es" },
+        num_unsolved,
+        if num_unsolved == 1 { "" } else { "s" }
+    );
+    match game.plan(ranked, guesses_left) {
+        Plan::Probe(guess) => outln!(
+            "Keep narrowing down all of the boards at once: play {}. None of them is close enough to finished to be worth a guess of its own yet.",
+            guess
+        ),
+        Plan::CloseOut { board, guess, why } => {
+            let num_answers = game.boards()[board].remaining_targets().len();
+            let what = if num_answers == 1 {
+                format!("board {} is certainly {}", board + 1, guess)
+            } else {
+                format!(
+                    "board {} has the fewest possibilities ({}), and {} is the best of them",
+                    board + 1,
+                    num_answers,
+                    guess
+                )
+            };
+            match why {
+                CloseOutReason::NoSpareGuesses => outln!(
+                    "There are no guesses to spare, so every guess from here has to be able to win a board: {}. Play it.",
+                    what
+                ),
+                CloseOutReason::FewSpareGuesses => outln!(
+                    "With so few guesses to spare, finish off a board while you can: {}. Play it.",
+                    what
+                ),
+                CloseOutReason::AsGoodAsProbe => outln!(
+                    "Go for a board: {}, and it does nearly as much for the other boards as the best guess overall. Play it.",
+                    what
+                ),
+            }
+        }
+    }
+    Ok(())
+}
+
+// One line per board: solved, or how many words it could still be.
+fn print_boards(game: &MultiBoard) -> Result<()> {
+    for (i, board) in game.boards().iter().enumerate() {
+        let remaining = board.remaining_targets();
+        if game.is_solved(i) {
+            outln!("Board {}: solved, {}", i + 1, remaining[0]);
+        } else if remaining.len() == 1 {
+            outln!("Board {}: it's {}", i + 1, remaining[0]);
+        } else if remaining.len() <= MAX_LISTED {
+            outln!(
+                "Board {}: {} possibilities: {}",
+                i + 1,
+                remaining.len(),
+                remaining.join(" ")
+            );
+        } else {
+            outln!("Board {}: {} possibilities", i + 1, remaining.len());
+        }
+    }
+    Ok(())
+}
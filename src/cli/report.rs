@@ -0,0 +1,164 @@
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::Path;
+use wordle_guesser::*;
+
+// The Markdown report written at the end of a session with --report: the board, and for each
+// turn, how many words were left, what the top suggestions were, and how the guess you made
+// compared with them. It's meant for pasting into a group chat, or keeping.
+
+// How many of the suggestions are kept for each turn's table.
+pub const REPORT_SUGGESTIONS: usize = 5;
+
+// What happened on one turn.
+pub struct TurnRecord {
+    // How many possible answers there were before the guess.
+    pub candidates: usize,
+
+    // The top few suggestions at that point.
+    pub top: Vec<GuessQuality<'static>>,
+
+    // What was played, and the score it got.
+    pub guess: String,
+    pub score: WordScore,
+
+    // Where the guess ranked among everything that could have been played, and how good it was.
+    // None if it wasn't one of the guesses being ranked.
+    pub rank: Option<(usize, usize, GuessQuality<'static>)>,
+}
+
+impl TurnRecord {
+    // Record a turn, from the ranked list of guesses that was shown for it.
+    pub fn new(
+        candidates: usize,
+        ranked: &[GuessQuality<'static>],
+        guess: &str,
+        score: WordScore,
+    ) -> TurnRecord {
+        TurnRecord {
+            candidates,
+            top: ranked.iter().take(REPORT_SUGGESTIONS).copied().collect(),
+            guess: guess.to_string(),
+            score,
+            rank: ranked
+                .iter()
+                .position(|q| q.guess == guess)
+                .map(|i| (i + 1, ranked.len(), ranked[i])),
+        }
+    }
+}
+
+// One game's part of the report.
+pub struct GameReport<'g> {
+    pub name: &'g str,
+    pub turns: &'g [TurnRecord],
+
+    // The answer, if it's been narrowed down to one and it isn't a spoiler.
+    pub answer: Option<&'g str>,
+}
+
+// A score as the colored squares the game uses when you share a result.
+fn score_squares(score: WordScore) -> String {
+    format_score(score)
+        .chars()
+        .map(|c| match c {
+            'G' => '\u{1f7e9}',
+            'y' => '\u{1f7e8}',
+            _ => '\u{2b1b}',
+        })
+        .collect()
+}
+
+fn render_report(games: &[GameReport]) -> String {
+    // Writing to a String can't fail, so the results of write! are ignored.
+    let mut md = String::new();
+    md.push_str("# Wordle session report\n");
+
+    for game in games {
+        if games.len() > 1 {
+            let _ = writeln!(md, "\n## Game: {}", game.name);
+        }
+
+        md.push('\n');
+        for turn in game.turns {
+            let _ = writeln!(
+                md,
+                "{} `{}`  ",
+                score_squares(turn.score),
+                turn.guess.to_uppercase()
+            );
+        }
+        match game.answer {
+            Some(answer) => {
+                let _ = writeln!(md, "\nThe answer: **{}**", answer.to_uppercase());
+            }
+            None if game.turns.is_empty() => md.push_str("\nNo guesses yet.\n"),
+            None => {}
+        }
+
+        for (i, turn) in game.turns.iter().enumerate() {
+            let _ = writeln!(
+                md,
+                "\n### Turn {}: {} possible answer{}\n",
+                i + 1,
+                turn.candidates,
+                if turn.candidates == 1 { "" } else { "s" }
+            );
+
+            if !turn.top.is_empty() {
+                md.push_str("| # | Suggestion | Could win | Average left | Max left |\n");
+                md.push_str("|---|---|---|---|---|\n");
+                for (n, q) in turn.top.iter().enumerate() {
+                    let _ = writeln!(
+                        md,
+                        "| {} | {} | {} | {:.1} | {} |",
+                        n + 1,
+                        q.guess,
+                        if q.has_winning { "yes" } else { "" },
+                        q.expected_remaining,
+                        q.max_remaining
+                    );
+                }
+                md.push('\n');
+            }
+
+            let _ = write!(
+                md,
+                "Played **{}**, which scored `{}`.",
+                turn.guess,
+                format_score(turn.score)
+            );
+            match (&turn.rank, turn.top.first()) {
+                (Some((1, _, _)), _) => md.push_str(" That was the top suggestion."),
+                (Some((rank, of, q)), Some(best)) => {
+                    let _ = write!(
+                        md,
+                        " It ranked #{} of {} (average {:.1} left, max {}), against {:.1} and {} for {}.",
+                        rank,
+                        of,
+                        q.expected_remaining,
+                        q.max_remaining,
+                        best.expected_remaining,
+                        best.max_remaining,
+                        best.guess
+                    );
+                }
+                _ => {}
+            }
+            md.push('\n');
+        }
+    }
+    md
+}
+
+// Write the report for the games in a session.
+pub fn write_report(path: &Path, games: &[GameReport]) -> Result<()> {
+    fs::write(path, render_report(games)).map_err(|e| {
+        io::Error::new(
+            e.kind(),
+            format!("couldn't write {}: {}", path.display(), e),
+        )
+    })?;
+    Ok(())
+}
@@ -0,0 +1,497 @@
+use crate::cli::*;
+use clap::Args;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::PathBuf;
+use wordle_guesser::*;
+
+#[derive(Args)]
+pub struct ResultsArgs {
+    #[clap(flatten)]
+    pub lists: ListArgs,
+
+    /// Add the games in this file of share texts, instead of having them pasted in. The answer
+    /// to a game can go on a line of its own after its grid.
+    #[clap(long, value_name = "FILE")]
+    pub import: Option<PathBuf>,
+
+    /// Just show the report on the games saved so far, without adding any.
+    #[clap(long, conflicts_with = "import")]
+    pub report: bool,
+}
+
+// The games are kept in the overlay directory, next to the word lists, one line each:
+//
+//     1234 4 hard plant .y... ..G.. .GGG. GGGGG
+//
+// That's the puzzle number, how many guesses it took (or X), hard or normal, the answer (or -
+// if it wasn't given), and the rows of the grid. It's plain text, so it's easy to fix by hand.
+const RESULTS_FILE: &str = "results.txt";
+const RESULTS_HEADER: &str = "# wordleguesser results 1";
+
+// A miss counts as this many guesses in the averages, the same as most people's spreadsheets.
+const MISS_GUESSES: usize = WORDLE_MAX_GUESSES + 1;
+
+// One of your games, from its share text.
+#[derive(Clone)]
+struct SavedGame {
+    puzzle: u32,
+    hard: bool,
+    answer: Option<String>,
+    rows: Vec<WordScore>,
+}
+
+impl SavedGame {
+    fn solved(&self) -> bool {
+        self.rows.last() == Some(&ALL_GREEN)
+    }
+
+    // How many guesses it took, with a miss counted as MISS_GUESSES.
+    fn guesses(&self) -> usize {
+        if self.solved() {
+            self.rows.len()
+        } else {
+            MISS_GUESSES
+        }
+    }
+
+    fn to_line(&self) -> String {
+        let mut fields = vec![
+            self.puzzle.to_string(),
+            if self.solved() {
+                self.rows.len().to_string()
+            } else {
+                "X".to_string()
+            },
+            if self.hard { "hard" } else { "normal" }.to_string(),
+            self.answer.clone().unwrap_or_else(|| "-".to_string()),
+        ];
+        fields.extend(
+            self.rows
+                .iter()
+                .map(|&score| format_score(score).to_string()),
+        );
+        fields.join(" ")
+    }
+
+    fn from_line(line: &str) -> Option<SavedGame> {
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+        let [puzzle, _, mode, answer, rows @ ..] = fields.as_slice() else {
+            return None;
+        };
+        if rows.is_empty() || rows.len() > WORDLE_MAX_GUESSES {
+            return None;
+        }
+        Some(SavedGame {
+            puzzle: puzzle.parse().ok()?,
+            hard: *mode == "hard",
+            answer: (*answer != "-").then(|| answer.to_string()),
+            rows: rows
+                .iter()
+                .map(|row| parse_score(row).ok())
+                .collect::<Option<_>>()?,
+        })
+    }
+}
+
+// Keep track of your own games: add the ones in the share texts that get pasted in (or
+// imported from a file), and report on all of them.
+pub fn run(args: &ResultsArgs, quiet: bool) -> Result<Outcome> {
+    let path = overlay::overlay_dir()
+        .ok_or_else(|| {
+            Error::Config("couldn't find your home directory to keep the results in".to_string())
+        })?
+        .join(RESULTS_FILE);
+    let mut games = load_results(&path)?;
+    let solver = args.lists.solver()?;
+
+    if !args.report {
+        let new_games = match &args.import {
+            Some(file) => {
+                let text = fs::read_to_string(file).map_err(|e| {
+                    io::Error::new(e.kind(), format!("couldn't read {}: {}", file.display(), e))
+                })?;
+                parse_share_texts(&text.lines().map(str::to_string).collect::<Vec<_>>())
+            }
+            None => read_pasted_games(quiet)?,
+        };
+
+        let mut added = 0;
+        for mut game in new_games {
+            if let Some(answer) = &game.answer {
+                if let Err(message) = check_answer(&solver, &game, answer) {
+                    eprintln!("{}", message);
+                    continue;
+                }
+            }
+            // Importing a game again replaces it, but keeps the answer if only the old one
+            // had it.
+            if let Some(old) = games.get(&game.puzzle) {
+                if game.answer.is_none() {
+                    game.answer = old.answer.clone();
+                }
+            }
+            games.insert(game.puzzle, game);
+            added += 1;
+        }
+        save_results(&path, &games)?;
+        if !quiet {
+            outln!(
+                "Added {} game{} to {}.",
+                added,
+                if added == 1 { "" } else { "s" },
+                path.display()
+            );
+        }
+    }
+
+    if games.is_empty() {
+        if !quiet {
+            outln!("There aren't any games saved yet. Paste in a share text to add one.");
+        }
+        return Ok(Outcome::Solved);
+    }
+    report(&solver, &games, quiet)?;
+    Ok(Outcome::Solved)
+}
+
+fn load_results(path: &std::path::Path) -> Result<BTreeMap<u32, SavedGame>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(e) => {
+            return Err(io::Error::new(
+                e.kind(),
+                format!("couldn't read {}: {}", path.display(), e),
+            )
+            .into())
+        }
+    };
+
+    let mut games = BTreeMap::new();
+    for (number, line) in text.lines().enumerate() {
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        let game = SavedGame::from_line(line).ok_or_else(|| {
+            Error::Config(format!(
+                "line {} of {} isn't a game: \"{}\"",
+                number + 1,
+                path.display(),
+                line
+            ))
+        })?;
+        games.insert(game.puzzle, game);
+    }
+    Ok(games)
+}
+
+// Write the games out in order of puzzle number. They go to a new file first, and then replace
+// the old one, so a crash partway through can't lose the games already saved.
+fn save_results(path: &std::path::Path, games: &BTreeMap<u32, SavedGame>) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("couldn't create {}: {}", dir.display(), e),
+            )
+        })?;
+    }
+    let mut text = format!("{}\n", RESULTS_HEADER);
+    for game in games.values() {
+        text.push_str(&game.to_line());
+        text.push('\n');
+    }
+    let new_path = path.with_extension("new");
+    fs::write(&new_path, text)
+        .and_then(|_| fs::rename(&new_path, path))
+        .map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!("couldn't write {}: {}", path.display(), e),
+            )
+        })?;
+    Ok(())
+}
+
+// Read share texts from the terminal, one at a time, each ending with a blank line once its
+// squares have come in. A game pasted without its answer gets asked for one, since that's what
+// the comparison with the solver needs; it can be skipped.
+fn read_pasted_games(quiet: bool) -> Result<Vec<SavedGame>> {
+    let mut games = Vec::new();
+    loop {
+        prompt(
+            quiet,
+            "\nPaste a share text (the \"Wordle 1,234 4/6\" line and the squares), then a blank line.\nOr just press Enter to finish:\n",
+        )?;
+        let mut lines = Vec::new();
+        while let Some(line) = read_input_line()? {
+            if line.is_empty()
+                && (lines.is_empty() || lines.iter().any(|l: &String| parse_score(l).is_ok()))
+            {
+                break;
+            }
+            lines.push(line);
+        }
+        if lines.is_empty() {
+            return Ok(games);
+        }
+
+        for mut game in parse_share_texts(&lines) {
+            if game.answer.is_none() && !quiet {
+                prompt(
+                    quiet,
+                    &format!(
+                        "What was the answer to Wordle {}? (Press Enter to skip.) ",
+                        game.puzzle
+                    ),
+                )?;
+                game.answer = read_input_line()?
+                    .map(|answer| answer.to_lowercase())
+                    .filter(|answer| !answer.is_empty());
+            }
+            games.push(game);
+        }
+    }
+}
+
+// Pick the games out of some share texts. Each starts with its "Wordle 1,234 4/6" line, and
+// then has its rows of squares, and optionally the answer on a line of its own. Anything else,
+// like the link the game adds, gets skipped. A game that doesn't add up, like one with a
+// different number of rows from what its first line says, is left out with a warning.
+fn parse_share_texts(lines: &[String]) -> Vec<SavedGame> {
+    let mut games = Vec::new();
+    let mut current: Option<(SavedGame, Option<usize>)> = None;
+    let mut finish = |current: Option<(SavedGame, Option<usize>)>| {
+        let Some((game, claimed)) = current else {
+            return;
+        };
+        let actual = game.solved().then_some(game.rows.len());
+        if game.rows.is_empty() {
+            eprintln!("Wordle {} doesn't have any squares.", game.puzzle);
+        } else if actual != claimed || (!game.solved() && game.rows.len() != WORDLE_MAX_GUESSES) {
+            eprintln!(
+                "Wordle {} says it took {}, but its squares don't match that.",
+                game.puzzle,
+                claimed.map_or("X".to_string(), |n| n.to_string())
+            );
+        } else {
+            games.push(game);
+        }
+    };
+
+    for line in lines {
+        let line = line.trim();
+        if let Some((puzzle, claimed, hard)) = parse_header(line) {
+            finish(current.take());
+            current = Some((
+                SavedGame {
+                    puzzle,
+                    hard,
+                    answer: None,
+                    rows: Vec::new(),
+                },
+                claimed,
+            ));
+            continue;
+        }
+        let Some((game, _)) = current.as_mut() else {
+            continue;
+        };
+        if let Ok(score) = parse_score(line) {
+            game.rows.push(score);
+        } else if is_valid_word(&line.to_lowercase()) {
+            game.answer = Some(line.to_lowercase());
+        }
+    }
+    finish(current);
+    games
+}
+
+// The first line of a share text: "Wordle 1,234 4/6", with a * on the end for hard mode, and an
+// X instead of the number for a miss. Some countries write the puzzle number with a dot or a
+// space for the thousands.
+fn parse_header(line: &str) -> Option<(u32, Option<usize>, bool)> {
+    let rest = line.strip_prefix("Wordle ")?;
+    let (puzzle, result) = rest.trim().rsplit_once(' ')?;
+    let puzzle = puzzle
+        .chars()
+        .filter(|c| !matches!(c, ',' | '.' | ' '))
+        .collect::<String>()
+        .parse()
+        .ok()?;
+    let hard = result.ends_with('*');
+    let (guesses, _) = result.trim_end_matches('*').split_once('/')?;
+    let claimed = match guesses {
+        "X" | "x" => None,
+        n => Some(n.parse().ok()?),
+    };
+    Some((puzzle, claimed, hard))
+}
+
+// Make sure a game's squares could have come from its answer, which catches a typo'd answer or
+// a grid pasted with the wrong game.
+fn check_answer(
+    solver: &Solver,
+    game: &SavedGame,
+    answer: &str,
+) -> std::result::Result<(), String> {
+    if !solver.valid_guesses().contains(&answer) {
+        return Err(format!(
+            "Skipping Wordle {}: '{}' isn't in the word list.",
+            game.puzzle, answer
+        ));
+    }
+    if reconstruct_guesses(
+        solver.valid_guesses(),
+        answer,
+        &game.rows,
+        game.hard,
+        0,
+        solver.duplicate_policy(),
+    )
+    .sequences
+        == 0
+    {
+        return Err(format!(
+            "Skipping Wordle {}: there's no way to get those squares if the answer was {}.",
+            game.puzzle,
+            answer.to_uppercase()
+        ));
+    }
+    Ok(())
+}
+
+// The current and longest winning streaks. Like the game's own, a streak is broken by a miss or
+// a missing day, so a gap in the saved games breaks it too.
+fn streaks(games: &BTreeMap<u32, SavedGame>) -> (usize, usize) {
+    let mut current = 0;
+    let mut longest = 0;
+    let mut last_puzzle = None;
+    for game in games.values() {
+        if last_puzzle.is_none_or(|last: u32| last + 1 != game.puzzle) {
+            current = 0;
+        }
+        current = if game.solved() { current + 1 } else { 0 };
+        longest = longest.max(current);
+        last_puzzle = Some(game.puzzle);
+    }
+    (current, longest)
+}
+
+fn report(solver: &Solver, games: &BTreeMap<u32, SavedGame>, quiet: bool) -> Result<()> {
+    let won = games.values().filter(|game| game.solved()).count();
+    let average =
+        games.values().map(|game| game.guesses()).sum::<usize>() as f64 / games.len() as f64;
+    let (current, longest) = streaks(games);
+
+    // The solver plays every puzzle with a known answer, to see how you compare. Answers that
+    // the solver doesn't start out considering (say, rarer words without --solutions) are left
+    // out, since it couldn't play them fairly.
+    let positions: HashMap<&str, usize> = solver
+        .remaining_targets()
+        .iter()
+        .enumerate()
+        .map(|(i, &word)| (word, i))
+        .collect();
+    let compared = games
+        .values()
+        .filter_map(|game| Some((game, *positions.get(game.answer.as_deref()?)?)))
+        .collect::<Vec<_>>();
+    let solver_games = Simulation::new(solver, &RankerStrategy, None)
+        .play(&compared.iter().map(|&(_, i)| i).collect::<Vec<_>>());
+    let solver_guesses = |game: &GameResult| {
+        if game.solved() && game.guesses.len() <= WORDLE_MAX_GUESSES {
+            game.guesses.len()
+        } else {
+            MISS_GUESSES
+        }
+    };
+
+    let yours = compared
+        .iter()
+        .map(|(game, _)| game.guesses())
+        .sum::<usize>();
+    let theirs = solver_games.iter().map(solver_guesses).sum::<usize>();
+    let (mut ahead, mut tied, mut behind) = (0, 0, 0);
+    for ((game, _), solver_game) in compared.iter().zip(&solver_games) {
+        match game.guesses().cmp(&solver_guesses(solver_game)) {
+            std::cmp::Ordering::Less => ahead += 1,
+            std::cmp::Ordering::Equal => tied += 1,
+            std::cmp::Ordering::Greater => behind += 1,
+        }
+    }
+
+    if quiet {
+        outln!(
+            "{} {} {} {} {:.3} {} {:.3}",
+            games.len(),
+            won,
+            current,
+            longest,
+            average,
+            compared.len(),
+            theirs as f64 / compared.len().max(1) as f64
+        );
+        return Ok(());
+    }
+
+    outln!(
+        "\n{} game{}, {} won ({:.0}%). Current streak {}, longest {}.",
+        games.len(),
+        if games.len() == 1 { "" } else { "s" },
+        won,
+        won as f64 * 100.0 / games.len() as f64,
+        current,
+        longest
+    );
+
+    let mut distribution = vec![0; MAX_SIMULATED_GUESSES + 1];
+    for game in games.values().filter(|game| game.solved()) {
+        distribution[game.rows.len()] += 1;
+    }
+    let summary = SimulationSummary {
+        games: games.len(),
+        average_guesses: average,
+        worst_guesses: games.values().map(|game| game.guesses()).max().unwrap_or(0),
+        failures: games.len() - won,
+        distribution,
+    };
+    outln!("\nYour guess distribution:");
+    for line in crate::cli::simulate::distribution_chart(&summary) {
+        outln!("{}", line);
+    }
+    outln!(
+        "\nYou average {:.2} guesses a game, counting a miss as {}.",
+        average,
+        MISS_GUESSES
+    );
+
+    if compared.is_empty() {
+        outln!("Give the answers to your games to see how the solver does on the same puzzles.");
+        return Ok(());
+    }
+    outln!(
+        "On the {} puzzle{} with answers, you averaged {:.2}, and the solver {:.2}{}.",
+        compared.len(),
+        if compared.len() == 1 { "" } else { "s" },
+        yours as f64 / compared.len() as f64,
+        theirs as f64 / compared.len() as f64,
+        describe_hard_mode(solver)
+    );
+    outln!(
+        "You did better on {}, the same on {}, and worse on {}.",
+        ahead,
+        tied,
+        behind
+    );
+    let left_out = games.values().filter(|game| game.answer.is_some()).count() - compared.len();
+    if left_out > 0 {
+        outln!(
+            "({} answer{} left out for not being in the solver's list. Try --solutions.)",
+            left_out,
+            if left_out == 1 { " was" } else { "s were" }
+        );
+    }
+    Ok(())
+}
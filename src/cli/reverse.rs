@@ -0,0 +1,188 @@
+use crate::cli::*;
+use clap::Args;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use wordle_guesser::*;
+
+#[derive(Args)]
+pub struct ReverseArgs {
+    #[clap(flatten)]
+    pub lists: ListArgs,
+
+    /// Read the grid from this file, instead of having it pasted in.
+    #[clap(long, value_name = "FILE")]
+    pub grid: Option<PathBuf>,
+
+    /// How many of the words that fit each row to show. The most common words come first.
+    #[clap(short = 'n', long, value_name = "N", default_value = "30")]
+    pub show: usize,
+
+    /// The answer to the game the grid is from.
+    pub answer: String,
+}
+
+// Work out what a friend must have guessed, from nothing but the colors in their share grid
+// and the day's answer. See reverse.rs for how.
+pub fn run(args: &ReverseArgs, quiet: bool) -> Result<Outcome> {
+    let answer = args.answer.to_lowercase();
+    if !is_valid_word(&answer) {
+        eprintln!("'{}' isn't a {} letter word.", answer, WORD_LENGTH);
+        return Ok(Outcome::InvalidInput);
+    }
+
+    let lines = match &args.grid {
+        Some(path) => fs::read_to_string(path)
+            .map_err(|e| {
+                io::Error::new(e.kind(), format!("couldn't read {}: {}", path.display(), e))
+            })?
+            .lines()
+            .map(|line| line.trim().to_string())
+            .collect(),
+        None => read_pasted_grid(quiet)?,
+    };
+    let scores = match parse_grid(&lines) {
+        Ok(scores) => scores,
+        Err(message) => {
+            eprintln!("{}", message);
+            return Ok(Outcome::InvalidInput);
+        }
+    };
+
+    let solver = args.lists.solver()?;
+    let reconstruction = reconstruct_guesses(
+        solver.valid_guesses(),
+        &answer,
+        &scores,
+        args.lists.is_hard(),
+        args.lists.free_guesses(),
+        solver.duplicate_policy(),
+    );
+
+    // Nobody guesses words they've never heard of, so the likeliest words go first.
+    let rank: HashMap<&str, usize> = word_frequencies()
+        .iter()
+        .enumerate()
+        .map(|(rank, &(word, _))| (word, rank))
+        .collect();
+    let by_commonness = |words: &[&'static str]| {
+        let mut words = words.to_vec();
+        words.sort_by_key(|w| (rank.get(w).copied().unwrap_or(usize::MAX), *w));
+        words
+    };
+
+    if quiet {
+        for row in &reconstruction.rows {
+            outln!(
+                "{} {}",
+                format_score(row.score),
+                by_commonness(&row.words).join(" ")
+            );
+        }
+        return Ok(if reconstruction.sequences > 0 {
+            Outcome::Solved
+        } else {
+            Outcome::InvalidInput
+        });
+    }
+
+    outln!(
+        "\nWorking back from {} row{} of colors to {}{}:\n",
+        scores.len(),
+        if scores.len() == 1 { "" } else { "s" },
+        answer.to_uppercase(),
+        describe_hard_mode(&solver)
+    );
+    for (turn, row) in reconstruction.rows.iter().enumerate() {
+        let words = by_commonness(&row.words);
+        let heading = match words.len() {
+            0 => "couldn't have been anything".to_string(),
+            1 => format!("was {}", words[0].to_uppercase()),
+            n => format!("must have been one of these {} words", n),
+        };
+        if plain_output() {
+            outln!(
+                "Guess {}, {}: {}.",
+                turn + 1,
+                describe_colors(row.score),
+                heading
+            );
+        } else {
+            outln!(
+                "Guess {}  {}  {}",
+                turn + 1,
+                format_score(row.score),
+                heading
+            );
+        }
+        if words.len() > 1 {
+            let shown = words.iter().take(args.show).copied().collect::<Vec<_>>();
+            for line in word_columns(&shown, false) {
+                outln!("    {}", line);
+            }
+            if words.len() > shown.len() {
+                outln!("    ...and {} less common ones", words.len() - shown.len());
+            }
+        }
+        outln!();
+    }
+
+    match reconstruction.sequences {
+        0 => {
+            outln!(
+                "No way of playing fits the whole grid{}. Check the answer, and whether they were playing in hard mode.",
+                if args.lists.is_hard() { " in hard mode" } else { "" }
+            );
+            Ok(Outcome::InvalidInput)
+        }
+        1 => {
+            outln!("There's only one way they could have played it.");
+            Ok(Outcome::Solved)
+        }
+        n => {
+            outln!(
+                "Altogether, there are {} ways they could have played it.",
+                n
+            );
+            Ok(Outcome::Solved)
+        }
+    }
+}
+
+// Read the grid from the terminal. Share text usually has a line like "Wordle 1,234 4/6" and a
+// blank line before the squares, so the grid only ends at a blank line once some rows have come
+// in, or at the end of the input.
+fn read_pasted_grid(quiet: bool) -> Result<Vec<String>> {
+    prompt(
+        quiet,
+        "Paste the share grid (or type the rows like \".y.GG\"), then a blank line:\n",
+    )?;
+    let mut lines = Vec::new();
+    while let Some(line) = read_input_line()? {
+        if line.is_empty() && lines.iter().any(|l: &String| parse_score(l).is_ok()) {
+            break;
+        }
+        lines.push(line);
+    }
+    Ok(lines)
+}
+
+// The rows of the grid, skipping anything around them that isn't one, like the "Wordle 1,234
+// 4/6" line. Once the answer has been found, that's the end of the game, so a row after a
+// winning one means something got pasted wrong.
+fn parse_grid(lines: &[String]) -> std::result::Result<Vec<WordScore>, String> {
+    let mut scores = Vec::new();
+    for line in lines {
+        let Ok(score) = parse_score(line) else {
+            continue;
+        };
+        if scores.last() == Some(&ALL_GREEN) {
+            return Err("The grid goes on after the answer was found.".to_string());
+        }
+        scores.push(score);
+    }
+    if scores.is_empty() {
+        return Err("There weren't any rows of colors in the grid.".to_string());
+    }
+    Ok(scores)
+}
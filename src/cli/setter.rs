@@ -0,0 +1,156 @@
+use crate::cli::*;
+use clap::{ArgEnum, Args};
+use rand::seq::SliceRandom;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use wordle_guesser::*;
+
+#[derive(Args)]
+pub struct SetterArgs {
+    #[clap(flatten)]
+    pub lists: ListArgs,
+
+    #[clap(flatten)]
+    pub memory: MemoryArgs,
+
+    /// Instead of the table, list answers of this difficulty, in a random order, one per line:
+    /// "easy", "medium", "hard", or "fiendish". Each is a quarter of the answers.
+    #[clap(long, arg_enum, value_name = "LEVEL")]
+    pub level: Option<Level>,
+
+    /// How many words to show: the hardest and easiest this many in the table, or this many of
+    /// the words at the --level. The default is 15 for the table, and every word at the level.
+    #[clap(short = 'n', long, value_name = "N")]
+    pub count: Option<usize>,
+
+    /// Seed the order the --level words come out in, to get the same list again.
+    #[clap(long)]
+    pub seed: Option<u64>,
+
+    /// Write the --level words to this file, instead of printing them.
+    #[clap(short, long, value_name = "FILE", requires = "level")]
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ArgEnum)]
+pub enum Level {
+    Easy,
+    Medium,
+    Hard,
+    Fiendish,
+}
+
+impl Level {
+    // The answers at this level, out of all of them from hardest to easiest. This goes by where
+    // they rank rather than the difficulty itself, since averaging the percentiles bunches the
+    // difficulties up in the middle.
+    fn pick(self, ratings: &[AnswerDifficulty]) -> &[AnswerDifficulty] {
+        let quarter = match self {
+            Level::Fiendish => 0,
+            Level::Hard => 1,
+            Level::Medium => 2,
+            Level::Easy => 3,
+        };
+        &ratings[ratings.len() * quarter / 4..ratings.len() * (quarter + 1) / 4]
+    }
+}
+
+// How many of the hardest and easiest words the table shows, normally.
+const WORDS_SHOWN: usize = 15;
+
+// For people running their own Wordle: rate every answer by how hard it is to find (see
+// difficulty.rs), and either show the hardest and easiest ones, or pick out a list of words of
+// some difficulty to use as the secret words.
+pub fn run(args: &SetterArgs, quiet: bool) -> Result<Outcome> {
+    let mut solver = args.lists.solver()?;
+    let strategies = real_strategies();
+
+    // With a level, the words are the whole output, so nothing else goes to stdout.
+    let chatty = !quiet && args.level.is_none();
+    args.memory.precompute(&mut solver, !chatty)?;
+    if chatty {
+        outln!(
+            "Playing {} strategies against all {} answers{}...\n",
+            strategies.len(),
+            solver.remaining_targets().len(),
+            describe_hard_mode(&solver)
+        );
+    }
+    let ratings = rate_answers(&solver, &strategies);
+
+    match args.level {
+        None => print_difficulty_table(&ratings, args.count.unwrap_or(WORDS_SHOWN), quiet),
+        Some(level) => {
+            let mut words = level
+                .pick(&ratings)
+                .iter()
+                .map(|r| r.word)
+                .collect::<Vec<_>>();
+            words.shuffle(&mut seeded_rng(args.seed));
+            words.truncate(args.count.unwrap_or(words.len()));
+
+            match &args.output {
+                None => {
+                    for word in &words {
+                        outln!("{}", word);
+                    }
+                }
+                Some(path) => {
+                    let text = words.iter().map(|w| format!("{}\n", w)).collect::<String>();
+                    fs::write(path, text).map_err(|e| {
+                        io::Error::new(
+                            e.kind(),
+                            format!("couldn't write {}: {}", path.display(), e),
+                        )
+                    })?;
+                    if !quiet {
+                        outln!("Wrote {} words to {}.", words.len(), path.display());
+                    }
+                }
+            }
+            Ok(Outcome::Solved)
+        }
+    }
+}
+
+// The hardest few words and the easiest few, with what makes them that way.
+fn print_difficulty_table(
+    ratings: &[AnswerDifficulty],
+    count: usize,
+    quiet: bool,
+) -> Result<Outcome> {
+    let row = |r: &AnswerDifficulty| {
+        format!(
+            "{}  {:10.1}  {:7.2}  {:5}  {:4}  {:10}",
+            r.word,
+            r.difficulty,
+            r.average_guesses,
+            r.worst_guesses,
+            r.trap_size,
+            r.commonness_rank
+        )
+    };
+    if quiet {
+        for r in ratings {
+            outln!("{}", row(r));
+        }
+        return Ok(Outcome::Solved);
+    }
+
+    let header = "word   difficulty  average  worst  trap  commonness";
+    outln!("The hardest answers:\n\n{}", header);
+    for r in ratings.iter().take(count) {
+        outln!("{}", row(r));
+    }
+    outln!("\nThe easiest answers:\n\n{}", header);
+    for r in ratings.iter().rev().take(count).rev() {
+        outln!("{}", row(r));
+    }
+    outln!(
+        "\n\"average\" and \"worst\" are how many guesses the strategies took, \"trap\" is the biggest group\n\
+         of answers it's in that are all the same but for one letter, and \"commonness\" is where it\n\
+         comes in the answers from most common to least."
+    );
+    Ok(Outcome::Solved)
+}
@@ -0,0 +1,299 @@
+use crate::cli::*;
+use checkpoint::Checkpoint;
+use clap::Args;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use wordle_guesser::*;
+
+#[derive(Args)]
+pub struct SimulateArgs {
+    #[clap(flatten)]
+    pub lists: ListArgs,
+
+    #[clap(flatten)]
+    pub memory: MemoryArgs,
+
+    /// The strategy to simulate: "ranker" (the suggestions this program normally makes),
+    /// "entropy" (the most information on average), "worstcase" (the smallest worst case), or
+    /// one of the baselines to compare them against: "random" (a random word that could still be
+    /// the answer), "frequent" (the most common word that could still be the answer), or
+    /// "greens" (whatever gets the most green letters). Use "all" to compare every strategy side
+    /// by side.
+    #[clap(default_value = "all")]
+    pub strategy: String,
+
+    /// Seed the random choices (the "random" strategy's guesses), so that a run can be repeated
+    /// exactly.
+    #[clap(long)]
+    pub seed: Option<u64>,
+
+    /// Also write every game to this file, one line per answer: the strategy, the answer, how
+    /// many guesses it took, and the guesses played. The columns are separated by tabs, for
+    /// sorting or loading into a spreadsheet.
+    #[clap(long, value_name = "FILE")]
+    pub per_answer: Option<PathBuf>,
+
+    /// Save the games to this file every few hundred games, so a long run can be picked up
+    /// again with --resume if it's stopped.
+    #[clap(long, value_name = "FILE")]
+    pub checkpoint: Option<PathBuf>,
+
+    /// Carry on from the games saved in the --checkpoint file, instead of starting over.
+    #[clap(long, requires = "checkpoint")]
+    pub resume: bool,
+
+    /// Start over even if the --checkpoint file already has games from this same run,
+    /// throwing them away.
+    #[clap(long, requires = "checkpoint", conflicts_with = "resume")]
+    pub force: bool,
+}
+
+// How many games get played between saves to the checkpoint file.
+const CHECKPOINT_GAMES: usize = 250;
+
+// One game as a line of the --per-answer file, which is also how games are saved in the
+// checkpoint file.
+fn game_line(strategy: &str, game: &GameResult) -> String {
+    format!(
+        "{}\t{}\t{}\t{}",
+        strategy,
+        game.answer,
+        game.guesses.len(),
+        game.guesses.join(" ")
+    )
+}
+
+// Read back the games saved in a checkpoint file, by strategy. The words in them have to be
+// ones the solver knows about.
+fn parse_saved_games(
+    lines: &[String],
+    solver: &Solver,
+) -> Result<HashMap<String, Vec<GameResult>>> {
+    let words: HashMap<&str, &'static str> = solver
+        .valid_guesses()
+        .iter()
+        .chain(solver.remaining_targets())
+        .map(|&w| (w, w))
+        .collect();
+    let mut saved: HashMap<String, Vec<GameResult>> = HashMap::new();
+    for line in lines {
+        let bad_line =
+            || Error::Checkpoint(format!("the saved game '{}' doesn't make sense", line));
+        let fields = line.split('\t').collect::<Vec<_>>();
+        let [strategy, answer, _, sequence] = fields.as_slice() else {
+            return Err(bad_line());
+        };
+        let answer = *words.get(answer).ok_or_else(bad_line)?;
+        let guesses = sequence
+            .split_whitespace()
+            .map(|guess| words.get(guess).copied())
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(bad_line)?;
+        saved
+            .entry(strategy.to_string())
+            .or_default()
+            .push(GameResult { answer, guesses });
+    }
+    Ok(saved)
+}
+
+// Simulate a game against every possible solution with each of the strategies, and print a
+// table comparing how they did. With a checkpoint, the games already saved in it are used
+// instead of being played again, and the rest are saved as they're played.
+fn run_simulations(
+    solver: &Solver,
+    strategies: &[Box<dyn Strategy>],
+    seed: Option<u64>,
+    mut per_answer: Option<&mut dyn Write>,
+    mut checkpoint: Option<(Checkpoint, HashMap<String, Vec<GameResult>>)>,
+    quiet: bool,
+) -> Result<()> {
+    if !quiet {
+        outln!(
+            "Simulating {} games per strategy{}...\n",
+            solver.remaining_targets().len(),
+            describe_hard_mode(solver)
+        );
+        outln!(
+            "strategy  | average | worst | failures (more than {} guesses)",
+            WORDLE_MAX_GUESSES
+        );
+        print_rule(58)?;
+    }
+
+    let mut summaries = Vec::new();
+    let mut violations = Vec::new();
+    for strategy in strategies {
+        let results = match checkpoint.as_mut() {
+            None => simulate(solver, strategy.as_ref(), seed),
+            Some((checkpoint, saved)) => {
+                let mut games: HashMap<&str, GameResult> = saved
+                    .remove(strategy.name())
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|game| (game.answer, game))
+                    .collect();
+                let targets = solver.remaining_targets();
+                let unplayed = (0..targets.len())
+                    .filter(|&game| !games.contains_key(targets[game]))
+                    .collect::<Vec<_>>();
+
+                let simulation = Simulation::new(solver, strategy.as_ref(), seed);
+                for chunk in unplayed.chunks(CHECKPOINT_GAMES) {
+                    let played = simulation.play(chunk);
+                    checkpoint.save(played.iter().map(|game| game_line(strategy.name(), game)))?;
+                    games.extend(played.into_iter().map(|game| (game.answer, game)));
+                }
+                targets.iter().filter_map(|t| games.remove(t)).collect()
+            }
+        };
+        let summary = summarize(&results);
+        violations.push((strategy.name(), hard_mode_violations(solver, &results)));
+
+        if let Some(file) = per_answer.as_mut() {
+            for game in &results {
+                writeln!(file, "{}", game_line(strategy.name(), game))?;
+            }
+        }
+
+        outln!(
+            "{:9} | {:7.3} | {:5} | {}",
+            strategy.name(),
+            summary.average_guesses,
+            summary.worst_guesses,
+            summary.failures
+        );
+        summaries.push((strategy.name(), summary));
+    }
+
+    // The averages can hide a lot, like a strategy that's usually quick but now and then takes
+    // forever, so show how the games were spread out as well.
+    if !quiet {
+        for (name, summary) in &summaries {
+            outln!("\nGuess distribution for {}:", name);
+            for line in distribution_chart(summary) {
+                outln!("{}", line);
+            }
+        }
+    }
+    for (name, violations) in &violations {
+        warn_about_hard_mode(&format!("the {} strategy", name), violations);
+    }
+
+    Ok(())
+}
+
+// How wide the longest bar in a distribution chart is.
+const CHART_WIDTH: usize = 40;
+
+// A bar chart of how many games took each number of guesses, like the statistics screen in the
+// game: one bar each for 1 to WORDLE_MAX_GUESSES, and X for the ones that took longer. Any
+// number of games that isn't zero gets at least a sliver of a bar, so the rare cases still
+// stand out. With --plain it's just the numbers, one sentence a line.
+pub fn distribution_chart(summary: &SimulationSummary) -> Vec<String> {
+    let mut rows = (1..=WORDLE_MAX_GUESSES)
+        .map(|guesses| (guesses.to_string(), summary.distribution[guesses]))
+        .collect::<Vec<_>>();
+    rows.push(("X".to_string(), summary.failures));
+
+    let most = rows
+        .iter()
+        .map(|(_, count)| *count)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    rows.iter()
+        .map(|(label, count)| {
+            if plain_output() {
+                let label = match label.as_str() {
+                    "X" => format!("More than {}", WORDLE_MAX_GUESSES),
+                    n => format!("In {}", n),
+                };
+                return format!(
+                    "{}: {} games ({:.1}%)",
+                    label,
+                    count,
+                    *count as f64 * 100.0 / summary.games.max(1) as f64
+                );
+            }
+            let bar = (count * CHART_WIDTH).div_ceil(most);
+            format!(
+                "{:>2} | {:<width$} {:5} ({:.1}%)",
+                label,
+                "#".repeat(bar),
+                count,
+                *count as f64 * 100.0 / summary.games.max(1) as f64,
+                width = CHART_WIDTH
+            )
+        })
+        .collect()
+}
+
+// Play a simulated game against every possible solution word and report how many guesses it
+// took.
+pub fn run(args: &SimulateArgs, quiet: bool) -> Result<Outcome> {
+    let strategies = if args.strategy == "all" {
+        all_strategies()
+    } else {
+        vec![strategy_by_name(&args.strategy)?]
+    };
+
+    let mut per_answer = match &args.per_answer {
+        Some(path) => {
+            let file = File::create(path).map_err(|e| {
+                io::Error::new(
+                    e.kind(),
+                    format!("couldn't create {}: {}", path.display(), e),
+                )
+            })?;
+            let mut file = BufWriter::new(file);
+            writeln!(file, "strategy\tanswer\tguesses\tsequence")?;
+            Some(file)
+        }
+        None => None,
+    };
+
+    let mut solver = args.lists.solver()?;
+
+    // The checkpoint is only any good for picking up the same run again: the same games, in
+    // the same mode, with the same random choices.
+    let checkpoint = match &args.checkpoint {
+        Some(path) => {
+            let header = format!(
+                "{} games per strategy{}, seed {}",
+                solver.remaining_targets().len(),
+                describe_hard_mode(&solver),
+                args.seed
+                    .map_or("none".to_string(), |seed| seed.to_string())
+            );
+            let (checkpoint, lines) = Checkpoint::open(path, &header, args.resume, args.force)?;
+            let saved = parse_saved_games(&lines, &solver)?;
+            if !quiet && !lines.is_empty() {
+                outln!(
+                    "Picking up from the {} games already in {}.",
+                    lines.len(),
+                    path.display()
+                );
+            }
+            Some((checkpoint, saved))
+        }
+        None => None,
+    };
+
+    args.memory.precompute(&mut solver, quiet)?;
+    run_simulations(
+        &solver,
+        &strategies,
+        args.seed,
+        per_answer.as_mut().map(|file| file as &mut dyn Write),
+        checkpoint,
+        quiet,
+    )?;
+
+    if let Some(mut file) = per_answer {
+        file.flush()?;
+    }
+    Ok(Outcome::Solved)
+}
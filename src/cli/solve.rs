@@ -0,0 +1,2120 @@
+use crate::cli::*;
+use clap::{ArgEnum, Args, Parser};
+use debug_dump::DebugDump;
+use itertools::Itertools;
+use rand::RngCore;
+use std::collections::{BTreeMap, HashSet};
+use std::path::PathBuf;
+use wordle_guesser::*;
+
+#[derive(Args)]
+pub struct SolveArgs {
+    #[clap(flatten)]
+    pub lists: ListArgs,
+
+    /// How many of the top-ranked guesses to list each turn. (Possibly-winning guesses
+    /// just past the cutoff are still shown.) The default is 15.
+    #[clap(short = 'n', long)]
+    pub suggestions: Option<usize>,
+
+    /// No spoilers: never print words from the Wordle solution list, and when the answer is
+    /// certain, don't show it until you type "reveal". You still get suggested guesses, just not
+    /// the answer handed to you.
+    #[clap(long)]
+    pub no_spoilers: bool,
+
+    /// Which strategy puts the suggestions in order, and plays 'finish' and 'best now':
+    /// "ranker" (the default), "entropy", "worstcase", "random", "frequent", or "greens". See the
+    /// simulate subcommand for how they compare.
+    #[clap(long)]
+    pub strategy: Option<String>,
+
+    /// Only suggest guesses that pass this filter, e.g. 'not ends_with(s) and unique_letters'.
+    /// The rules are unique_letters, starts_with(LETTERS), ends_with(LETTERS),
+    /// contains(LETTERS), has(LETTERS), and common(N), combined with not, and, or, and
+    /// parentheses. The possible answers aren't affected.
+    #[clap(long, value_name = "EXPR")]
+    pub suggest_filter: Option<String>,
+
+    /// Instead of the usual list, show every guess where nothing else is better on both the
+    /// average and the worst case. That's the full range of trade-offs between playing it safe
+    /// and going for a good average, from one extreme to the other.
+    #[clap(long)]
+    pub pareto: bool,
+
+    /// Also show the single best guess by each way of measuring it (the usual product,
+    /// entropy, worst case, and chance of winning by the next turn), to see where they agree.
+    #[clap(long)]
+    pub compare_metrics: bool,
+
+    /// Under each suggestion, also show the best guess to play next if its worst-case score
+    /// comes up, and how many words that would leave at most, to see whether the worst case is
+    /// really so bad.
+    #[clap(long)]
+    pub follow_up: bool,
+
+    /// After you enter your guess, and before its score, show the best next guess for each of
+    /// the likeliest scores it could get, so you can plan ahead while the game is showing you
+    /// the colors. Just --preview shows the 8 likeliest.
+    #[clap(
+        long,
+        value_name = "SCORES",
+        min_values = 0,
+        default_missing_value = "8"
+    )]
+    pub preview: Option<usize>,
+
+    /// Coach mode: when you type in a guess that isn't the top suggestion, show how it stacks
+    /// up against it before asking whether you still want to play it.
+    #[clap(long)]
+    pub coach: bool,
+
+    /// When guesses are equally good, list the ones that are easier to type first (home row
+    /// letters, alternating hands) instead of going alphabetically.
+    #[clap(long, overrides_with = "no-easy-typing")]
+    pub easy_typing: bool,
+
+    /// Break ties alphabetically, even if the config file says easy_typing.
+    #[clap(long, overrides_with = "easy-typing")]
+    pub no_easy_typing: bool,
+
+    /// Soft hard mode: guesses that fit every score so far are treated as that many percent
+    /// better than they really are, so they get suggested over slightly better guesses that
+    /// don't. That steers you towards playing by the hard mode rules without forbidding a good
+    /// probe. Just --soft-hard means 20 percent.
+    #[clap(
+        long,
+        value_name = "PERCENT",
+        min_values = 0,
+        default_missing_value = "20",
+        conflicts_with_all = &["hard", "hard-after"]
+    )]
+    pub soft_hard: Option<u32>,
+
+    /// List the possible answers in alphabetical order, down the columns. Normally they're in
+    /// order of how common they are, across the rows.
+    #[clap(long)]
+    pub alphabetical: bool,
+
+    /// Which game you're playing: "wordle", "nerdle", where the answer is an equation like
+    /// 12+35=47 instead of a word, or "peaks", for Wordle Peaks, where each letter's clue says
+    /// whether the answer's letter comes earlier or later in the alphabet, or "antiwordle",
+    /// where the goal is to avoid the answer for as long as you can.
+    #[clap(long, arg_enum, value_name = "GAME", default_value = "wordle")]
+    pub game: GameKind,
+
+    /// Start from a bundle of settings: "casual" (everyday words, a short list of
+    /// suggestions), "competitive" (hard mode, with the smallest-worst-case guess pointed out),
+    /// or "cheat" (Wordle's own answer list, with the most informative guess pointed out).
+    /// Anything else given on the command line wins over the profile.
+    #[clap(long, arg_enum, value_name = "NAME")]
+    pub profile: Option<Profile>,
+
+    /// When the session ends, write a Markdown report to this file: the board, and for each
+    /// turn, the top suggestions and how your guess compared with them.
+    #[clap(long, value_name = "FILE")]
+    pub report: Option<PathBuf>,
+
+    /// Always work out the rankings from scratch, rather than using (or saving) the ones cached
+    /// in ~/.cache/wordleguesser from earlier runs.
+    #[clap(long)]
+    pub no_cache: bool,
+
+    /// Record everything about the session in this JSON file: the options, every line you
+    /// typed, and the candidates and suggestions each turn. Attach it to a bug report, and the
+    /// session can be played out again exactly.
+    #[clap(long, value_name = "FILE")]
+    pub debug_dump: Option<PathBuf>,
+
+    /// Pick up a game from the code someone shared with you, like W1-CRANEHB-SLATEAK, with
+    /// its guesses and scores already played. Type 'share' at the guess prompt to get the code
+    /// for your own game.
+    #[clap(long, value_name = "CODE")]
+    pub load: Option<String>,
+
+    /// How hard to think about the suggestions: "instant" ranks every guess by how many words
+    /// it leaves, "balanced" also looks two guesses ahead for the best few, and "thorough" works
+    /// out exactly how many guesses the best few take with perfect play (when there are few
+    /// enough possible answers for that, and not in hard mode). "auto" goes by how many possible
+    /// answers there are: thorough for up to 100, balanced for up to 500, and instant for more
+    /// than that.
+    #[clap(long, arg_enum, value_name = "LEVEL", default_value = "auto")]
+    pub quality: Quality,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ArgEnum)]
+pub enum GameKind {
+    Wordle,
+    Nerdle,
+    Peaks,
+    Antiwordle,
+}
+
+// How much work goes into the suggestions.
+#[derive(Clone, Copy, PartialEq, Eq, ArgEnum)]
+pub enum Quality {
+    Auto,
+    Instant,
+    Balanced,
+    Thorough,
+}
+
+// How many of the top guesses get looked at more closely, with balanced or thorough.
+const LOOKAHEAD_GUESSES: usize = 20;
+
+// With auto, the most possible answers for each level. Past these, they'd take more than a
+// second or so on a typical computer.
+const AUTO_THOROUGH_MAX: usize = 100;
+const AUTO_BALANCED_MAX: usize = 500;
+
+// The exact search takes seconds for a few hundred possible answers, but hours for a few
+// thousand, so past this even asking for thorough only gets balanced.
+const THOROUGH_MAX: usize = 400;
+
+impl Quality {
+    // The level to use for a game in this state. The exact search doesn't do hard mode, so
+    // thorough looks two guesses ahead there instead, the same as when there are too many
+    // possible answers for it.
+    fn for_game(self, solver: &Solver) -> Quality {
+        let candidates = solver.remaining_targets().len();
+        let quality = match self {
+            _ if candidates <= 2 => Quality::Instant,
+            Quality::Auto if candidates <= AUTO_THOROUGH_MAX => Quality::Thorough,
+            Quality::Auto if candidates <= AUTO_BALANCED_MAX => Quality::Balanced,
+            Quality::Auto => Quality::Instant,
+            quality => quality,
+        };
+        if quality == Quality::Thorough && (solver.is_hard() || candidates > THOROUGH_MAX) {
+            Quality::Balanced
+        } else {
+            quality
+        }
+    }
+}
+
+// Named bundles of settings, for people who'd rather not learn all the flags.
+#[derive(Clone, Copy, ArgEnum)]
+pub enum Profile {
+    // Playing for fun: only common words, both as answers and as suggestions, and not too many
+    // of them.
+    Casual,
+
+    // Playing for a streak: hard mode, and the guess that keeps the worst case smallest.
+    Competitive,
+
+    // Anything goes: the real answer list, and the guess that tells you the most.
+    Cheat,
+}
+
+impl SolveArgs {
+    // Fill in the settings from --profile. This comes before the config file, since the profile
+    // was asked for on the command line.
+    pub fn apply_profile(&mut self) {
+        let profile = match self.profile {
+            Some(profile) => profile,
+            None => return,
+        };
+        let lists_chosen = self.lists.common.is_some() || self.lists.solutions || self.lists.kids;
+        let (common, solutions, hard, suggestions, strategy, filter) = match profile {
+            Profile::Casual => (Some(3000), false, false, 8, None, Some("common(3000)")),
+            Profile::Competitive => (None, false, true, 15, Some("worstcase"), None),
+            Profile::Cheat => (None, true, false, 10, Some("entropy"), None),
+        };
+
+        if !lists_chosen {
+            self.lists.common = common;
+            self.lists.solutions = solutions;
+        }
+        if !self.lists.no_hard {
+            self.lists.hard |= hard;
+        }
+        self.suggestions.get_or_insert(suggestions);
+        if self.strategy.is_none() {
+            self.strategy = strategy.map(str::to_string);
+        }
+        if self.suggest_filter.is_none() {
+            self.suggest_filter = filter.map(str::to_string);
+        }
+    }
+
+    pub fn apply_config(&mut self, config: &config::Config) {
+        self.lists.apply_config(config);
+        if self.suggestions.is_none() {
+            self.suggestions = config.suggestions;
+        }
+        if self.suggest_filter.is_none() {
+            self.suggest_filter = config.suggest_filter.clone();
+        }
+        if !self.easy_typing && !self.no_easy_typing {
+            self.easy_typing = config.easy_typing.unwrap_or(false);
+        }
+        if self.strategy.is_none() {
+            self.strategy = config.strategy.clone();
+        }
+        if self.soft_hard.is_none() && !self.lists.is_hard() {
+            self.soft_hard = config.soft_hard;
+        }
+    }
+}
+
+// How the suggestions and lists of possible words get printed.
+pub struct DisplayOptions {
+    // How many of the top-ranked guesses to list.
+    pub num_shown: usize,
+
+    // Just the data: no banners, prompts, or explanations.
+    pub quiet: bool,
+
+    // Never print words that could be the answer. Suggested guesses are still shown, since
+    // that's the whole point, but not the lists of remaining possibilities.
+    pub hide_candidates: bool,
+
+    // Use ANSI colors.
+    pub color: bool,
+
+    // Show the Pareto frontier instead of the top of the ranked list.
+    pub pareto: bool,
+
+    // Show the best guess by each metric side by side.
+    pub compare_metrics: bool,
+
+    // Show what to play after each suggestion's worst-case score.
+    pub follow_up: bool,
+
+    // Break ties by how easy the guesses are to type.
+    pub easy_typing: bool,
+
+    // Give guesses that fit the scores so far a head start of this many percent.
+    pub soft_hard: Option<u32>,
+
+    // Sort the lists of possible answers alphabetically.
+    pub alphabetical: bool,
+
+    // Save rankings that take a while, and reuse them in later runs.
+    pub use_cache: bool,
+
+    // How closely to look at the top guesses.
+    pub quality: Quality,
+}
+
+// Print a presorted GuessQuality list in a way that's user-friendly.
+pub fn print_suggested_guess_list(
+    list: &[GuessQuality],
+    solver: &Solver,
+    display: &DisplayOptions,
+) -> Result<()> {
+    let targets = solver.remaining_targets();
+    let mut num_winning = 0;
+    let mut num_skipped = 0;
+    let mut num_printed = 0;
+
+    // Guesses that split up the possible answers exactly the same way as one that's already
+    // been printed. Those get listed along with it, rather than taking up places of their own.
+    let mut grouped = HashSet::new();
+
+    for (i, q) in list.iter().enumerate() {
+        if grouped.contains(&i) {
+            continue;
+        }
+
+        if num_printed < display.num_shown || q.has_winning {
+            num_printed += 1;
+            if num_skipped > 0 && !display.quiet {
+                outln!("   ... ({} words omitted) ...", num_skipped);
+                num_skipped = 0;
+            }
+
+            if plain_output() {
+                out!(
+                    "{}{}: average {:.1} left, at most {} left if it gets {}",
+                    q.guess,
+                    if q.has_winning { ", could win" } else { "" },
+                    q.expected_remaining,
+                    q.max_remaining,
+                    describe_score(q.guess, q.score_with_max_remaining)
+                );
+            } else {
+                out!(
+                    "{} {} | average {:.1} left, max {} left with {}",
+                    if q.has_winning { '*' } else { ' ' },
+                    q.guess,
+                    q.expected_remaining,
+                    q.max_remaining,
+                    format_score(q.score_with_max_remaining)
+                );
+            }
+
+            // The words that would be left after that worst-case score, unless those are
+            // spoilers. These get written out as they're found, rather than collected up
+            // and joined.
+            if !display.hide_candidates {
+                let max_targets_shown = 10;
+                let mut targets_with_max_score = targets
+                    .iter()
+                    .filter(|w| solver.score(q.guess, w) == q.score_with_max_remaining);
+                out!("{}", if plain_output() { ", leaving" } else { " =>" });
+                for w in targets_with_max_score.by_ref().take(max_targets_shown) {
+                    out!(" {}", w);
+                }
+                if targets_with_max_score.next().is_some() {
+                    out!("...");
+                }
+            }
+            outln!();
+
+            if display.follow_up && !display.quiet {
+                print_worst_case_follow_up(solver, q)?;
+            }
+
+            if !display.quiet {
+                let same = equivalent_guesses(list, i, solver);
+                if !same.is_empty() {
+                    let max_names_shown = 8;
+                    out!("      same split:");
+                    for &j in same.iter().take(max_names_shown) {
+                        out!(" {}", list[j].guess);
+                    }
+                    if same.len() > max_names_shown {
+                        out!(" (and {} more)", same.len() - max_names_shown);
+                    }
+                    outln!();
+                    grouped.extend(same);
+                }
+            }
+        } else {
+            num_skipped += 1;
+        }
+
+        if q.has_winning {
+            num_winning += 1;
+        }
+
+        if num_winning > 4 && i > 10 {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+// What to do if a guess gets its worst-case score: the best guess after that, and how many
+// words it could leave. A big worst case isn't so scary if the next guess splits it right up.
+fn print_worst_case_follow_up(solver: &Solver, q: &GuessQuality) -> Result<()> {
+    let score = show_score(q.guess, q.score_with_max_remaining);
+    let mut next = solver.clone();
+    if q.max_remaining == 1
+        || next
+            .apply_guess(q.guess, q.score_with_max_remaining)
+            .is_err()
+    {
+        return Ok(());
+    }
+    if let Some(follow_up) = next.best_guess() {
+        outln!(
+            "      after {}: {}, which leaves at most {} ({:.1} on average)",
+            score,
+            follow_up.guess,
+            follow_up.max_remaining,
+            follow_up.expected_remaining
+        );
+    }
+    Ok(())
+}
+
+// The guesses further down the list that are interchangeable with the one at `index`. They all
+// have the same numbers, and since the list is sorted, they come right after it -- so only the
+// run of guesses with the same numbers needs checking.
+fn equivalent_guesses(list: &[GuessQuality], index: usize, solver: &Solver) -> Vec<usize> {
+    // Late in the game there can be long runs of guesses that are all equally good, and the
+    // splits get compared for every one of them, so don't go on forever.
+    let max_checked = 1000;
+
+    let q = &list[index];
+    let same_numbers = |other: &GuessQuality| {
+        other.has_winning == q.has_winning
+            && other.max_remaining == q.max_remaining
+            && other.expected_remaining == q.expected_remaining
+    };
+    let (targets, policy) = (solver.remaining_targets(), solver.duplicate_policy());
+    let signature = partition_signature(q.guess, targets, policy);
+    list.iter()
+        .enumerate()
+        .skip(index + 1)
+        .take(max_checked)
+        .take_while(|(_, other)| same_numbers(other))
+        .filter(|(_, other)| partition_signature(other.guess, targets, policy) == signature)
+        .map(|(j, _)| j)
+        .collect()
+}
+
+// The core routine. Check the quality of various guesses against the full set
+// of targets, sort the qualities in a useful way, and print them out. The full ranking is
+// returned too, for looking up guesses that didn't make the list.
+fn generate_and_print_suggestions(
+    solver: &Solver,
+    strategy: &dyn Strategy,
+    rng: &mut dyn RngCore,
+    display: &DisplayOptions,
+) -> Result<Vec<GuessQuality<'static>>> {
+    let mut all_guesses_scored = if display.use_cache {
+        cache::rank_guesses(solver)
+    } else {
+        solver.rank_guesses()
+    };
+    if display.easy_typing {
+        all_guesses_scored.sort_by(compare_guess_quality_easy_typing);
+    }
+    if let Some(percent) = display.soft_hard {
+        // The sort is stable, so guesses that come out equal stay in the order they were in.
+        let bonus = 1.0 - percent.min(100) as f64 / 100.0;
+        let adjusted = |q: &GuessQuality| {
+            if solver.fits_scores_so_far(q.guess) {
+                quality_product(q) * bonus
+            } else {
+                quality_product(q)
+            }
+        };
+        all_guesses_scored.sort_by(|a, b| {
+            adjusted(a)
+                .partial_cmp(&adjusted(b))
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+    }
+    strategy.order_suggestions(solver, &mut all_guesses_scored, rng);
+
+    let lookahead = match display.quality.for_game(solver) {
+        Quality::Balanced => Some((
+            Quality::Balanced,
+            refine_two_ply(solver, &all_guesses_scored, LOOKAHEAD_GUESSES),
+        )),
+        Quality::Thorough => Some((
+            Quality::Thorough,
+            exact_values(solver, &all_guesses_scored, LOOKAHEAD_GUESSES),
+        )),
+        _ => None,
+    };
+    if let Some((quality, values)) = &lookahead {
+        // The guesses that were looked at go first, in their new order, and the rest of the
+        // ranking stays as it was after them.
+        let reordered = values.iter().map(|v| v.quality).collect::<Vec<_>>();
+        all_guesses_scored.retain(|q| !reordered.iter().any(|r| r.guess == q.guess));
+        all_guesses_scored.splice(0..0, reordered);
+        if !display.quiet && !display.pareto {
+            print_lookahead(*quality, values, display)?;
+        }
+    }
+
+    if display.pareto {
+        print_pareto_frontier(&all_guesses_scored, solver, display)?;
+    } else {
+        if !display.quiet {
+            let lookahead = lookahead
+                .as_ref()
+                .map(|(quality, values)| (*quality, values.len()));
+            outln!(
+                "\nSUGGESTED GUESSES (sorted by {})",
+                suggestion_order(strategy, lookahead)
+            );
+            print_rule(102)?;
+            if let Some(percent) = display.soft_hard {
+                outln!(
+                    "(Guesses that fit every score so far get a {}% head start.)",
+                    percent.min(100)
+                );
+            }
+        }
+
+        print_suggested_guess_list(&all_guesses_scored, solver, display)?;
+    }
+
+    if display.compare_metrics {
+        print_metric_comparison(
+            &all_guesses_scored,
+            solver.remaining_targets().len(),
+            display,
+        )?;
+    }
+
+    if !display.quiet {
+        print_strategy_profiles(&all_guesses_scored)?;
+    }
+
+    Ok(all_guesses_scored)
+}
+
+// What the suggestions are in order of, for the heading over them: the strategy's order, unless
+// the best few were put in a new one by looking further ahead (see Quality).
+fn suggestion_order(strategy: &dyn Strategy, lookahead: Option<(Quality, usize)>) -> String {
+    let order = if strategy.name() == RankerStrategy.name() {
+        "expected_remaining * max_remaining".to_string()
+    } else {
+        format!(
+            "the {} strategy, {}",
+            strategy.name(),
+            strategy.description()
+        )
+    };
+    match lookahead {
+        Some((Quality::Thorough, count)) => format!(
+            "guesses needed with perfect play for the best {}, then {}",
+            count, order
+        ),
+        Some((_, count)) => format!(
+            "words left after two guesses for the best {}, then {}",
+            count, order
+        ),
+        None => order,
+    }
+}
+
+// The top guesses as they came out of looking further ahead, next to what the ranking alone
+// says about them, so it's clear why the order changed.
+fn print_lookahead(
+    quality: Quality,
+    values: &[LookaheadValue],
+    display: &DisplayOptions,
+) -> Result<()> {
+    let shown = values.iter().take(display.num_shown.min(10));
+    if quality == Quality::Thorough {
+        outln!(
+            "\nWITH PERFECT PLAY (the best {} guesses, by exactly how many guesses they take on average)",
+            values.len()
+        );
+        print_rule(102)?;
+        for v in shown {
+            if plain_output() {
+                outln!(
+                    "{}: {:.3} guesses, leaving {:.1} after it",
+                    v.quality.guess,
+                    v.value,
+                    v.quality.expected_remaining
+                );
+            } else {
+                outln!(
+                    "  {}  {:6.3} guesses   (average {:.1} left after it)",
+                    v.quality.guess,
+                    v.value,
+                    v.quality.expected_remaining
+                );
+            }
+        }
+    } else {
+        outln!(
+            "\nLOOKING TWO GUESSES AHEAD (the best {} guesses, by how many words they leave after the best next guess)",
+            values.len()
+        );
+        print_rule(102)?;
+        for v in shown {
+            if plain_output() {
+                outln!(
+                    "{}: {:.2} left after two guesses, {:.1} after one",
+                    v.quality.guess,
+                    v.value,
+                    v.quality.expected_remaining
+                );
+            } else {
+                outln!(
+                    "  {}  average {:5.2} left after two   ({:.1} after one)",
+                    v.quality.guess,
+                    v.value,
+                    v.quality.expected_remaining
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+// One row per metric, with the guess that metric likes best and all of that guess's numbers, so
+// you can see at a glance whether they agree. When several metrics pick the same word, the rows
+// repeat it, which is the point.
+fn print_metric_comparison(
+    list: &[GuessQuality],
+    num_targets: usize,
+    display: &DisplayOptions,
+) -> Result<()> {
+    if !display.quiet {
+        outln!("\nBEST BY EACH METRIC");
+        print_rule(102)?;
+        outln!(
+            "{:10}  {:5}  {:>7}  {:>4}  {:>6}  {:>10}",
+            "metric",
+            "guess",
+            "average",
+            "max",
+            "bits",
+            "win next"
+        );
+    }
+    for metric in ALL_METRICS {
+        let q = match metric.best(list) {
+            Some(q) => q,
+            None => continue,
+        };
+        outln!(
+            "{:10}  {:5}  {:>7.1}  {:>4}  {:>6.2}  {:>9.1}%{}",
+            metric.name(),
+            q.guess,
+            q.expected_remaining,
+            q.max_remaining,
+            q.entropy,
+            100.0 * q.unique_answers as f64 / num_targets.max(1) as f64,
+            if q.has_winning { " *" } else { "" }
+        );
+    }
+    Ok(())
+}
+
+// Print every guess on the Pareto frontier, from the best average to the best worst case.
+fn print_pareto_frontier(
+    list: &[GuessQuality],
+    solver: &Solver,
+    display: &DisplayOptions,
+) -> Result<()> {
+    if !display.quiet {
+        outln!("\nBEST TRADE-OFFS (nothing else beats these on both the average and the max; best average first)");
+        print_rule(102)?;
+    }
+
+    // Each one goes through the usual printing on its own, so that none of them get cut off as
+    // being too far down the list.
+    for q in pareto_frontier(list) {
+        print_suggested_guess_list(&[q], solver, display)?;
+    }
+    Ok(())
+}
+
+// Show how one particular guess does against the remaining words, and where it ranks among all
+// of them, whether or not it made it into the list of suggestions. Returns false if it isn't a
+// valid guess at all (or isn't allowed any more, in hard mode).
+pub fn rate_guess(
+    word: &str,
+    ranked: &[GuessQuality],
+    solver: &Solver,
+    quiet: bool,
+    hide_candidates: bool,
+) -> Result<bool> {
+    let rank = match ranked.iter().position(|q| q.guess == word) {
+        Some(rank) => rank,
+        None => return Ok(false),
+    };
+
+    if !quiet {
+        outln!("#{} of {}:", rank + 1, ranked.len());
+    }
+
+    let display = DisplayOptions {
+        num_shown: 1,
+        quiet,
+        hide_candidates,
+        color: false,
+        pareto: false,
+        compare_metrics: false,
+        follow_up: false,
+        easy_typing: false,
+        soft_hard: None,
+        alphabetical: false,
+        use_cache: false,
+        quality: Quality::Instant,
+    };
+    print_suggested_guess_list(&ranked[rank..rank + 1], solver, &display)?;
+    Ok(true)
+}
+
+// List the possible answers that can be spelled with a bag of letters, like Scrabble tiles: each
+// letter can be used as many times as it's in the bag, and not all of them have to be used. With
+// `exact`, it's only the anagrams, which use every letter in the bag.
+fn print_bag(letters: &str, targets: &[&str], exact: bool, alphabetical: bool) -> Result<()> {
+    let mut bag = [0u8; 26];
+    for c in letters.bytes() {
+        if !c.is_ascii_lowercase() {
+            outln!("The bag can only have letters in it.");
+            return Ok(());
+        }
+        bag[(c - b'a') as usize] += 1;
+    }
+
+    // The bag only holds A to Z, so a word with any other letter in it (from a list in another
+    // language) can't be spelled with it, and is skipped before it's looked up.
+    let fits = |word: &&str| {
+        let mut left = bag;
+        word.bytes().all(|c| {
+            if !c.is_ascii_lowercase() {
+                return false;
+            }
+            let count = &mut left[(c - b'a') as usize];
+            *count = count.wrapping_sub(1);
+            *count != u8::MAX
+        }) && (!exact || left.iter().all(|&n| n == 0))
+    };
+    let found = targets.iter().copied().filter(fits).collect::<Vec<_>>();
+
+    if found.is_empty() {
+        outln!("None of the possible answers fit.");
+    }
+    for line in word_columns(&found, alphabetical) {
+        outln!("{}", line);
+    }
+    Ok(())
+}
+
+// Whether a pattern is somewhere in a word: its letters in a row, where '?', '.', or '_' is any
+// one letter and '*' is any number of them.
+fn contains_pattern(word: &[u8], pattern: &[u8]) -> bool {
+    fn matches_here(word: &[u8], pattern: &[u8]) -> bool {
+        match pattern.split_first() {
+            None => true,
+            Some((b'*', rest)) => (0..=word.len()).any(|skip| matches_here(&word[skip..], rest)),
+            Some((&p, rest)) => match word.split_first() {
+                Some((&w, word_rest)) if p == w || matches!(p, b'?' | b'.' | b'_') => {
+                    matches_here(word_rest, rest)
+                }
+                _ => false,
+            },
+        }
+    }
+    (0..=word.len()).any(|start| matches_here(&word[start..], pattern))
+}
+
+// Whether a word has the letters of a pattern in the same order, with anything in between.
+fn contains_in_order(word: &[u8], pattern: &[u8]) -> bool {
+    let mut letters = word.iter();
+    pattern
+        .iter()
+        .filter(|&&p| p.is_ascii_lowercase())
+        .all(|p| letters.any(|w| w == p))
+}
+
+// A quick way to ask "is it still possible that...": the possible answers with a bit of text
+// in them, most common first. If nothing has it exactly, the ones with its letters in that
+// order, spread out, are the next best thing, since that's usually a half-remembered word.
+fn print_find(pattern: &str, targets: &[&str], alphabetical: bool) -> Result<()> {
+    let pattern = pattern.as_bytes();
+    let mut found = targets
+        .iter()
+        .copied()
+        .filter(|w| contains_pattern(w.as_bytes(), pattern))
+        .collect::<Vec<_>>();
+    let spread_out = found.is_empty();
+    if spread_out {
+        found = targets
+            .iter()
+            .copied()
+            .filter(|w| contains_in_order(w.as_bytes(), pattern))
+            .collect();
+    }
+
+    let what = if spread_out {
+        format!(
+            "the letters of '{}', in that order,",
+            String::from_utf8_lossy(pattern)
+        )
+    } else {
+        format!("'{}'", String::from_utf8_lossy(pattern))
+    };
+    match found.len() {
+        0 => outln!(
+            "None of the possible answers have '{}' in them.",
+            String::from_utf8_lossy(pattern)
+        ),
+        1 => outln!("Only {} has {} in it.", found[0].to_uppercase(), what),
+        n => outln!(
+            "{} of the {} possible answers have {} in them:",
+            n,
+            targets.len(),
+            what
+        ),
+    }
+    if found.len() > 1 {
+        for line in word_columns(&found, alphabetical) {
+            outln!("{}", line);
+        }
+    }
+    Ok(())
+}
+
+// Not everybody plays the same way. Some people want to never lose, some want to win as fast
+// as possible, and some want a bit of both. This pulls the best few guesses for each of those
+// out of the ranked list, so you can find your kind of guess without reading the whole thing.
+fn print_strategy_profiles(list: &[GuessQuality]) -> Result<()> {
+    let num_per_profile = 3;
+
+    // The list is in whatever order the strategy put it in, so balanced gets sorted too.
+    let mut balanced = list.iter().collect::<Vec<_>>();
+    balanced.sort_by(|a, b| compare_guess_quality(a, b));
+    balanced.truncate(num_per_profile);
+
+    let mut safest = list.iter().collect::<Vec<_>>();
+    safest.sort_by_key(|q| q.max_remaining);
+    safest.truncate(num_per_profile);
+
+    // Every possibly-winning guess has the same chance of winning right now, so among those,
+    // prefer the ones that leave the least to do if they miss.
+    let mut fastest = list.iter().filter(|q| q.has_winning).collect::<Vec<_>>();
+    fastest.sort_by(|a, b| a.expected_remaining.total_cmp(&b.expected_remaining));
+    fastest.truncate(num_per_profile);
+
+    outln!("\nBY PLAY STYLE");
+    print_rule(102)?;
+    let profiles = [
+        ("safest", "smallest worst case", &safest),
+        ("fastest", "could win right now", &fastest),
+        ("balanced", "average * worst case", &balanced),
+    ];
+    for (name, description, guesses) in profiles {
+        if plain_output() {
+            out!("{} ({}):", name, description);
+        } else {
+            out!("{:8} {:22}:", name, format!("({})", description));
+        }
+        if guesses.is_empty() {
+            out!("  (none)");
+        }
+        for q in guesses.iter() {
+            out!(
+                "  {} (average {:.1}, max {})",
+                q.guess,
+                q.expected_remaining,
+                q.max_remaining
+            );
+        }
+        outln!();
+    }
+
+    Ok(())
+}
+
+// The answer is known, but the user asked not to have it spoiled. Keep it hidden unless they
+// ask for it.
+fn wait_for_reveal(answer: &str) -> Result<()> {
+    loop {
+        prompt(
+            false,
+            "I know the answer! Type 'reveal' to see it, or just close me if you'd rather work it out: ",
+        )?;
+        match read_input_line()? {
+            Some(input) if input.eq_ignore_ascii_case("reveal") => {
+                outln!("The word is: {}", answer);
+                return Ok(());
+            }
+            Some(_) => continue,
+            None => return Ok(()),
+        }
+    }
+}
+
+// One puzzle being solved. A session can have several of these going at once, say for Wordle
+// and a couple of its clones, each with its own word lists.
+struct Game {
+    solver: Solver,
+
+    // How many possibilities there were at the start of each turn, to show how the game is
+    // going.
+    trajectory: Vec<usize>,
+
+    // Never print possible answers for this game. (See DisplayOptions.)
+    hide_candidates: bool,
+
+    // How the game ended, once it has.
+    outcome: Option<Outcome>,
+
+    // Take the accents off of the guesses typed in, to match the word lists.
+    fold_accents: bool,
+
+    // Every turn so far, for --report.
+    turns: Vec<report::TurnRecord>,
+}
+
+impl Game {
+    fn new(lists: &ListArgs, args: &SolveArgs) -> Result<Game> {
+        let mut solver = lists.solver()?;
+        if let Some(text) = &args.suggest_filter {
+            let filter = WordFilter::parse(text)?;
+            solver.filter_guesses(|w| filter.matches(w));
+            if solver.valid_guesses().is_empty() {
+                return Err(Error::InvalidFilter(format!(
+                    "none of the guesses pass '{}'",
+                    text
+                )));
+            }
+        }
+
+        // With --no-spoilers we can show possible answers drawn from the general word list, since
+        // those are only guesses, but never anything from Wordle's own solution list.
+        Ok(Game {
+            solver,
+            trajectory: Vec::new(),
+            hide_candidates: args.no_spoilers && (lists.solutions || lists.kids),
+            outcome: None,
+            fold_accents: lists.fold_accents,
+            turns: Vec::new(),
+        })
+    }
+
+    // Play the turns in a game code, one after another, the same as if they'd been typed in.
+    fn replay(&mut self, code: &str) -> Result<()> {
+        for (guess, score) in decode_state(code)? {
+            let candidates = self.solver.remaining_targets().len();
+            self.solver.apply_guess(&guess, score)?;
+            self.trajectory.push(candidates);
+            self.turns
+                .push(report::TurnRecord::new(candidates, &[], &guess, score));
+        }
+        Ok(())
+    }
+}
+
+// The "game" command at the guess prompt: "game NAME" switches to another game, starting it if
+// it's new, and plain "game" lists them. A new game can have its own word lists, e.g.
+// "game kids -k", and otherwise gets the same ones as the first game.
+#[derive(Parser)]
+#[clap(name = "game", no_binary_name = true)]
+struct GameCommand {
+    name: Option<String>,
+
+    #[clap(flatten)]
+    lists: ListArgs,
+}
+
+// For the likeliest scores a guess could get, what to play next: a small table to plan the next
+// move with while the game is still revealing the colors. The chances go by how common the
+// words are, the same as 'best now'.
+fn print_score_preview(
+    solver: &Solver,
+    guess: &str,
+    num_shown: usize,
+    hide_candidates: bool,
+) -> Result<()> {
+    let mut chances: BTreeMap<WordScore, (f64, Vec<&str>)> = BTreeMap::new();
+    for (word, chance) in solver.answer_chances() {
+        let entry = chances.entry(solver.score(guess, word)).or_default();
+        entry.0 += chance;
+        entry.1.push(word);
+    }
+    let mut by_chance = chances.into_iter().collect::<Vec<_>>();
+    by_chance.sort_by(|a, b| b.1 .0.total_cmp(&a.1 .0).then(a.0.cmp(&b.0)));
+
+    if plain_output() {
+        outln!("\nWhat to play next, for the likeliest scores:");
+    } else {
+        outln!("\nIf you get...    chance   left   then play");
+    }
+    for (score, (chance, words)) in by_chance.iter().take(num_shown) {
+        let next = if *score == ALL_GREEN {
+            "nothing -- you've won!".to_string()
+        } else if words.len() == 1 {
+            if hide_candidates {
+                "the answer".to_string()
+            } else {
+                format!("{}, the answer", words[0])
+            }
+        } else {
+            let mut next = solver.clone();
+            match next
+                .apply_guess(guess, *score)
+                .ok()
+                .and_then(|()| next.best_guess())
+            {
+                Some(q) => format!(
+                    "{}{}, which leaves at most {}",
+                    q.guess,
+                    match (q.has_winning, plain_output()) {
+                        (false, _) => "",
+                        (true, false) => "*",
+                        (true, true) => " (could win)",
+                    },
+                    q.max_remaining
+                ),
+                None => continue,
+            }
+        };
+        if plain_output() {
+            outln!(
+                "If you get {}: a {:.1}% chance, {} left, then play {}.",
+                describe_score(guess, *score),
+                chance * 100.0,
+                words.len(),
+                next
+            );
+        } else {
+            outln!(
+                "  {}          {:5.1}%  {:5}   {}",
+                format_score(*score),
+                chance * 100.0,
+                words.len(),
+                next
+            );
+        }
+    }
+    if by_chance.len() > num_shown {
+        outln!(
+            "  ...and {} less likely scores",
+            by_chance.len() - num_shown
+        );
+    }
+    Ok(())
+}
+
+// Coach mode: before a guess is played, put it side by side with the top suggestion, on the
+// things that make a guess good, and let the player decide whether to go ahead with it. The
+// point is to learn what makes a guess good, so the decision stays with the player. Returns
+// whether to play it.
+fn coach_approves(guess: &str, ranked: &[GuessQuality], solver: &Solver) -> Result<bool> {
+    let best = match ranked.first() {
+        Some(best) => best,
+        None => return Ok(true),
+    };
+    if best.guess == guess {
+        outln!("That's the top suggestion. Good choice!");
+        return Ok(true);
+    }
+    let (rank, quality) = match ranked.iter().enumerate().find(|(_, q)| q.guess == guess) {
+        Some(found) => found,
+        None => {
+            outln!("'{}' isn't one of the guesses being suggested, so there's nothing to compare it with.", guess);
+            return Ok(true);
+        }
+    };
+
+    let remaining = solver.remaining_targets().len();
+    let chances = solver.answer_chances();
+    let win_chance = |q: &GuessQuality| {
+        chances
+            .iter()
+            .find(|(word, _)| *word == q.guess)
+            .map_or(0.0, |(_, chance)| chance * 100.0)
+    };
+    let sure_chance = |q: &GuessQuality| q.unique_answers as f64 * 100.0 / remaining.max(1) as f64;
+
+    outln!(
+        "\n{} is #{} of {} guesses. Here's how it compares with the top suggestion:\n",
+        guess.to_uppercase(),
+        rank + 1,
+        ranked.len()
+    );
+    if !plain_output() {
+        outln!("                 average left  worst case  right now  sure of it after");
+    }
+    for (label, q) in [("yours:", quality), ("suggested:", best)] {
+        if plain_output() {
+            outln!(
+                "{} {}: {:.1} left on average, {} at worst, a {:.1}% chance of winning right now, and a {:.1}% chance of being sure of the answer after.",
+                label.trim_end_matches(':'),
+                q.guess.to_uppercase(),
+                q.expected_remaining,
+                q.max_remaining,
+                win_chance(q),
+                sure_chance(q)
+            );
+            continue;
+        }
+        outln!(
+            "{:10} {}  {:12.1}  {:10}  {:8.1}%  {:15.1}%",
+            label,
+            q.guess.to_uppercase(),
+            q.expected_remaining,
+            q.max_remaining,
+            win_chance(q),
+            sure_chance(q)
+        );
+    }
+
+    // Point out the biggest difference, since that's the lesson.
+    let note = if quality.max_remaining > best.max_remaining * 2 {
+        format!(
+            "The big difference is the worst case: if it goes badly, {} could leave {} words, against {} for {}.",
+            guess.to_uppercase(),
+            quality.max_remaining,
+            best.max_remaining,
+            best.guess.to_uppercase()
+        )
+    } else if quality.expected_remaining > best.expected_remaining * 1.5 {
+        format!(
+            "On average, {} would leave {:.1} times as many words as {}.",
+            guess.to_uppercase(),
+            quality.expected_remaining / best.expected_remaining.max(f64::MIN_POSITIVE),
+            best.guess.to_uppercase()
+        )
+    } else if win_chance(quality) > win_chance(best) {
+        "It's a bit worse at narrowing things down, but it has a better chance of being the answer, which can be worth it late in the game.".to_string()
+    } else {
+        "They're pretty close.".to_string()
+    };
+    outln!("\n{}", note);
+
+    loop {
+        prompt(
+            false,
+            &format!("Play {} anyway? [y/n] ", guess.to_uppercase()),
+        )?;
+        match read_input_line()?.map(|s| s.to_lowercase()).as_deref() {
+            Some("y" | "yes" | "") => return Ok(true),
+            Some("n" | "no") | None => return Ok(false),
+            Some(_) => outln!("Type y to play it, or n to pick another guess."),
+        }
+    }
+}
+
+// When the top suggestion can't be the answer, it looks like a strange thing to play, next to
+// the possible answers right there in the list. So compare it with the likeliest of those, with
+// real numbers, and let the player decide whether the trade is worth it.
+fn explain_probe(ranked: &[GuessQuality], solver: &Solver) -> Result<()> {
+    let top = match ranked.first() {
+        Some(top) if !top.has_winning => top,
+        _ => return Ok(()),
+    };
+    let obvious = match solver.answer_chances().first() {
+        Some(&(word, chance)) => (word, chance),
+        None => return Ok(()),
+    };
+    let obvious_quality = match ranked.iter().find(|q| q.guess == obvious.0) {
+        Some(q) => q,
+        None => return Ok(()),
+    };
+
+    let targets = solver.remaining_targets();
+    outln!(
+        "\nWhy {} and not {}, the likeliest answer? {} can't be the answer, but it splits the {} possible answers into {} groups, the biggest of them {}, leaving {:.1} on average.",
+        top.guess.to_uppercase(),
+        obvious.0.to_uppercase(),
+        top.guess.to_uppercase(),
+        targets.len(),
+        top.partitions,
+        top.max_remaining,
+        top.expected_remaining
+    );
+    outln!(
+        "{} splits them into {} groups, the biggest {}, leaving {:.1} on average, but it has a {:.0}% chance of winning right away.",
+        obvious.0.to_uppercase(),
+        obvious_quality.partitions,
+        obvious_quality.max_remaining,
+        obvious_quality.expected_remaining,
+        obvious.1 * 100.0
+    );
+    Ok(())
+}
+
+// How many of the runners-up 'best now' shows.
+const BEST_NOW_RUNNERS_UP: usize = 4;
+
+// The words most likely to be the answer, going by how common they are, and which of them the
+// strategy would go for if the guess had to be one of them.
+fn print_best_now(solver: &Solver, strategy: &dyn Strategy, rng: &mut dyn RngCore) -> Result<()> {
+    let chances = solver.answer_chances();
+    let (best, chance) = match chances.first() {
+        Some(&first) => first,
+        None => return Ok(()),
+    };
+    outln!(
+        "\nIf you had to guess the answer right now: {} ({:.0}% chance, going by how common the words are; 1 in {} if they're all equally likely).",
+        best,
+        chance * 100.0,
+        chances.len()
+    );
+    let runners_up = &chances[1..chances.len().min(BEST_NOW_RUNNERS_UP + 1)];
+    if !runners_up.is_empty() {
+        outln!(
+            "After that: {}",
+            runners_up
+                .iter()
+                .map(|(word, chance)| format!("{} ({:.0}%)", word, chance * 100.0))
+                .join(", ")
+        );
+    }
+
+    let mut answers_only = solver.clone();
+    let targets = solver.remaining_targets();
+    answers_only.filter_guesses(|word| targets.contains(&word));
+    outln!(
+        "Going for the win, the {} strategy would guess: {}",
+        strategy.name(),
+        strategy.choose_guess(&answers_only, rng)
+    );
+    Ok(())
+}
+
+// Play the rest of the game out against every possible answer, the way a strategy would, and
+// show how many more guesses it took. That's a fair answer to "how much longer is this going to
+// take?", assuming you play about as well as the strategy does.
+fn print_finish(solver: &Solver, strategy: &dyn Strategy) -> Result<()> {
+    let played = solver.history().len();
+    let summary = summarize(&simulate(solver, strategy, Some(0)));
+    outln!(
+        "\nPlaying on with the {} strategy, against each of the {} possible answers:",
+        strategy.name(),
+        summary.games
+    );
+    outln!(
+        "  {:.2} more guesses on average, and {} at most.",
+        summary.average_guesses,
+        summary.worst_guesses
+    );
+    for (more, &count) in summary.distribution.iter().enumerate().skip(1) {
+        if count > 0 {
+            outln!(
+                "  {:2} more (solved in {:2}): {:5} answers ({:.0}%)",
+                more,
+                played + more,
+                count,
+                count as f64 * 100.0 / summary.games as f64
+            );
+        }
+    }
+    Ok(())
+}
+
+// With this many possible answers or fewer, each one gets the turn it would be found by.
+const MAX_DEPTHS_SHOWN: usize = 20;
+
+// Play the rest of the game against each of the possible answers, following the top suggestion
+// every turn, and group the answers by the turn they'd be found on. The ones at the bottom are
+// the dangerous ones: if it's one of those, following the suggestions will take the longest.
+fn print_depths(solver: &Solver) -> Result<()> {
+    let played = solver.history().len();
+    let mut by_turn: BTreeMap<usize, Vec<&str>> = BTreeMap::new();
+    for game in simulate(solver, &RankerStrategy, Some(0)) {
+        let turn = if game.solved() {
+            played + game.guesses.len()
+        } else {
+            usize::MAX
+        };
+        by_turn.entry(turn).or_default().push(game.answer);
+    }
+
+    outln!("\nFollowing the top suggestion each turn:");
+    for (turn, mut answers) in by_turn {
+        answers.sort_unstable();
+        if turn == usize::MAX {
+            outln!("  never found:      {}", answers.join(" "));
+        } else {
+            outln!(
+                "  found by turn {:2}: {}{}",
+                turn,
+                answers.join(" "),
+                if turn > WORDLE_MAX_GUESSES {
+                    "  (too late!)"
+                } else {
+                    ""
+                }
+            );
+        }
+    }
+    Ok(())
+}
+
+// How many of the possible answers get listed each turn. The 'peek' command shows more.
+const MAX_CANDIDATES_SHOWN: usize = 200;
+
+// How many lines of words 'peek' shows before waiting for Enter.
+const PEEK_PAGE_LINES: usize = 20;
+
+// Show up to `count` of the possible answers, a page at a time, for when the usual list gets
+// cut off.
+fn peek(targets: &[&str], count: usize, alphabetical: bool) -> Result<()> {
+    let shown = &targets[..count.min(targets.len())];
+    let lines = word_columns(shown, alphabetical);
+    let num_pages = lines.len().div_ceil(PEEK_PAGE_LINES);
+    for (page, chunk) in lines.chunks(PEEK_PAGE_LINES).enumerate() {
+        for line in chunk {
+            outln!("{}", line);
+        }
+        if page + 1 < num_pages {
+            prompt(false, "-- more (Enter to keep going, 'q' to stop) --")?;
+            match read_input_line()? {
+                Some(s) if s.is_empty() => {}
+                _ => return Ok(()),
+            }
+        }
+    }
+    outln!(
+        "({} of {} possible answers)",
+        count.min(targets.len()),
+        targets.len()
+    );
+    Ok(())
+}
+
+// The name of the game a session starts with.
+const FIRST_GAME: &str = "main";
+
+// Help the user solve a puzzle: suggest guesses, read back the guess they used and the score it
+// got, narrow down the possibilities, and repeat.
+pub fn run(args: &SolveArgs, quiet: bool, color: bool) -> Result<Outcome> {
+    if args.game == GameKind::Nerdle {
+        return crate::cli::nerdle::run(args.suggestions.unwrap_or(15), quiet);
+    }
+    if args.game == GameKind::Peaks {
+        return crate::cli::peaks::run(&args.lists, args.suggestions.unwrap_or(15), quiet);
+    }
+    if args.game == GameKind::Antiwordle {
+        return crate::cli::antiwordle::run(&args.lists, args.suggestions.unwrap_or(15), quiet);
+    }
+
+    let strategy = strategy_by_name(args.strategy.as_deref().unwrap_or("ranker"))?;
+    match &args.debug_dump {
+        None => play_wordle(args, strategy.as_ref(), quiet, color, None),
+        Some(path) => {
+            let mut dump = DebugDump::start(args);
+            let result = play_wordle(args, strategy.as_ref(), quiet, color, Some(&mut dump));
+            dump.write(path, &result)?;
+            result
+        }
+    }
+}
+
+// The Wordle session itself, with the strategy picking the suggestions, recording it in the
+// debug dump as it goes if there is one.
+fn play_wordle(
+    args: &SolveArgs,
+    strategy: &dyn Strategy,
+    quiet: bool,
+    color: bool,
+    mut dump: Option<&mut DebugDump>,
+) -> Result<Outcome> {
+    let mut display = DisplayOptions {
+        num_shown: args.suggestions.unwrap_or(15),
+        quiet,
+        hide_candidates: false,
+        color,
+        pareto: args.pareto,
+        compare_metrics: args.compare_metrics,
+        follow_up: args.follow_up,
+        easy_typing: args.easy_typing,
+        soft_hard: args.soft_hard,
+        alphabetical: args.alphabetical,
+        use_cache: !args.no_cache,
+        quality: args.quality,
+    };
+
+    let mut rng = seeded_rng(None);
+
+    // The game being played, and any others that have been put aside.
+    let mut game_name = FIRST_GAME.to_string();
+    let mut game = Game::new(&args.lists, args)?;
+    if let Some(code) = &args.load {
+        game.replay(code)?;
+    }
+    let mut other_games: BTreeMap<String, Game> = BTreeMap::new();
+
+    // Whether the session was cut short, by running out of input or by 'quit', rather than
+    // ending with the answer found.
+    let mut stopped = None;
+
+    // Guess words until we've sufficiently narrowed the space!
+    let outcome = 'session: loop {
+        display.hide_candidates = game.hide_candidates;
+        let solver = &mut game.solver;
+
+        // Give some info on the current state of the possibility space.
+        let remaining_targets = solver.remaining_targets();
+        game.trajectory.push(remaining_targets.len());
+        if !quiet && game.trajectory.len() > 1 {
+            outln!(
+                "\nProgress: {}",
+                game.trajectory
+                    .iter()
+                    .map(|n| n.to_string())
+                    .join(if plain_output() {
+                        ", then "
+                    } else {
+                        " \u{2192} "
+                    })
+            );
+            outln!(
+                "\n{}\n",
+                keyboard::render_keyboard(solver.knowledge(), display.color)
+            );
+        }
+        let finished = match remaining_targets.len() {
+            0 => {
+                if !quiet {
+                    outln!("Somehow, there are no possible words remaining. Did you enter your guesses and scores correctly?");
+                }
+                Some(Outcome::Contradiction)
+            }
+            1 if args.no_spoilers => {
+                if !quiet {
+                    wait_for_reveal(remaining_targets[0])?;
+                }
+                Some(Outcome::Solved)
+            }
+            1 => {
+                if quiet {
+                    outln!("{}", remaining_targets[0]);
+                } else {
+                    outln!("The word is: {}", remaining_targets[0]);
+                }
+                Some(Outcome::Solved)
+            }
+            2 if quiet && !display.hide_candidates => {
+                outln!("{} {}", remaining_targets[0], remaining_targets[1]);
+                Some(Outcome::Solved)
+            }
+            _ if quiet => None,
+            _ if display.hide_candidates => {
+                // Without seeing the list, two possibilities isn't obviously the end of the game,
+                // so we keep going and let the suggestions point at one of them.
+                outln!(
+                    "There are {} possibilities for the word.",
+                    remaining_targets.len()
+                );
+                None
+            }
+            _ => {
+                outln!(
+                    "There are {} possibilities for the word.\n",
+                    remaining_targets.len()
+                );
+                let shown = &remaining_targets[..remaining_targets.len().min(MAX_CANDIDATES_SHOWN)];
+                for line in word_columns(shown, display.alphabetical) {
+                    outln!("{}", line);
+                }
+                if remaining_targets.len() > MAX_CANDIDATES_SHOWN {
+                    outln!("... (type 'peek all' to see the rest)");
+                }
+                if (3..=MAX_DEPTHS_SHOWN).contains(&remaining_targets.len()) {
+                    print_depths(solver)?;
+                }
+
+                // If there are only two possible solutions left then you know what to do from here.
+                // Guess one of them, and if it's not that it's the other.
+                (remaining_targets.len() == 2).then_some(Outcome::Solved)
+            }
+        };
+
+        // With just the one game, that's the end. Otherwise there may be other games to get
+        // back to.
+        let mut ranked = Vec::new();
+        if let Some(outcome) = finished {
+            game.outcome = Some(outcome);
+            if other_games.is_empty() {
+                break outcome;
+            }
+            if !quiet {
+                outln!(
+                    "\nThat's the end of the '{}' game. Use 'game NAME' to switch to another one: {}",
+                    game_name,
+                    other_games.keys().join(", ")
+                );
+            }
+        } else {
+            // Analyze the list of remaining words and print out some suggested guesses that will
+            // do the most to cull the possibility space, and print them out.
+            ranked = generate_and_print_suggestions(solver, strategy, &mut rng, &display)?;
+            if !quiet && !display.hide_candidates && !solver.history().is_empty() {
+                explain_probe(&ranked, solver)?;
+            }
+        }
+
+        // Get the word that the user is going to enter and solve the puzzle.
+        let guess = loop {
+            prompt(
+                quiet,
+                if game.outcome.is_some() {
+                    "\nEnter 'game NAME' to switch games: "
+                } else {
+                    "\nPlease enter the guess you'll use (or just press Enter for the top suggestion, 'rate WORD' to check a word, 'game NAME' to switch games): "
+                },
+            )?;
+            let input_str = match read_input_line()? {
+                Some(s) if game.fold_accents => fold_accents(&s.to_lowercase()),
+                Some(s) => s.to_lowercase(),
+                None => {
+                    stopped = Some(game.outcome.unwrap_or(Outcome::Unsolved));
+                    break None;
+                }
+            };
+            if is_quit(&input_str) {
+                stopped = Some(game.outcome.unwrap_or(Outcome::Unsolved));
+                break None;
+            }
+
+            if input_str == "game" || input_str.starts_with("game ") {
+                let command =
+                    match GameCommand::try_parse_from(input_str.split_whitespace().skip(1)) {
+                        Ok(command) => command,
+                        Err(e) => {
+                            outln!("{}", e);
+                            continue;
+                        }
+                    };
+
+                let name = match command.name {
+                    Some(name) => name,
+                    None => {
+                        if !quiet {
+                            list_games(&game_name, &game, &other_games)?;
+                        }
+                        continue;
+                    }
+                };
+                if name == game_name {
+                    continue;
+                }
+
+                // A brand new game gets the word lists from the command, if it gave any.
+                // Otherwise it's the same lists as the first game.
+                let next = match other_games.remove(&name) {
+                    Some(next) => next,
+                    None if input_str.split_whitespace().count() > 2 => {
+                        Game::new(&command.lists, args)?
+                    }
+                    None => Game::new(&args.lists, args)?,
+                };
+                if !quiet {
+                    outln!("\nSwitching to the '{}' game.", name);
+                }
+
+                let previous = std::mem::replace(&mut game, next);
+                other_games.insert(std::mem::replace(&mut game_name, name), previous);
+
+                // The new game's state gets shown again from the top, without counting that as
+                // another turn.
+                game.trajectory.pop();
+                continue 'session;
+            }
+
+            // The game so far as a code, for someone else to 'load' and carry on from.
+            if input_str == "share" {
+                match encode_state(game.solver.history()) {
+                    Ok(code) if quiet => outln!("{}", code),
+                    Ok(code) => {
+                        outln!("\nThe code for this game: {}", code);
+                        outln!("Anyone can pick it up from here by typing 'load {}' at this prompt, or with --load.", code);
+                    }
+                    Err(e) => outln!("{}", e),
+                }
+                continue;
+            }
+
+            // Start the game over from someone else's code. The game isn't touched unless the
+            // whole code makes sense.
+            if let Some(code) = input_str.strip_prefix("load ") {
+                let mut loaded = Game::new(&args.lists, args)?;
+                match loaded.replay(code) {
+                    Ok(()) => {
+                        if !quiet {
+                            outln!(
+                                "\nLoaded {} guess{}.",
+                                loaded.turns.len(),
+                                if loaded.turns.len() == 1 { "" } else { "es" }
+                            );
+                        }
+                        game = loaded;
+                        continue 'session;
+                    }
+                    Err(e) => {
+                        outln!("{}", e);
+                        continue;
+                    }
+                }
+            }
+
+            if game.outcome.is_some() {
+                outln!("That game is over.");
+                continue;
+            }
+
+            // Nothing at all, or a '?', means going with the top suggestion, which saves typing
+            // it out when you were going to anyway.
+            if input_str.is_empty() || input_str == "?" {
+                match ranked.first() {
+                    Some(q) => {
+                        if !quiet {
+                            outln!("Going with the top suggestion, {}.", q.guess);
+                        }
+                        break Some(q.guess.to_string());
+                    }
+                    None => {
+                        outln!("There aren't any suggestions to go with.");
+                        continue;
+                    }
+                }
+            }
+
+            if input_str == "peek" || input_str.starts_with("peek ") {
+                let count = match input_str["peek".len()..].trim() {
+                    "" | "all" => Some(usize::MAX),
+                    n => n.parse::<usize>().ok(),
+                };
+                match count {
+                    _ if display.hide_candidates => {
+                        outln!("That would show possible answers, which is a spoiler.")
+                    }
+                    Some(count) => {
+                        peek(game.solver.remaining_targets(), count, display.alphabetical)?
+                    }
+                    None => outln!("Use 'peek N' to see N of the possible answers, or 'peek all'."),
+                }
+                continue;
+            }
+
+            if input_str == "stats" {
+                if display.hide_candidates {
+                    outln!("That would show possible answers, which is a spoiler.");
+                } else {
+                    outln!(
+                        "\n{}",
+                        stats::render_candidate_stats(game.solver.remaining_targets())
+                    );
+                }
+                continue;
+            }
+
+            // Switch the list of possible answers between most common first and alphabetical,
+            // and show it again the new way.
+            if input_str == "sort" {
+                display.alphabetical = !display.alphabetical;
+                outln!(
+                    "Listing the possible answers {}.",
+                    if display.alphabetical {
+                        "alphabetically"
+                    } else {
+                        "most common first"
+                    }
+                );
+                if !display.hide_candidates {
+                    outln!();
+                    peek(
+                        game.solver.remaining_targets(),
+                        MAX_CANDIDATES_SHOWN,
+                        display.alphabetical,
+                    )?;
+                }
+                continue;
+            }
+
+            // Not the guess that tells you the most, but the one most likely to be right, for
+            // when it's the last turn or you just feel lucky.
+            if input_str == "best now" {
+                if display.hide_candidates {
+                    outln!("That would show possible answers, which is a spoiler.");
+                } else {
+                    print_best_now(&game.solver, strategy, &mut rng)?;
+                }
+                continue;
+            }
+
+            if input_str == "finish" {
+                print_finish(&game.solver, strategy)?;
+                continue;
+            }
+
+            if input_str == "history" {
+                print_history(&game, display.color)?;
+                continue;
+            }
+
+            if input_str == "grid" {
+                outln!(
+                    "\n{}",
+                    grid::render_constraint_grid(game.solver.knowledge())
+                );
+                continue;
+            }
+
+            let bag = input_str
+                .strip_prefix("bag ")
+                .map(|letters| (letters, false));
+            let anagram = input_str
+                .strip_prefix("anagram ")
+                .map(|letters| (letters, true));
+            if let Some((letters, exact)) = bag.or(anagram) {
+                if display.hide_candidates {
+                    outln!("That would show possible answers, which is a spoiler.");
+                } else {
+                    print_bag(
+                        letters.trim(),
+                        game.solver.remaining_targets(),
+                        exact,
+                        display.alphabetical,
+                    )?;
+                }
+                continue;
+            }
+
+            if let Some(pattern) = input_str.strip_prefix("find ") {
+                if display.hide_candidates {
+                    outln!("That would show possible answers, which is a spoiler.");
+                } else {
+                    print_find(
+                        pattern.trim(),
+                        game.solver.remaining_targets(),
+                        display.alphabetical,
+                    )?;
+                }
+                continue;
+            }
+
+            if let Some(word) = input_str.strip_prefix("rate ") {
+                let word = word.trim();
+                cache::complete_ranking(&mut ranked, &game.solver, word);
+                if !rate_guess(word, &ranked, &game.solver, quiet, display.hide_candidates)? {
+                    if game
+                        .solver
+                        .history()
+                        .iter()
+                        .any(|(played, _)| played == word)
+                    {
+                        outln!("'{}' has already been played.", word);
+                        continue;
+                    }
+                    if let Some(reason) = game.solver.hard_mode_violation(word) {
+                        outln!("The game won't take '{}' in hard mode: {}.", word, reason);
+                        continue;
+                    }
+                    outln!(
+                        "'{}' isn't a valid guess{}.",
+                        word,
+                        if game.solver.hard_mode_applies() {
+                            " in hard mode with the scores so far"
+                        } else {
+                            ""
+                        }
+                    );
+                }
+                continue;
+            }
+
+            // The real game won't take a guess that breaks hard mode's rules, so there's no
+            // score coming for it, and it's better to say why now than to wait for one.
+            if let Some(reason) = game.solver.hard_mode_violation(&input_str) {
+                if quiet {
+                    eprintln!(
+                        "Guess '{}' isn't allowed in hard mode: {}.",
+                        input_str, reason
+                    );
+                    return Ok(Outcome::InvalidInput);
+                }
+                outln!(
+                    "\nThe game won't take '{}' in hard mode: {}.",
+                    input_str,
+                    reason
+                );
+                continue;
+            }
+
+            if is_valid_word(&input_str) {
+                cache::complete_ranking(&mut ranked, &game.solver, &input_str);
+                if args.coach && !quiet && !coach_approves(&input_str, &ranked, &game.solver)? {
+                    continue;
+                }
+                break Some(input_str);
+            }
+
+            if quiet {
+                eprintln!("Guess '{}' is not exactly five letters.", input_str);
+                return Ok(Outcome::InvalidInput);
+            }
+
+            outln!(
+                "\nYour guess of '{}' was not exactly five letters.",
+                input_str
+            );
+        };
+        let guess = match guess {
+            Some(guess) => guess,
+            None => break stopped.unwrap_or(Outcome::Unsolved),
+        };
+
+        if let (Some(num_shown), false) = (args.preview, quiet) {
+            print_score_preview(&game.solver, &guess, num_shown, display.hide_candidates)?;
+        }
+
+        // Get the score that the puzzle gave to the user.
+        let score = loop {
+            prompt(
+                quiet,
+                "Enter the score you got for that word, in \".y.GG\" format: ",
+            )?;
+            let input_str = match read_input_line()? {
+                Some(s) if is_quit(&s) => {
+                    stopped = Some(Outcome::Unsolved);
+                    break None;
+                }
+                Some(s) => s,
+                None => {
+                    stopped = Some(Outcome::Unsolved);
+                    break None;
+                }
+            };
+
+            let duplicates = game.solver.duplicate_policy();
+            let e = match parse_score(&input_str) {
+                // A score that no word could get is caught here, so that only the score has to
+                // be typed again. In quiet mode, applying it reports the same thing.
+                Ok(s) if !quiet => match score_impossibility(&guess, s, duplicates) {
+                    Some(reason) => {
+                        outln!(
+                            "\nSorry, no word could score {} for '{}': {}.",
+                            show_score(&guess, s),
+                            guess,
+                            reason
+                        );
+                        continue;
+                    }
+                    None => break Some(s),
+                },
+                Ok(s) => break Some(s),
+                Err(e) => e,
+            };
+
+            if quiet {
+                eprintln!("{}", e);
+                return Ok(Outcome::InvalidInput);
+            }
+
+            outln!();
+            outln!("Sorry, {}.", e);
+            outln!(
+                "Scores should be entered as {} characters, with this code:",
+                WORD_LENGTH
+            );
+            outln!("  . = letter that did not matching anything (or -, _, x, or 0)");
+            outln!("  y = (yellow) letter that's in the word but in the wrong place (or 1)");
+            outln!("  G = (GREEN) the right letter in the right place (or 2)");
+            outln!();
+        };
+        let score = match score {
+            Some(score) => score,
+            None => break stopped.unwrap_or(Outcome::Unsolved),
+        };
+
+        // Cull the solution space to things that would give the above score for the above guess.
+        // A score that doesn't fit anything is a typo, so it goes back to asking for the guess.
+        let before = game.solver.remaining_targets().to_vec();
+        let candidates = before.len();
+        match game.solver.apply_guess(&guess, score) {
+            Ok(()) => {
+                if !quiet {
+                    print_elimination(&guess, score, &before, &game.solver)?;
+                }
+                if let Some(dump) = dump.as_mut() {
+                    dump.record_turn(
+                        &game_name,
+                        candidates,
+                        game.solver.remaining_targets().len(),
+                        &ranked,
+                        &guess,
+                        score,
+                    );
+                }
+                game.turns
+                    .push(report::TurnRecord::new(candidates, &ranked, &guess, score));
+            }
+            Err(e @ (Error::InconsistentScore(..) | Error::EmptyCandidateSet(..))) => {
+                if quiet {
+                    eprintln!("{}", e);
+                    return Ok(Outcome::from_error(&e));
+                }
+                outln!(
+                    "\nThat can't be right: {}. Did you enter your guess and score correctly? Let's try that again.",
+                    e
+                );
+                game.trajectory.pop();
+            }
+            Err(e) => return Err(e),
+        }
+    };
+
+    if stopped.is_some() && !quiet {
+        print_session_summary(&game_name, &game, &other_games)?;
+    }
+
+    if let Some(path) = &args.report {
+        other_games.insert(game_name, game);
+        let games = other_games
+            .iter()
+            .map(|(name, game)| {
+                let remaining = game.solver.remaining_targets();
+                report::GameReport {
+                    name,
+                    turns: &game.turns,
+                    answer: (remaining.len() == 1 && !game.hide_candidates).then(|| remaining[0]),
+                }
+            })
+            .collect::<Vec<_>>();
+        report::write_report(path, &games)?;
+        if !quiet {
+            outln!("\nWrote the report to {}.", path.display());
+        }
+    }
+
+    Ok(outcome)
+}
+
+// How many of the possible answers the score ruled out, and which of its colors did it (see
+// explain_elimination). The counts don't give anything away, so this shows with --no-spoilers
+// too.
+fn print_elimination(
+    guess: &str,
+    score: WordScore,
+    before: &[&'static str],
+    solver: &Solver,
+) -> Result<()> {
+    let kept: HashSet<&str> = solver.remaining_targets().iter().copied().collect();
+    let removed = before
+        .iter()
+        .copied()
+        .filter(|word| !kept.contains(word))
+        .collect::<Vec<_>>();
+    if removed.is_empty() {
+        outln!(
+            "\nThat score didn't rule out any of the {} possible answers.",
+            before.len()
+        );
+        return Ok(());
+    }
+
+    let elimination = explain_elimination(guess, score, &removed, solver.duplicate_policy());
+    let parts = [
+        (elimination.greens, "the greens"),
+        (elimination.yellows, "the yellows"),
+        (elimination.grays, "the grays"),
+    ]
+    .into_iter()
+    .filter(|&(count, _)| count > 0)
+    .enumerate()
+    .map(|(i, (count, color))| match i {
+        0 => format!("{} by {}", count, color),
+        _ => format!("{} more by {}", count, color),
+    })
+    .collect::<Vec<_>>();
+    let breakdown = match parts.as_slice() {
+        [only] => only.clone(),
+        [first, second] => format!("{} and {}", first, second),
+        [rest @ .., last] => format!("{}, and {}", rest.join(", "), last),
+        [] => String::new(),
+    };
+    outln!(
+        "\nThat score ruled out {} of the {} possible answers: {}.",
+        removed.len(),
+        before.len(),
+        breakdown
+    );
+    Ok(())
+}
+
+// Every turn of a game so far in one table, to look back over without scrolling: the guess and
+// its score, how many possible answers there were before and after it, and where the guess
+// ranked among the suggestions.
+fn print_history(game: &Game, color: bool) -> Result<()> {
+    if game.turns.is_empty() {
+        outln!("No guesses yet.");
+        return Ok(());
+    }
+
+    if !plain_output() {
+        outln!("\n #  guess                         before   after  rank");
+    }
+    for (i, turn) in game.turns.iter().enumerate() {
+        let after = match game.turns.get(i + 1) {
+            Some(next) => next.candidates,
+            None => game.solver.remaining_targets().len(),
+        };
+        if plain_output() {
+            outln!(
+                "Guess {}, {}. {} possible answers before it and {} after.{}",
+                i + 1,
+                play::render_board_row(&turn.guess, turn.score, color),
+                turn.candidates,
+                after,
+                match turn.rank {
+                    Some((rank, of, _)) => format!(" It was suggestion {} of {}.", rank, of),
+                    None => String::new(),
+                }
+            );
+            continue;
+        }
+        outln!(
+            "{:2}  {}  {:7}  {:6}  {}",
+            i + 1,
+            play::render_board_row(&turn.guess, turn.score, color),
+            turn.candidates,
+            after,
+            match turn.rank {
+                Some((rank, of, _)) => format!("#{} of {}", rank, of),
+                None => "-".to_string(),
+            }
+        );
+    }
+    Ok(())
+}
+
+// Typing this at either prompt ends the session, the same as running out of input: a game
+// that's been solved exits with 0, and one that hasn't, with 1, so a script can't mistake
+// giving up for winning.
+fn is_quit(input: &str) -> bool {
+    matches!(input.trim().to_lowercase().as_str(), "quit" | "exit")
+}
+
+// What happened in the session, for when it ends before the answer's been found: each game's
+// guesses and scores, and how close it got.
+fn print_session_summary(
+    current: &str,
+    game: &Game,
+    others: &BTreeMap<String, Game>,
+) -> Result<()> {
+    let mut all: Vec<(&str, &Game)> = others.iter().map(|(n, g)| (n.as_str(), g)).collect();
+    all.push((current, game));
+    all.sort_by_key(|(name, _)| *name);
+
+    outln!("\nSession summary:");
+    for (name, g) in all {
+        let remaining = g.solver.remaining_targets();
+        let status = match (g.outcome, remaining.len()) {
+            (Some(Outcome::Solved), 1) if !g.hide_candidates => {
+                format!("solved: {}", remaining[0].to_uppercase())
+            }
+            (Some(Outcome::Solved), _) => "solved".to_string(),
+            (Some(_), _) => "no possible words left".to_string(),
+            (None, n) => format!("{} possible words left", n),
+        };
+        let guesses = format!(
+            "{} guess{}",
+            g.turns.len(),
+            if g.turns.len() == 1 { "" } else { "es" }
+        );
+        if others.is_empty() {
+            outln!("{}, {}", guesses, status);
+        } else {
+            outln!("\n{}: {}, {}", name, guesses, status);
+        }
+        for turn in &g.turns {
+            if plain_output() {
+                outln!(
+                    "  {}",
+                    play::render_board_row(&turn.guess, turn.score, false)
+                );
+            } else {
+                outln!(
+                    "  {} {}",
+                    turn.guess.to_uppercase(),
+                    format_score(turn.score)
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+// Show every game in the session, and how each one is going.
+fn list_games(current: &str, game: &Game, others: &BTreeMap<String, Game>) -> Result<()> {
+    let mut all: Vec<(&str, &Game)> = others.iter().map(|(n, g)| (n.as_str(), g)).collect();
+    all.push((current, game));
+    all.sort_by_key(|(name, _)| *name);
+
+    for (name, g) in all {
+        outln!(
+            "{} {:12} {}",
+            if name == current { '*' } else { ' ' },
+            name,
+            match g.outcome {
+                Some(Outcome::Solved) => "finished".to_string(),
+                Some(_) => "no possible words left".to_string(),
+                None => format!("{} possible words", g.solver.remaining_targets().len()),
+            }
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,154 @@
+use crate::cli::*;
+use clap::Args;
+use wordle_guesser::*;
+
+#[derive(Args)]
+pub struct SquardleArgs {
+    #[clap(flatten)]
+    pub lists: ListArgs,
+
+    /// How many of the top-ranked guesses to list each turn.
+    #[clap(short = 'n', long, default_value_t = 8)]
+    pub suggestions: usize,
+}
+
+// A slot with this many possible words or fewer has them listed.
+const MAX_LISTED: usize = 8;
+
+// Help solve a crossword grid like Squardle's: three words across and three down, sharing the
+// letters where they cross. Each turn, you say which slot your guess went in, and the score it
+// got there. (There's no hard mode, so --hard doesn't do anything here.)
+pub fn run(args: &SquardleArgs, quiet: bool) -> Result<Outcome> {
+    let mut grid = Crossword::new(
+        args.lists.target_list(),
+        overlay::word_lists(args.lists.fold_accents)?,
+    );
+    grid.set_duplicate_policy(default_duplicate_policy());
+
+    loop {
+        if !quiet {
+            outln!();
+            print_grid(&grid)?;
+        }
+        if grid.all_solved() {
+            if quiet {
+                for slot in 0..grid.slots().len() {
+                    outln!("{} {}", grid.slots()[slot].name(), grid.candidates(slot)[0]);
+                }
+            } else {
+                outln!("\nThat's the whole grid!");
+            }
+            return Ok(Outcome::Solved);
+        }
+
+        if !quiet {
+            outln!();
+            for (i, slot) in grid.slots().iter().enumerate() {
+                let words = grid.candidates(i);
+                if grid.is_solved(i) {
+                    outln!("{:9} {}", slot.name(), words[0]);
+                } else if words.len() <= MAX_LISTED {
+                    outln!(
+                        "{:9} {} words: {}",
+                        slot.name(),
+                        words.len(),
+                        words.join(" ")
+                    );
+                } else {
+                    outln!("{:9} {} words", slot.name(), words.len());
+                }
+            }
+            outln!("\nSUGGESTED GUESSES (sorted by the number of words left in the whole grid)");
+            print_rule(102)?;
+        }
+        for q in grid.rank_guesses().iter().take(args.suggestions) {
+            outln!(
+                "{:9} {} | average {:.1} words left in the grid",
+                grid.slots()[q.slot].name(),
+                q.guess,
+                q.expected_total_remaining
+            );
+        }
+
+        let (slot, guess) = loop {
+            prompt(
+                quiet,
+                "\nPlease enter where your guess went and the guess, like 'row 1 crane': ",
+            )?;
+            let input_str = match read_input_line()? {
+                Some(s) => s.to_lowercase(),
+                None => return Ok(Outcome::Unsolved),
+            };
+            let parsed = input_str.rsplit_once(' ').and_then(|(slot, word)| {
+                let slot = grid
+                    .slots()
+                    .iter()
+                    .position(|&s| Some(s) == Slot::parse(slot))?;
+                is_valid_word(word).then(|| (slot, word.to_string()))
+            });
+            if let Some(parsed) = parsed {
+                break parsed;
+            }
+            if quiet {
+                eprintln!("Couldn't understand '{}'.", input_str);
+                return Ok(Outcome::InvalidInput);
+            }
+            outln!("Sorry, that should be a row or column (1, 3, or 5) and then a five-letter word, like 'row 1 crane' or 'c3 slate'.");
+        };
+
+        let score = loop {
+            prompt(
+                quiet,
+                "Enter the score you got for that word, in \".y.GG\" format: ",
+            )?;
+            let input_str = match read_input_line()? {
+                Some(s) => s,
+                None => return Ok(Outcome::Unsolved),
+            };
+            match parse_score(&input_str) {
+                Ok(score) => break score,
+                Err(e) if quiet => {
+                    eprintln!("{}", e);
+                    return Ok(Outcome::InvalidInput);
+                }
+                Err(e) => outln!("Sorry, {}.", e),
+            }
+        };
+
+        match grid.apply_guess(slot, &guess, score) {
+            Ok(()) => {}
+            Err(e @ (Error::InconsistentScore(..) | Error::EmptyCandidateSet(..))) => {
+                if quiet {
+                    eprintln!("{}", e);
+                    return Ok(Outcome::from_error(&e));
+                }
+                outln!(
+                    "\nThat can't be right: {}. Did you enter your guess and score correctly? Let's try that again.",
+                    e
+                );
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+// The grid, with the letters that are certain filled in. The squares between the words, which
+// don't hold letters, are left blank.
+fn print_grid(grid: &Crossword) -> Result<()> {
+    for row in 0..WORD_LENGTH {
+        let line = (0..WORD_LENGTH)
+            .map(|column| {
+                if row % 2 == 1 && column % 2 == 1 {
+                    ' '
+                } else {
+                    grid.letter_at(row, column)
+                        .map_or('_', |c| c.to_ascii_uppercase())
+                }
+            })
+            .map(String::from)
+            .collect::<Vec<_>>()
+            .join(" ");
+        outln!("  {}", line);
+    }
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use itertools::Itertools;
+use std::collections::HashMap;
+use wordle_guesser::*;
+
+// How many letters to list for each place in the word, and overall.
+const TOP_LETTERS: usize = 3;
+
+// Sum up the possible answers, for when the list is too long to get a feel for by reading it:
+// how common the words are, how many have a letter twice, how many vowels they have, and which
+// letters turn up the most in each place. Words aren't listed, apart from the most and least
+// common, so this is mostly about the shape of the list.
+pub fn render_candidate_stats(targets: &[&str]) -> String {
+    let mut lines = vec![format!("{} possible answers.", targets.len())];
+    if targets.is_empty() {
+        return lines.join("\n");
+    }
+    let percent = |n: usize| n * 100 / targets.len();
+
+    // The frequency list is sorted most common first, so a word's place in it says how common
+    // it is. The counts themselves are from a huge pile of text, and aren't very meaningful on
+    // their own.
+    let ranks: HashMap<&str, usize> = word_frequencies()
+        .iter()
+        .enumerate()
+        .map(|(rank, (word, _count))| (*word, rank + 1))
+        .collect();
+    let ranked = targets
+        .iter()
+        .filter_map(|w| Some((*ranks.get(w)?, *w)))
+        .sorted()
+        .collect::<Vec<_>>();
+    if let (Some(first), Some(last)) = (ranked.first(), ranked.last()) {
+        lines.push(format!(
+            "Commonness: the middle one is the #{} most common five-letter word. Most common is {}, least common is {}.",
+            ranked[ranked.len() / 2].0,
+            first.1,
+            last.1
+        ));
+    }
+    if ranked.len() < targets.len() {
+        lines.push(format!(
+            "{} aren't in the word frequency list at all.",
+            targets.len() - ranked.len()
+        ));
+    }
+
+    let repeats = targets
+        .iter()
+        .filter(|w| w.chars().unique().count() < w.chars().count())
+        .count();
+    lines.push(format!(
+        "Repeated letters: {} ({}%) have a letter more than once.",
+        repeats,
+        percent(repeats)
+    ));
+
+    let mut by_vowels = [0usize; WORD_LENGTH + 1];
+    for word in targets {
+        let vowels = word.chars().filter(|c| "aeiou".contains(*c)).count();
+        by_vowels[vowels.min(WORD_LENGTH)] += 1;
+    }
+    lines.push(format!(
+        "Vowels (not counting Y): {}",
+        by_vowels
+            .iter()
+            .enumerate()
+            .filter(|(_, &count)| count > 0)
+            .map(|(vowels, &count)| format!("{}% have {}", percent(count), vowels))
+            .join(", ")
+    ));
+
+    let top_letters = |letters: &mut dyn Iterator<Item = char>| {
+        letters
+            .counts()
+            .into_iter()
+            .sorted_by_key(|&(letter, count)| (std::cmp::Reverse(count), letter))
+            .take(TOP_LETTERS)
+            .map(|(letter, count)| format!("{} {}%", letter.to_uppercase(), percent(count)))
+            .join(", ")
+    };
+    lines.push(format!(
+        "Most words have: {}",
+        top_letters(&mut targets.iter().flat_map(|w| w.chars().unique()))
+    ));
+    lines.push("Most common letters in each place:".to_string());
+    for position in 0..WORD_LENGTH {
+        lines.push(format!(
+            "  {}: {}",
+            position + 1,
+            top_letters(&mut targets.iter().filter_map(|w| w.chars().nth(position)))
+        ));
+    }
+
+    lines.join("\n")
+}
@@ -0,0 +1,250 @@
+use crate::cli::*;
+use clap::Args;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use wordle_guesser::*;
+
+#[derive(Args)]
+pub struct SweepArgs {
+    #[clap(flatten)]
+    pub lists: ListArgs,
+
+    #[clap(flatten)]
+    pub memory: MemoryArgs,
+
+    /// The weights to try on the log of the average number of words a guess leaves. A range
+    /// is written START:END:STEP, like 0:2:0.5, or the values can be listed, like 0.5,1,2.
+    #[clap(long, value_name = "RANGE", default_value = "1", parse(try_from_str = parse_sweep))]
+    pub average: Sweep,
+
+    /// The weights to try on the log of the most words a guess can leave.
+    #[clap(long, value_name = "RANGE", default_value = "1", parse(try_from_str = parse_sweep))]
+    pub worst: Sweep,
+
+    /// The weights to try on how much a guess tells you on average (its entropy).
+    #[clap(long, value_name = "RANGE", default_value = "0", parse(try_from_str = parse_sweep))]
+    pub entropy: Sweep,
+
+    /// The bonuses to try for a guess that might be the answer itself. 0.1 makes a guess like
+    /// that worth the same as one that leaves about 10% fewer words.
+    #[clap(long, value_name = "RANGE", default_value = "0", parse(try_from_str = parse_sweep))]
+    pub could_win: Sweep,
+
+    /// Write every combination's results to this file, as CSV, for plotting.
+    #[clap(long, value_name = "FILE")]
+    pub csv: Option<PathBuf>,
+
+    /// How many of the best combinations to show at the end.
+    #[clap(short = 'n', long, value_name = "N", default_value = "10")]
+    pub show: usize,
+}
+
+// The values to try for one of the weights.
+#[derive(Clone, Debug)]
+pub struct Sweep(Vec<f64>);
+
+// A run of games with one combination of weights.
+struct Trial {
+    weights: Weights,
+    summary: SimulationSummary,
+}
+
+// Try every combination of weights in the ranges, playing a game against every solution with
+// each, to tune the ranking. The weights that come out of the box (1, 1, 0, 0) are the same as
+// the ranker's suggestions, so it's easy to see whether a change is an improvement.
+pub fn run(args: &SweepArgs, quiet: bool) -> Result<Outcome> {
+    let mut solver = Solver::with_word_lists(
+        TargetList::Solutions,
+        args.lists.is_hard(),
+        overlay::word_lists(args.lists.fold_accents)?,
+    );
+    args.lists.apply_hard_mode(&mut solver);
+    solver.set_duplicate_policy(default_duplicate_policy());
+    args.memory.precompute(&mut solver, quiet)?;
+
+    let mut combinations = Vec::new();
+    for &average in &args.average.0 {
+        for &worst in &args.worst.0 {
+            for &entropy in &args.entropy.0 {
+                for &could_win in &args.could_win.0 {
+                    combinations.push(Weights {
+                        average,
+                        worst,
+                        entropy,
+                        could_win,
+                    });
+                }
+            }
+        }
+    }
+
+    // The CSV is written as the results come in, so a long run that gets stopped still leaves
+    // everything it finished.
+    let mut csv = match &args.csv {
+        None => None,
+        Some(path) => {
+            let file = File::create(path).map_err(|e| {
+                io::Error::new(
+                    e.kind(),
+                    format!("couldn't create {}: {}", path.display(), e),
+                )
+            })?;
+            let mut file = BufWriter::new(file);
+            writeln!(
+                file,
+                "average,worst,entropy,could_win,average_guesses,worst_guesses,failures"
+            )?;
+            Some(file)
+        }
+    };
+
+    if !quiet {
+        outln!(
+            "Trying {} combination{} of weights against all {} solutions{}...\n",
+            combinations.len(),
+            if combinations.len() == 1 { "" } else { "s" },
+            solver.remaining_targets().len(),
+            describe_hard_mode(&solver)
+        );
+        outln!("average  worst  entropy  could win | guesses | worst | fail");
+        print_rule(58)?;
+    }
+
+    let row = |trial: &Trial| {
+        format!(
+            "{:7}  {:5}  {:7}  {:9} | {:7.4} | {:5} | {:4}",
+            trial.weights.average,
+            trial.weights.worst,
+            trial.weights.entropy,
+            trial.weights.could_win,
+            trial.summary.average_guesses,
+            trial.summary.worst_guesses,
+            trial.summary.failures
+        )
+    };
+
+    let mut trials = Vec::with_capacity(combinations.len());
+    for weights in combinations {
+        let games = simulate(&solver, &WeightedStrategy(weights), None);
+        let trial = Trial {
+            weights,
+            summary: summarize(&games),
+        };
+        if !quiet {
+            outln!("{}", row(&trial));
+        }
+        if let Some(file) = csv.as_mut() {
+            writeln!(
+                file,
+                "{},{},{},{},{:.6},{},{}",
+                weights.average,
+                weights.worst,
+                weights.entropy,
+                weights.could_win,
+                trial.summary.average_guesses,
+                trial.summary.worst_guesses,
+                trial.summary.failures
+            )?;
+            file.flush()?;
+        }
+        trials.push(trial);
+    }
+
+    // The same order as the tournament: missing a game is losing, so that counts first.
+    trials.sort_by(|a, b| {
+        a.summary
+            .failures
+            .cmp(&b.summary.failures)
+            .then(
+                a.summary
+                    .average_guesses
+                    .total_cmp(&b.summary.average_guesses),
+            )
+            .then(a.summary.worst_guesses.cmp(&b.summary.worst_guesses))
+    });
+
+    if quiet {
+        for trial in &trials {
+            outln!(
+                "{} {} {} {} {:.4} {} {}",
+                trial.weights.average,
+                trial.weights.worst,
+                trial.weights.entropy,
+                trial.weights.could_win,
+                trial.summary.average_guesses,
+                trial.summary.worst_guesses,
+                trial.summary.failures
+            );
+        }
+        return Ok(Outcome::Solved);
+    }
+
+    if trials.len() > 1 {
+        outln!("\nThe best {}:\n", args.show.min(trials.len()));
+        outln!("average  worst  entropy  could win | guesses | worst | fail");
+        print_rule(58)?;
+        for trial in trials.iter().take(args.show) {
+            outln!("{}", row(trial));
+        }
+    }
+    if let Some(best) = trials.first() {
+        outln!(
+            "\nThe best weights: average {}, worst {}, entropy {}, could win {}{}.",
+            best.weights.average,
+            best.weights.worst,
+            best.weights.entropy,
+            best.weights.could_win,
+            if best.weights == Weights::RANKER {
+                ", the same as the ranker"
+            } else {
+                ""
+            }
+        );
+    }
+    if let Some(path) = &args.csv {
+        outln!("Wrote every combination to {}.", path.display());
+    }
+    Ok(Outcome::Solved)
+}
+
+// Read a list of values to try: START:END:STEP for a range that includes both ends, or values
+// separated by commas, or just one value.
+fn parse_sweep(text: &str) -> std::result::Result<Sweep, String> {
+    let bad = || {
+        format!(
+            "'{}' isn't a range like 0:2:0.5, or a list like 0.5,1,2",
+            text
+        )
+    };
+    let number = |s: &str| s.trim().parse::<f64>().ok().filter(|n| n.is_finite());
+
+    let parts = text.split(':').collect::<Vec<_>>();
+    if let [start, end, step] = parts.as_slice() {
+        let (start, end, step) = match (number(start), number(end), number(step)) {
+            (Some(start), Some(end), Some(step)) if step > 0.0 && start <= end => {
+                (start, end, step)
+            }
+            _ => return Err(bad()),
+        };
+
+        // Counting steps rather than adding the step up each time, so that 0:1:0.1 doesn't
+        // drift and miss out the 1 at the end.
+        let steps = ((end - start) / step + 1e-9).floor() as usize;
+        return Ok(Sweep(
+            (0..=steps)
+                .map(|i| {
+                    let value = start + i as f64 * step;
+                    (value * 1e9).round() / 1e9
+                })
+                .collect(),
+        ));
+    }
+
+    text.split(',')
+        .map(number)
+        .collect::<Option<Vec<_>>>()
+        .filter(|values| !values.is_empty())
+        .map(Sweep)
+        .ok_or_else(bad)
+}
@@ -0,0 +1,226 @@
+use crate::cli::json::*;
+use crate::cli::*;
+use clap::{ArgEnum, Args};
+use serde::Serialize;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use wordle_guesser::*;
+
+// Scores for a fixed set of guesses and answers, for checking another scorer against this one:
+// a SIMD or GPU version, or one in another language. Most pairs of real words are easy to get
+// right; the mistakes are all in the repeated letters. So the set is some real words picked for
+// their repeats, and then every pair of words made from just a couple of letters, which goes
+// through every way the copies of a letter can line up.
+
+// The pairs picked by hand, and what each one checks.
+const HAND_PICKED: &[(&str, &str, &str)] = &[
+    ("crane", "crane", "every letter green"),
+    ("pious", "crane", "every letter gray"),
+    ("alert", "later", "every letter yellow"),
+    (
+        "cheer",
+        "abbey",
+        "the green E uses up the answer's only E, so the other is gray",
+    ),
+    (
+        "geese",
+        "those",
+        "three E's in the guess, one in the answer, and it's the green one",
+    ),
+    (
+        "speed",
+        "abide",
+        "two E's in the guess, one in the answer: the first is yellow",
+    ),
+    (
+        "speed",
+        "erase",
+        "two E's in the guess and two in the answer, both yellow",
+    ),
+    ("lever", "eerie", "the green E, then a yellow one"),
+    (
+        "eerie",
+        "lever",
+        "three E's against two: one green, one yellow, one gray",
+    ),
+    (
+        "abbey",
+        "kebab",
+        "two B's in each, one lined up and one not",
+    ),
+    ("kebab", "abbey", "the same, the other way around"),
+    (
+        "error",
+        "rover",
+        "three R's in the guess, two in the answer",
+    ),
+    (
+        "rover",
+        "error",
+        "two R's in the guess, three in the answer",
+    ),
+    ("mamma", "maxim", "three M's against two, one of them green"),
+    (
+        "llama",
+        "hello",
+        "two L's in the guess and in the answer, in other places",
+    ),
+    ("sassy", "assay", "three S's against two"),
+    ("tatty", "treat", "three T's against two, one green"),
+    ("fluff", "offal", "three F's against two, none green"),
+    ("niñas", "señal", "a letter outside of plain ASCII"),
+];
+
+// Every word made of just these letters gets scored against every other. Two letters is all it
+// takes to go through the ways copies of one letter can line up; three adds how two repeated
+// letters get in each other's way, at 59049 pairs.
+const MAX_LETTERS: usize = 3;
+
+#[derive(Args)]
+pub struct TestVectorsArgs {
+    /// Write the vectors to this file instead of printing them.
+    #[clap(short, long, value_name = "FILE")]
+    pub output: Option<PathBuf>,
+
+    /// JSON, or one vector per line, tab-separated.
+    #[clap(long, arg_enum, default_value = "json")]
+    pub format: VectorFormat,
+
+    /// Also score every word made of just these letters against every other. Up to three
+    /// letters.
+    #[clap(long, value_name = "LETTERS", default_value = "ab")]
+    pub letters: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ArgEnum)]
+pub enum VectorFormat {
+    Json,
+    Tsv,
+}
+
+// One guess and answer, and the score. The score is in the usual ".y.GG" form, and also as the
+// number the program uses for it: each letter is a digit in base 3, gray 0, yellow 1, green 2,
+// with the first letter the lowest digit. So ".y..G" is 0 + 1*3 + 0*9 + 0*27 + 2*81 = 165.
+#[derive(Serialize)]
+struct Vector {
+    guess: String,
+    target: String,
+    score: String,
+    value: WordScore,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    note: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+struct VectorFile {
+    schema_version: u32,
+    version: &'static str,
+    duplicates: &'static str,
+    vectors: Vec<Vector>,
+}
+
+pub fn run(args: &TestVectorsArgs, quiet: bool) -> Result<Outcome> {
+    let letters = args.letters.to_lowercase().chars().collect::<Vec<_>>();
+    if letters.is_empty()
+        || letters.len() > MAX_LETTERS
+        || !letters.iter().all(|c| c.is_alphabetic())
+        || (1..letters.len()).any(|i| letters[..i].contains(&letters[i]))
+    {
+        return Err(Error::Config(format!(
+            "'{}' should be one to {} different letters",
+            args.letters, MAX_LETTERS
+        )));
+    }
+
+    let mut vectors = Vec::new();
+    for &(guess, target, note) in HAND_PICKED {
+        vectors.push(vector(guess, target, Some(note))?);
+    }
+    let words = words_from(&letters);
+    for guess in &words {
+        for target in &words {
+            vectors.push(vector(guess, target, None)?);
+        }
+    }
+
+    let count = vectors.len();
+    let text = match args.format {
+        VectorFormat::Json => {
+            let file = VectorFile {
+                schema_version: SCHEMA_VERSION,
+                version: env!("CARGO_PKG_VERSION"),
+                duplicates: match default_duplicate_policy() {
+                    DuplicatePolicy::Standard => "standard",
+                    DuplicatePolicy::AllYellow => "all-yellow",
+                },
+                vectors,
+            };
+            serde_json::to_string_pretty(&file).map_err(io::Error::from)? + "\n"
+        }
+        VectorFormat::Tsv => {
+            let mut text = "# guess\ttarget\tscore\tvalue\n".to_string();
+            for v in &vectors {
+                text += &format!("{}\t{}\t{}\t{}\n", v.guess, v.target, v.score, v.value);
+            }
+            text
+        }
+    };
+
+    match &args.output {
+        None => out!("{}", text),
+        Some(path) => {
+            fs::write(path, &text).map_err(|e| {
+                io::Error::new(
+                    e.kind(),
+                    format!("couldn't write {}: {}", path.display(), e),
+                )
+            })?;
+            if !quiet {
+                outln!("Wrote {} test vectors to {}.", count, path.display());
+            }
+        }
+    }
+
+    Ok(Outcome::Solved)
+}
+
+// Score a pair by the rule from --duplicates, making sure first that the fast scorer and the
+// simple one agree on it. A file of vectors from a broken scorer would be worse than none. The
+// simple one only knows Wordle's own rule for repeated letters, and only English.
+fn vector(guess: &str, target: &str, note: Option<&'static str>) -> Result<Vector> {
+    let policy = default_duplicate_policy();
+    let value = policy.score(guess, target);
+    if policy == DuplicatePolicy::Standard
+        && guess.is_ascii()
+        && target.is_ascii()
+        && score_word_pair_simple(guess, target) != value
+    {
+        return Err(Error::Config(format!(
+            "the scorers disagree on {} against {}: {} from the fast one, {} from the simple one",
+            guess,
+            target,
+            format_score(value),
+            format_score(score_word_pair_simple(guess, target))
+        )));
+    }
+    Ok(Vector {
+        guess: guess.to_string(),
+        target: target.to_string(),
+        score: format_score(value).to_string(),
+        value,
+        note,
+    })
+}
+
+// Every word of WORD_LENGTH letters made of just these, in order.
+fn words_from(letters: &[char]) -> Vec<String> {
+    let mut words = vec![String::new()];
+    for _ in 0..WORD_LENGTH {
+        words = words
+            .iter()
+            .flat_map(|word| letters.iter().map(move |&c| format!("{}{}", word, c)))
+            .collect();
+    }
+    words
+}
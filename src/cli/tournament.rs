@@ -0,0 +1,166 @@
+use crate::cli::analyze::SIGNIFICANCE_LEVEL;
+use crate::cli::*;
+use clap::Args;
+use std::time::Instant;
+use wordle_guesser::*;
+
+#[derive(Args)]
+pub struct TournamentArgs {
+    #[clap(flatten)]
+    pub lists: ListArgs,
+
+    #[clap(flatten)]
+    pub memory: MemoryArgs,
+
+    /// Also play each strategy against a host that cheats, like Absurdle, changing the answer
+    /// after every guess to whichever word keeps the game going longest.
+    #[clap(long)]
+    pub adversarial: bool,
+
+    /// Seed the random choices (the "random" strategy's guesses), so that a run can be repeated
+    /// exactly.
+    #[clap(long)]
+    pub seed: Option<u64>,
+}
+
+// How one strategy did in the tournament.
+struct Entry {
+    name: &'static str,
+    games: Vec<GameResult>,
+    summary: SimulationSummary,
+    absurdle: Option<GameResult>,
+    seconds: f64,
+}
+
+// Every strategy there is against the whole Wordle solution list, ranked into a leaderboard.
+// This goes further than `analyze strategies`: it puts them in order, checks whether each one
+// is really better than the next one down or just luckier with the answers, and can add a round
+// against a cheating host as well.
+pub fn run(args: &TournamentArgs, quiet: bool) -> Result<Outcome> {
+    let mut solver = Solver::with_word_lists(
+        TargetList::Solutions,
+        args.lists.is_hard(),
+        overlay::word_lists(args.lists.fold_accents)?,
+    );
+    args.lists.apply_hard_mode(&mut solver);
+    solver.set_duplicate_policy(default_duplicate_policy());
+    args.memory.precompute(&mut solver, quiet)?;
+
+    let strategies = all_strategies();
+    if !quiet {
+        outln!(
+            "Playing {} strategies against all {} solutions{}{}...",
+            strategies.len(),
+            solver.remaining_targets().len(),
+            describe_hard_mode(&solver),
+            if args.adversarial {
+                ", and against a cheating host"
+            } else {
+                ""
+            }
+        );
+    }
+
+    let mut entries = Vec::with_capacity(strategies.len());
+    for strategy in &strategies {
+        let start = Instant::now();
+        let games = simulate(&solver, strategy.as_ref(), args.seed);
+        let absurdle = args
+            .adversarial
+            .then(|| play_absurdle(&solver, strategy.as_ref(), args.seed));
+        entries.push(Entry {
+            name: strategy.name(),
+            summary: summarize(&games),
+            games,
+            absurdle,
+            seconds: start.elapsed().as_secs_f64(),
+        });
+    }
+
+    // Fewest guesses on average wins. A strategy that misses games is worse than one that
+    // doesn't, whatever its average, since missing is losing; the worst game breaks any ties
+    // that are left.
+    entries.sort_by(|a, b| {
+        a.summary
+            .failures
+            .cmp(&b.summary.failures)
+            .then(
+                a.summary
+                    .average_guesses
+                    .total_cmp(&b.summary.average_guesses),
+            )
+            .then(a.summary.worst_guesses.cmp(&b.summary.worst_guesses))
+            .then(a.name.cmp(b.name))
+    });
+
+    if quiet {
+        for (place, entry) in entries.iter().enumerate() {
+            out!(
+                "{} {} {:.4} {} {}",
+                place + 1,
+                entry.name,
+                entry.summary.average_guesses,
+                entry.summary.worst_guesses,
+                entry.summary.failures
+            );
+            if let Some(game) = &entry.absurdle {
+                out!(" {}", game.guesses.len());
+            }
+            outln!();
+        }
+        return Ok(Outcome::Solved);
+    }
+
+    out!("\n  # strategy  | average | worst | fail | in 3 |");
+    if args.adversarial {
+        out!(" absurdle |");
+    }
+    outln!(" seconds | vs. next");
+    print_rule(78)?;
+    for (place, entry) in entries.iter().enumerate() {
+        let summary = &entry.summary;
+        let in_three: usize = summary.distribution[..=3].iter().sum();
+        out!(
+            "{:3} {:9} | {:7.3} | {:5} | {:4} | {:3.0}% |",
+            place + 1,
+            entry.name,
+            summary.average_guesses,
+            summary.worst_guesses,
+            summary.failures,
+            in_three as f64 * 100.0 / summary.games.max(1) as f64
+        );
+        if let Some(game) = &entry.absurdle {
+            if game.solved() {
+                out!(" {:8} |", game.guesses.len());
+            } else {
+                out!(" {:>8} |", "gave up");
+            }
+        }
+        out!(" {:7.2} |", entry.seconds);
+
+        // Whether the gap to the next one down is real, or could be down to which answers
+        // happened to suit which strategy.
+        match entries.get(place + 1) {
+            Some(next) => {
+                let result = head_to_head(&entry.games, &next.games);
+                if result.t_test_p < SIGNIFICANCE_LEVEL {
+                    outln!(" better");
+                } else {
+                    outln!(" too close to call");
+                }
+            }
+            None => outln!(),
+        }
+    }
+
+    outln!("\n\"in 3\" is the share of games won in three guesses or fewer.");
+    if args.adversarial {
+        outln!("\"absurdle\" is how many guesses it took to corner the cheating host.");
+    }
+    outln!(
+        "\"vs. next\" says whether a strategy is really better than the one below it, or whether the\n\
+         gap could be down to luck (a paired t-test, at p < {}).",
+        SIGNIFICANCE_LEVEL
+    );
+    Ok(Outcome::Solved)
+}
@@ -0,0 +1,351 @@
+use crate::cli::solve::{print_suggested_guess_list, DisplayOptions, Quality};
+use crate::cli::*;
+use wordle_guesser::*;
+
+// The tutorial is a sample game, played for real against the solver, with explanations along
+// the way. The answer's always the same, so it's a game you can't lose track of, but the guesses
+// are up to you: the solver suggests, you pick, and the tutorial plays the part of the website
+// and tells you the colors. Reading about the score format is one thing; typing a few in while
+// someone checks them is how it sticks.
+
+// The answer to the sample game. It's a common word, so it's in the list from the start, and
+// it takes the suggestions three or four guesses to find, which is enough turns to show
+// everything off without dragging on.
+const ANSWER: &str = "plant";
+
+// The same number of tries as the real game.
+const MAX_TURNS: usize = 6;
+
+// How many suggestions to list. Fewer than `solve` shows, so the explanations aren't lost in
+// them.
+const SUGGESTIONS_SHOWN: usize = 5;
+
+// For the first few turns, you type in the score yourself, for practice. After that the
+// tutorial fills it in, so the end of the game doesn't drag.
+const PRACTICE_TURNS: usize = 2;
+
+// How many tries you get at typing a score before the tutorial just tells you.
+const PRACTICE_TRIES: usize = 3;
+
+// Walk through a game against ANSWER, explaining the suggestions, the score format, and the
+// commands as they come up. Returns Unsolved if the input runs out partway.
+pub fn run(color: bool) -> Result<Outcome> {
+    let mut solver =
+        Solver::with_word_lists(TargetList::Common(5000), false, overlay::word_lists(false)?);
+    solver.set_duplicate_policy(default_duplicate_policy());
+    let display = DisplayOptions {
+        num_shown: SUGGESTIONS_SHOWN,
+        quiet: false,
+        hide_candidates: false,
+        color,
+        pareto: false,
+        compare_metrics: false,
+        follow_up: false,
+        easy_typing: false,
+        soft_hard: None,
+        alphabetical: false,
+        use_cache: true,
+        quality: Quality::Instant,
+    };
+
+    outln!("Welcome! This is a practice game, to show you how WordleGuesser works.\n");
+    outln!("Normally you play Wordle on its website, and tell this program what guesses you made");
+    outln!("and what colors they got. It keeps track of which words could still be the answer,");
+    outln!("and suggests the guess that narrows them down the most. Here, the tutorial plays the");
+    outln!("part of the website: it has picked a secret word, and will color your guesses.");
+    if !pause()? {
+        return Ok(Outcome::Unsolved);
+    }
+
+    let mut board = Vec::new();
+    for turn in 1..=MAX_TURNS {
+        let mut ranked = cache::rank_guesses(&solver);
+        match solver.remaining_targets().len() {
+            1 => outln!("\nThere's only one possible answer left. The top suggestions:\n"),
+            n => outln!(
+                "\nThere are {} possible answers right now. The top suggestions:\n",
+                n
+            ),
+        }
+        print_suggested_guess_list(&ranked, &solver, &display)?;
+        match turn {
+            1 => explain_suggestions(&ranked)?,
+            2 => explain_commands()?,
+            _ => {}
+        }
+
+        let guess = match read_guess(&solver, &mut ranked, &board)? {
+            Some(guess) => guess,
+            None => return Ok(Outcome::Unsolved),
+        };
+        let score = solver.score(&guess, ANSWER);
+        board.push(play::render_board_row(&guess, score, color));
+
+        outln!("\nThe game colors your guess like this:\n");
+        outln!("{}", play::render_board_row(&guess, score, color));
+        if score == ALL_GREEN {
+            outln!(
+                "\nAll green: the answer was {}, and you got it in {}!",
+                ANSWER.to_uppercase(),
+                turn
+            );
+            explain_whats_next()?;
+            return Ok(Outcome::Solved);
+        }
+
+        if turn <= PRACTICE_TURNS {
+            if turn == 1 {
+                explain_score_format()?;
+            }
+            if !practice_score(&guess, score)? {
+                return Ok(Outcome::Unsolved);
+            }
+        } else {
+            outln!(
+                "\n(You'd type that as {}. The tutorial has entered it for you.)",
+                format_score(score)
+            );
+        }
+
+        let before = solver.remaining_targets().len();
+        solver.apply_guess(&guess, score)?;
+        if turn == 1 {
+            outln!(
+                "\nThat score rules out every word that wouldn't have gotten it, leaving {} of the {}.",
+                solver.remaining_targets().len(),
+                before
+            );
+            outln!("Here's the keyboard, the same as the game shows it, with what's known about each letter:\n");
+            outln!("{}", keyboard::render_keyboard(solver.knowledge(), color));
+            if !pause()? {
+                return Ok(Outcome::Unsolved);
+            }
+        }
+    }
+
+    outln!(
+        "\nOut of tries! The answer was {}. It happens -- and in a real game, the suggestions",
+        ANSWER.to_uppercase()
+    );
+    outln!("would have found it.");
+    explain_whats_next()?;
+    Ok(Outcome::Solved)
+}
+
+// Wait for Enter, so that the explanations don't all scroll past at once. Returns false if the
+// input has run out.
+fn pause() -> Result<bool> {
+    prompt(false, "\n(Press Enter to go on.) ")?;
+    Ok(read_input_line()?.is_some())
+}
+
+fn explain_suggestions(ranked: &[GuessQuality]) -> Result<()> {
+    let top = match ranked.first() {
+        Some(top) => top,
+        None => return Ok(()),
+    };
+    outln!("\nEach line is a guess you could make, best first. Taking the top one:");
+    outln!(
+        "  - \"average {:.1} left\": after the game colors {}, that's how many words would be left",
+        top.expected_remaining,
+        top.guess
+    );
+    outln!("    on average. Smaller is better.");
+    // The same words as the line being explained, which are different with --plain.
+    let worst = if plain_output() {
+        format!(
+            "at most {} left if it gets {}",
+            top.max_remaining,
+            describe_score(top.guess, top.score_with_max_remaining)
+        )
+    } else {
+        format!(
+            "max {} left with {}",
+            top.max_remaining,
+            format_score(top.score_with_max_remaining)
+        )
+    };
+    outln!(
+        "  - \"{}\": the worst it could go. If it got those colors, {} words",
+        worst,
+        top.max_remaining
+    );
+    if plain_output() {
+        outln!("    would still be in the running. The words after \"leaving\" are some of them.");
+        outln!("  - \"could win\" means the guess could be the answer itself, and win right away.");
+    } else {
+        outln!("    would still be in the running. The words after the => are some of them.");
+        outln!(
+            "  - A * at the start means the guess could be the answer itself, and win right away."
+        );
+    }
+    outln!("The top suggestions usually can't be the answer: they're picked to find out as much");
+    outln!("as possible, not to get lucky.");
+    outln!(
+        "\nType one of them, or any other word you like, or just press Enter to take the top one."
+    );
+    Ok(())
+}
+
+fn explain_commands() -> Result<()> {
+    outln!("\nAt this prompt, you can type a command instead of a guess. A few to try:");
+    outln!("  peek       the words that could still be the answer");
+    outln!("  grid       what's known about each letter of the answer");
+    outln!("  history    the guesses so far");
+    outln!("  rate WORD  how a word you have in mind compares with the suggestions");
+    outln!("The real solver has more: 'best now' for the single likeliest answer, 'find' to");
+    outln!("search the possible answers with a pattern, 'finish' to see how many more guesses");
+    outln!("it'll probably take, and others.");
+    outln!("When you're ready, type a guess, or press Enter for the top suggestion.");
+    Ok(())
+}
+
+fn explain_score_format() -> Result<()> {
+    outln!(
+        "\nNow you tell the program what colors you got, in a short code, one character a letter:"
+    );
+    outln!("  .  for gray: the letter isn't in the answer");
+    outln!("  y  for yellow: it's in the answer, but somewhere else");
+    outln!("  G  for green: it's in the answer, right there");
+    outln!("So gray, yellow, gray, gray, green would be .y..G. Have a go at typing this one in.");
+    Ok(())
+}
+
+// Have the player type in the score, and check it. A wrong one gets the letters that are off
+// pointed out, and after a few tries, the tutorial gives the answer and moves on. Returns false
+// if the input runs out.
+fn practice_score(guess: &str, score: WordScore) -> Result<bool> {
+    let expected = format_score(score);
+    for _ in 0..PRACTICE_TRIES {
+        prompt(false, "\nThe score: ")?;
+        let input = match read_input_line()? {
+            Some(input) => input,
+            None => return Ok(false),
+        };
+        let typed = match parse_score(&input) {
+            Ok(typed) => typed,
+            Err(e) => {
+                outln!("{}", e);
+                continue;
+            }
+        };
+        if typed == score {
+            outln!("That's right.");
+            return Ok(true);
+        }
+
+        let typed = format_score(typed);
+        for (i, (letter, (want, got))) in guess
+            .chars()
+            .zip(expected.chars().zip(typed.chars()))
+            .enumerate()
+        {
+            if want != got {
+                outln!(
+                    "Not quite: letter {}, {}, is {}, so it's a '{}'.",
+                    i + 1,
+                    letter.to_ascii_uppercase(),
+                    match want {
+                        'G' => "green",
+                        'y' => "yellow",
+                        _ => "gray",
+                    },
+                    want
+                );
+            }
+        }
+    }
+    outln!("It's '{}'. You'll get the hang of it!", expected);
+    Ok(true)
+}
+
+// Read the next guess, handling the commands along the way. An empty line takes the top
+// suggestion. Returns None if the input runs out.
+fn read_guess(
+    solver: &Solver,
+    ranked: &mut Vec<GuessQuality<'static>>,
+    board: &[String],
+) -> Result<Option<String>> {
+    loop {
+        prompt(false, "\nYour guess: ")?;
+        let input = match read_input_line()? {
+            Some(input) => input.to_lowercase(),
+            None => return Ok(None),
+        };
+
+        match input.as_str() {
+            "" => match ranked.first() {
+                Some(top) => {
+                    outln!("Going with the top suggestion, {}.", top.guess);
+                    return Ok(Some(top.guess.to_string()));
+                }
+                None => continue,
+            },
+            "peek" => {
+                outln!();
+                for line in word_columns(solver.remaining_targets(), false) {
+                    outln!("{}", line);
+                }
+                outln!("\nThe most common words come first, since they're the likeliest answers.");
+                continue;
+            }
+            "grid" => {
+                outln!("\n{}", grid::render_constraint_grid(solver.knowledge()));
+                continue;
+            }
+            "history" => {
+                if board.is_empty() {
+                    outln!("No guesses yet.");
+                }
+                for row in board {
+                    outln!("{}", row);
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        if let Some(word) = input.strip_prefix("rate ") {
+            let word = word.trim();
+            cache::complete_ranking(ranked, solver, word);
+            match ranked.iter().position(|q| q.guess == word) {
+                Some(i) => outln!(
+                    "{} is #{} of {}: average {:.1} left, max {} left.{}",
+                    word,
+                    i + 1,
+                    ranked.len(),
+                    ranked[i].expected_remaining,
+                    ranked[i].max_remaining,
+                    if ranked[i].has_winning {
+                        " It could be the answer."
+                    } else {
+                        ""
+                    }
+                ),
+                None => outln!("'{}' isn't in the word list.", word),
+            }
+            continue;
+        }
+
+        if !solver.valid_guesses().contains(&input.as_str()) {
+            outln!(
+                "'{}' isn't in the word list. Guesses have to be real five-letter words.",
+                input
+            );
+            continue;
+        }
+        return Ok(Some(input));
+    }
+}
+
+fn explain_whats_next() -> Result<()> {
+    outln!("\nThat's the tutorial! To use the solver on a real game, run wordle_guesser with no");
+    outln!("arguments, and type in your guesses and their scores as you play. Some options:");
+    outln!("  -h, --hard       for hard mode, where every guess has to fit the colors so far");
+    outln!("  --solutions      start from Wordle's own list of answers");
+    outln!("  --plain          plain output, for screen readers");
+    outln!("And some other things it does: 'wordle_guesser play' to play a game against it,");
+    outln!("'wordle_guesser reverse' to work out a friend's guesses from their share grid, and");
+    outln!("'wordle_guesser --help' for everything else.");
+    Ok(())
+}
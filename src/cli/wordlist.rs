@@ -0,0 +1,28 @@
+use crate::cli::*;
+use clap::Args;
+
+#[derive(Args)]
+pub struct WordlistArgs {
+    #[clap(flatten)]
+    pub lists: ListArgs,
+
+    /// List the words that are accepted as guesses, instead of the possible answers.
+    #[clap(short, long)]
+    pub guesses: bool,
+}
+
+// Print one of the word lists, one word per line, most common first where that's known.
+pub fn run(args: &WordlistArgs) -> Result<Outcome> {
+    let solver = args.lists.solver()?;
+    let words = if args.guesses {
+        solver.valid_guesses()
+    } else {
+        solver.remaining_targets()
+    };
+
+    for word in words {
+        outln!("{}", word);
+    }
+
+    Ok(Outcome::Solved)
+}
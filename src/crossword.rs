@@ -0,0 +1,269 @@
+use crate::*;
+use std::collections::BTreeMap;
+
+// Squardle and games like it put several words in a grid, crossing each other: rows 1, 3, and 5
+// go across, columns 1, 3, and 5 go down, and where a row and a column cross they share a
+// letter. Each guess goes in one of those slots, and gets scored against that slot's word.
+//
+// (The real Squardle scores with arrows that point along rows and columns. This uses plain
+// Wordle scores for the slot a guess went in, which is what most of the copies of it do.)
+//
+// What makes it more than six games of Wordle is the crossings. Once a row is down to words
+// with an A or an E in the middle, the middle column can only be words with an A or an E in the
+// middle too, and that can cut down the column, which can cut down the other rows, and so on.
+// So a guess gets ranked by how many words it's expected to leave across the whole grid, after
+// all of that has played out, rather than just in its own slot.
+
+// Where one word goes in the grid.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Slot {
+    pub across: bool,
+
+    // Which row or column, counting from 0. Only the even ones hold words.
+    pub line: usize,
+}
+
+impl Slot {
+    // Every slot in the grid: the rows, then the columns.
+    pub fn all() -> Vec<Slot> {
+        [true, false]
+            .into_iter()
+            .flat_map(|across| {
+                (0..WORD_LENGTH)
+                    .step_by(2)
+                    .map(move |line| Slot { across, line })
+            })
+            .collect()
+    }
+
+    // "row 1", "column 3", and so on, counting from 1 the way people do.
+    pub fn name(&self) -> String {
+        format!(
+            "{} {}",
+            if self.across { "row" } else { "column" },
+            self.line + 1
+        )
+    }
+
+    // Read a slot back from its name. "r1", "c3", and "col 3" work too.
+    pub fn parse(text: &str) -> Option<Slot> {
+        let text = text.trim().to_lowercase();
+        let split = text.find(|c: char| c.is_ascii_digit())?;
+        let (kind, number) = text.split_at(split);
+        let across = match kind.trim() {
+            "r" | "row" => true,
+            "c" | "col" | "column" => false,
+            _ => return None,
+        };
+        let line = number.trim().parse::<usize>().ok()?.checked_sub(1)?;
+        Slot::all()
+            .into_iter()
+            .find(|slot| slot.across == across && slot.line == line)
+    }
+
+    // The row and column of the letter at some place in the slot's word.
+    pub fn cell(&self, position: usize) -> (usize, usize) {
+        if self.across {
+            (self.line, position)
+        } else {
+            (position, self.line)
+        }
+    }
+
+    // If another slot crosses this one, the place in this word and the place in the other word
+    // where they share a letter.
+    fn crossing(&self, other: &Slot) -> Option<(usize, usize)> {
+        (self.across != other.across).then_some((other.line, self.line))
+    }
+}
+
+// How a guess in one slot does across the whole grid.
+pub struct CrosswordQuality {
+    pub slot: usize,
+    pub guess: &'static str,
+
+    // The number of words left in every slot put together, on average, once the crossings
+    // have been taken into account.
+    pub expected_total_remaining: f64,
+}
+
+// Only this many of the best guesses for each slot on its own get the full treatment, since
+// working out the crossings for every guess would take far too long.
+const TOP_GUESSES_PER_SLOT: usize = 8;
+
+// The state of a crossword game: the words that could still go in each slot.
+#[derive(Clone)]
+pub struct Crossword {
+    guesses: Vec<&'static str>,
+    slots: Vec<Slot>,
+    candidates: Vec<Vec<&'static str>>,
+    duplicates: DuplicatePolicy,
+}
+
+impl Crossword {
+    // The crossings are worked out a byte at a time, so only plain English words are used.
+    pub fn new(target_list: TargetList, lists: WordLists) -> Crossword {
+        let solver = Solver::with_word_lists(target_list, false, lists);
+        let english = |words: &[&'static str]| {
+            words
+                .iter()
+                .copied()
+                .filter(|w| w.is_ascii())
+                .collect::<Vec<_>>()
+        };
+        let slots = Slot::all();
+        Crossword {
+            guesses: english(solver.valid_guesses()),
+            candidates: vec![english(solver.remaining_targets()); slots.len()],
+            slots,
+            duplicates: DuplicatePolicy::Standard,
+        }
+    }
+
+    // Score the grid by some other rule for repeated letters, the same as
+    // Solver::set_duplicate_policy. This has to be set before any guesses are played.
+    pub fn set_duplicate_policy(&mut self, policy: DuplicatePolicy) {
+        self.duplicates = policy;
+    }
+
+    pub fn slots(&self) -> &[Slot] {
+        &self.slots
+    }
+
+    // The words that could still go in a slot.
+    pub fn candidates(&self, slot: usize) -> &[&'static str] {
+        &self.candidates[slot]
+    }
+
+    pub fn is_solved(&self, slot: usize) -> bool {
+        self.candidates[slot].len() == 1
+    }
+
+    pub fn all_solved(&self) -> bool {
+        (0..self.slots.len()).all(|slot| self.is_solved(slot))
+    }
+
+    // The letter in a cell of the grid, if it's known for certain. Cells where no word goes are
+    // never known.
+    pub fn letter_at(&self, row: usize, column: usize) -> Option<char> {
+        let mut letters = self
+            .slots
+            .iter()
+            .zip(&self.candidates)
+            .filter_map(|(slot, words)| {
+                let position = (0..WORD_LENGTH).find(|&i| slot.cell(i) == (row, column))?;
+                let first = words.first()?.as_bytes()[position];
+                words
+                    .iter()
+                    .all(|w| w.as_bytes()[position] == first)
+                    .then_some(first as char)
+            });
+        letters.next()
+    }
+
+    // Put a guess in a slot, with the score it got there. If that leaves nothing for some slot,
+    // nothing changes, and the error says so.
+    pub fn apply_guess(&mut self, slot: usize, guess: &str, score: WordScore) -> Result<()> {
+        if let Some(reason) = score_impossibility(guess, score, self.duplicates) {
+            return Err(Error::InconsistentScore(
+                guess.to_string(),
+                format_score(score).to_string(),
+                reason,
+            ));
+        }
+        let mut candidates = self.candidates.clone();
+        candidates[slot].retain(|w| self.duplicates.score(guess, w) == score);
+        if !propagate(&self.slots, &mut candidates, slot) {
+            return Err(Error::EmptyCandidateSet(
+                guess.to_string(),
+                format_score(score).to_string(),
+            ));
+        }
+        self.candidates = candidates;
+        self.guesses.retain(|w| *w != guess);
+        Ok(())
+    }
+
+    // The most promising guesses across the grid, best first. Each slot's best few guesses on
+    // their own are tried out against every score they could get, with the crossings worked
+    // through, and ranked by how many words are left in the whole grid on average.
+    pub fn rank_guesses(&self) -> Vec<CrosswordQuality> {
+        let mut ranked = Vec::new();
+        for slot in 0..self.slots.len() {
+            if self.is_solved(slot) {
+                continue;
+            }
+            // At the start, every slot is the same, so there's no need to do them all.
+            if (0..slot).any(|earlier| self.candidates[earlier] == self.candidates[slot]) {
+                continue;
+            }
+            let targets = &self.candidates[slot];
+            for q in rank_guesses(&self.guesses, targets, self.duplicates)
+                .into_iter()
+                .take(TOP_GUESSES_PER_SLOT)
+            {
+                ranked.push(CrosswordQuality {
+                    slot,
+                    guess: q.guess,
+                    expected_total_remaining: self.expected_total_after(slot, q.guess),
+                });
+            }
+        }
+        ranked.sort_by(|a, b| {
+            a.expected_total_remaining
+                .total_cmp(&b.expected_total_remaining)
+                .then_with(|| a.guess.cmp(b.guess))
+        });
+        ranked
+    }
+
+    // The total number of words left in the grid, on average, after playing a guess in a slot.
+    fn expected_total_after(&self, slot: usize, guess: &str) -> f64 {
+        let targets = &self.candidates[slot];
+        let mut by_score: BTreeMap<WordScore, Vec<&'static str>> = BTreeMap::new();
+        for &target in targets {
+            by_score
+                .entry(self.duplicates.score(guess, target))
+                .or_default()
+                .push(target);
+        }
+
+        let mut total = 0.0;
+        for (_, words) in by_score {
+            let chance = words.len() as f64 / targets.len() as f64;
+            let mut candidates = self.candidates.clone();
+            candidates[slot] = words;
+            propagate(&self.slots, &mut candidates, slot);
+            total += chance * candidates.iter().map(Vec::len).sum::<usize>() as f64;
+        }
+        total
+    }
+}
+
+// Narrow down the slots that cross `changed`, and the ones that cross those, and so on, until
+// every word left in every slot has some word it could cross in every crossing slot. Returns
+// false if some slot runs out of words.
+fn propagate(slots: &[Slot], candidates: &mut [Vec<&'static str>], changed: usize) -> bool {
+    let mut to_check = vec![changed];
+    while let Some(from) = to_check.pop() {
+        if candidates[from].is_empty() {
+            return false;
+        }
+        for to in 0..slots.len() {
+            let (from_place, to_place) = match slots[from].crossing(&slots[to]) {
+                Some(places) => places,
+                None => continue,
+            };
+            let mut letters = [false; 256];
+            for w in &candidates[from] {
+                letters[w.as_bytes()[from_place] as usize] = true;
+            }
+            let before = candidates[to].len();
+            candidates[to].retain(|w| letters[w.as_bytes()[to_place] as usize]);
+            if candidates[to].len() < before && !to_check.contains(&to) {
+                to_check.push(to);
+            }
+        }
+    }
+    true
+}
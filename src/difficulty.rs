@@ -0,0 +1,155 @@
+use crate::score::*;
+use crate::simulate::*;
+use crate::solver::*;
+use crate::strategy::*;
+use std::collections::HashMap;
+
+// How hard each possible answer is to find, for people running their own Wordle and picking
+// the secret words. Three things make a word hard:
+//
+// - Good strategies take a lot of guesses to find it. This is the main one, so it counts
+//   double.
+// - It's in a trap: a group of words that are the same but for one letter, like the -IGHT words
+//   or BAKER/FAKER/MAKER/WAKER. Strategies get through those eventually, but people guessing one
+//   word at a time run out of guesses.
+// - It's rare. Nobody guesses a word they've never heard of.
+//
+// Each of those is turned into a percentile among all the answers, so that they're on the same
+// scale, and the difficulty is their weighted average, from 0 for the easiest word to 100 for
+// the hardest.
+
+// How much each part counts towards the difficulty.
+const GUESSES_WEIGHT: f64 = 2.0;
+const TRAP_WEIGHT: f64 = 1.0;
+const RARITY_WEIGHT: f64 = 1.0;
+
+#[derive(Clone, Debug)]
+pub struct AnswerDifficulty {
+    pub word: &'static str,
+
+    // The average number of guesses the strategies took, and the most any of them took.
+    pub average_guesses: f64,
+    pub worst_guesses: usize,
+
+    // How many answers are in the biggest group this word is in of words that are the same but
+    // for one letter, counting itself. 1 means it's not in a trap at all.
+    pub trap_size: usize,
+
+    // Where the word comes in the answers from most common to least, starting at 1.
+    pub commonness_rank: usize,
+
+    // The overall difficulty, 0 to 100.
+    pub difficulty: f64,
+}
+
+// Rate every possible answer, by playing each of the strategies against all of them. They come
+// back hardest first.
+pub fn rate_answers(solver: &Solver, strategies: &[Box<dyn Strategy>]) -> Vec<AnswerDifficulty> {
+    let answers = solver.remaining_targets();
+
+    let mut total_guesses = vec![0usize; answers.len()];
+    let mut worst_guesses = vec![0usize; answers.len()];
+    for strategy in strategies {
+        // The games come back in the same order as the answers.
+        for (i, game) in simulate(solver, strategy.as_ref(), Some(0))
+            .iter()
+            .enumerate()
+        {
+            total_guesses[i] += game.guesses.len();
+            worst_guesses[i] = worst_guesses[i].max(game.guesses.len());
+        }
+    }
+    let average_guesses = total_guesses
+        .iter()
+        .map(|&total| total as f64 / strategies.len().max(1) as f64)
+        .collect::<Vec<_>>();
+
+    let trap_sizes = trap_sizes(answers);
+
+    let commonness: HashMap<&str, usize> = solver
+        .answer_chances()
+        .iter()
+        .enumerate()
+        .map(|(rank, &(word, _))| (word, rank + 1))
+        .collect();
+    let commonness_ranks = answers
+        .iter()
+        .map(|word| commonness[word])
+        .collect::<Vec<_>>();
+
+    let guesses_percentiles = percentiles(&average_guesses);
+    let trap_percentiles = percentiles(
+        &trap_sizes
+            .iter()
+            .map(|&size| size as f64)
+            .collect::<Vec<_>>(),
+    );
+    let rarity_percentiles = percentiles(
+        &commonness_ranks
+            .iter()
+            .map(|&rank| rank as f64)
+            .collect::<Vec<_>>(),
+    );
+
+    let mut ratings = (0..answers.len())
+        .map(|i| AnswerDifficulty {
+            word: answers[i],
+            average_guesses: average_guesses[i],
+            worst_guesses: worst_guesses[i],
+            trap_size: trap_sizes[i],
+            commonness_rank: commonness_ranks[i],
+            difficulty: (guesses_percentiles[i] * GUESSES_WEIGHT
+                + trap_percentiles[i] * TRAP_WEIGHT
+                + rarity_percentiles[i] * RARITY_WEIGHT)
+                / (GUESSES_WEIGHT + TRAP_WEIGHT + RARITY_WEIGHT),
+        })
+        .collect::<Vec<_>>();
+    ratings.sort_by(|a, b| {
+        b.difficulty
+            .total_cmp(&a.difficulty)
+            .then_with(|| a.word.cmp(b.word))
+    });
+    ratings
+}
+
+// For each word, the size of the biggest group of words it's in that only differ in one place.
+fn trap_sizes(words: &[&'static str]) -> Vec<usize> {
+    // Every word goes in five groups, one for each place it could differ in, keyed by the place
+    // and the word with that place blanked out.
+    let blanked = |word: &str, place: usize| {
+        let mut letters = word.chars().collect::<Vec<_>>();
+        letters[place] = '_';
+        (place, letters)
+    };
+    let mut groups: HashMap<_, usize> = HashMap::new();
+    for word in words {
+        for place in 0..WORD_LENGTH {
+            *groups.entry(blanked(word, place)).or_default() += 1;
+        }
+    }
+    words
+        .iter()
+        .map(|word| {
+            (0..WORD_LENGTH)
+                .map(|place| groups[&blanked(word, place)])
+                .max()
+                .unwrap_or(1)
+        })
+        .collect()
+}
+
+// Where each value comes among all of them, from 0 to 100. Ties all get the same percentile,
+// halfway through the places they share, so a value that every word has doesn't count for or
+// against any of them.
+fn percentiles(values: &[f64]) -> Vec<f64> {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f64::total_cmp);
+    values
+        .iter()
+        .map(|value| {
+            let below = sorted.partition_point(|v| v < value);
+            let equal = sorted[below..].partition_point(|v| v <= value);
+            (below as f64 + equal as f64 / 2.0) * 100.0 / values.len() as f64
+        })
+        .collect()
+}
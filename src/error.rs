@@ -0,0 +1,60 @@
+use crate::score::WORD_LENGTH;
+use thiserror::Error;
+
+// Everything that can go wrong in the library, so that programs using it can decide for
+// themselves what to do about it, rather than having the library panic or print.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("'{0}' is not exactly {len} letters", len = WORD_LENGTH)]
+    InvalidWordLength(String),
+
+    #[error("'{0}' isn't in the word list")]
+    WordNotInDictionary(String),
+
+    #[error("'{0}' is not a valid score; scores are {len} characters of '.', 'y', and 'G', like \".y.GG\"", len = WORD_LENGTH)]
+    InvalidScore(String),
+
+    #[error("'{0}' is not a valid score; Nerdle scores are {len} characters of '.', 'y', and 'G', like \"..y.G.GG\"", len = crate::nerdle::NERDLE_LENGTH)]
+    InvalidEquationScore(String),
+
+    #[error("'{0}' is not a valid score; Wordle Peaks scores are {len} characters of '<' (earlier in the alphabet), '>' (later), and 'G', like \"<>G<<\"", len = WORD_LENGTH)]
+    InvalidPeaksScore(String),
+
+    #[error("'{0}' is not a valid score: letter {2}, '{1}', should be '.' (gray), 'y' (yellow), or 'G' (green)")]
+    InvalidScoreLetter(String, char, usize),
+
+    #[error("no word could score {1} for '{0}': {2}")]
+    InconsistentScore(String, String, String),
+
+    #[error("none of the possible answers would score {1} for '{0}'")]
+    EmptyCandidateSet(String, String),
+
+    #[error("expected {0} scores, one for each board that isn't solved yet, but got {1}")]
+    WrongNumberOfScores(usize, usize),
+
+    #[error("unknown strategy '{0}'; expected one of: {1}")]
+    UnknownStrategy(String, String),
+
+    #[error("invalid filter: {0}")]
+    InvalidFilter(String),
+
+    #[error("invalid policy: {0}")]
+    InvalidPolicy(String),
+
+    #[error("invalid game code: {0}")]
+    InvalidStateCode(String),
+
+    #[error("can't resume: {0}")]
+    Checkpoint(String),
+
+    #[error("{0}")]
+    Config(String),
+
+    #[error("{0}")]
+    Network(String),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
@@ -0,0 +1,229 @@
+use crate::score::*;
+use crate::solver::*;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+// The simulator and policies say how well some particular way of playing does. This works out
+// how well the best possible way of playing does: the fewest guesses on average, with perfect
+// play after a given opening word. That's the number the famous "SALET takes 3.4212 guesses"
+// tables are made of.
+//
+// The search goes like this. To find every answer in a group of possible answers, some guess
+// has to be played next, which takes one guess for each answer in the group. Then the answers
+// split up by the score they'd give that guess, and each of those smaller groups needs the best
+// play to find every answer in it, and so on down. The best total for the group is the least of
+// those totals over every guess there is. That's a lot of guesses to try, so it's cut down with
+// a lower bound: every answer in a group of N needs at least two guesses, except for one that
+// could be guessed right away, so no guess can do better than 2N - 1. Guesses are tried in
+// order of how good their bound is, and once the bound of the next one is no better than the
+// best found so far, none of the rest can win, and the search stops there. Groups that have
+// been worked out before are remembered.
+//
+// It's still slow -- up to a minute or so for a good opener on the full lists, and longer for
+// bad ones, where there's less to cut off -- so there's the option of only trying the most
+// promising few guesses at each step. That's much faster, but then the results are only the
+// best that those guesses can do, not the best of all.
+
+// How few guesses perfect play takes after one opening word.
+#[derive(Clone, Copy, Debug)]
+pub struct OpenerValue {
+    pub opener: &'static str,
+
+    // The total number of guesses to find every answer, counting the opener.
+    pub total_guesses: u32,
+
+    pub num_answers: usize,
+}
+
+impl OpenerValue {
+    pub fn average_guesses(&self) -> f64 {
+        self.total_guesses as f64 / self.num_answers.max(1) as f64
+    }
+}
+
+// Everything the search needs about a game's words, set up once and shared by every opener.
+pub struct ExactSearch {
+    guesses: Vec<&'static str>,
+    num_answers: usize,
+
+    // Every guess's score against every answer, a row per guess.
+    scores: Vec<WordScore>,
+
+    // For each answer, its row, if it's one of the guesses. These are the only guesses that can
+    // win right away.
+    answer_rows: Vec<Option<usize>>,
+
+    // Only try this many guesses at each step after the opener, if there's a limit.
+    breadth: Option<usize>,
+}
+
+// The fewest guesses it could possibly take to find every one of this many answers.
+fn lower_bound(num_answers: usize) -> u32 {
+    (2 * num_answers as u32).saturating_sub(1)
+}
+
+impl ExactSearch {
+    // Get ready to search from where the solver is now. With a breadth, only that many of the
+    // most promising guesses are tried at each step after the opener.
+    pub fn new(solver: &Solver, breadth: Option<usize>) -> ExactSearch {
+        let guesses = solver.valid_guesses().to_vec();
+        let answers = solver.remaining_targets();
+        let rows: HashMap<&str, usize> = guesses
+            .iter()
+            .enumerate()
+            .map(|(row, &guess)| (guess, row))
+            .collect();
+
+        let mut scores = vec![0; guesses.len() * answers.len()];
+        scores
+            .par_chunks_mut(answers.len().max(1))
+            .zip(guesses.par_iter())
+            .for_each(|(row, &guess)| {
+                for (score, answer) in row.iter_mut().zip(answers) {
+                    *score = solver.score(guess, answer);
+                }
+            });
+
+        ExactSearch {
+            num_answers: answers.len(),
+            scores,
+            answer_rows: answers.iter().map(|a| rows.get(a).copied()).collect(),
+            guesses,
+            breadth,
+        }
+    }
+
+    pub fn guesses(&self) -> &[&'static str] {
+        &self.guesses
+    }
+
+    fn score(&self, row: usize, answer: u16) -> WordScore {
+        self.scores[row * self.num_answers + answer as usize]
+    }
+
+    // The value of opening with a word, or None if it isn't one of the guesses. This is
+    // independent for each opener, so openers can be worked out in parallel.
+    pub fn opener_value(&self, opener: &str) -> Option<OpenerValue> {
+        let row = self.guesses.iter().position(|&g| g == opener)?;
+        let answers = (0..self.num_answers as u16).collect::<Vec<_>>();
+        let mut memo = HashMap::new();
+        Some(OpenerValue {
+            opener: self.guesses[row],
+            total_guesses: self.total_after(row, &answers, u32::MAX, &mut memo),
+            num_answers: self.num_answers,
+        })
+    }
+
+    // The fewest guesses to find every answer in a group, starting with the next guess. If
+    // that's `beta` or more, all that's promised is that the result is at least `beta`, which
+    // is all the caller needs to know to rule out the guess it's trying.
+    fn best_total(&self, answers: &[u16], beta: u32, memo: &mut HashMap<Vec<u16>, u32>) -> u32 {
+        let n = answers.len();
+        match n {
+            1 => return 1,
+            2 => return 3,
+            _ => {}
+        }
+        if let Some(&total) = memo.get(answers) {
+            return total;
+        }
+        let best_possible = lower_bound(n);
+        if beta <= best_possible {
+            return beta;
+        }
+
+        // An answer whose score splits up all of the others is as good as it gets.
+        let mut histogram = [0u16; NUM_SCORES];
+        for row in answers.iter().filter_map(|&a| self.answer_rows[a as usize]) {
+            for &a in answers {
+                histogram[self.score(row, a) as usize] += 1;
+            }
+            let splits_all = answers
+                .iter()
+                .all(|&a| histogram[self.score(row, a) as usize] == 1);
+            for &a in answers {
+                histogram[self.score(row, a) as usize] = 0;
+            }
+            if splits_all {
+                memo.insert(answers.to_vec(), best_possible);
+                return best_possible;
+            }
+        }
+
+        // The bound for each guess: the guess itself, then 2N - 1 for each group it leaves.
+        // Guesses that don't split the group up at all are no use.
+        let mut candidates = Vec::new();
+        for row in 0..self.guesses.len() {
+            let mut bound = n as u32;
+            let mut useless = false;
+            for &a in answers {
+                let score = self.score(row, a);
+                histogram[score as usize] += 1;
+                match histogram[score as usize] {
+                    1 if score != ALL_GREEN => bound += 1,
+                    count if count as usize == n => useless = true,
+                    count if count > 1 && score != ALL_GREEN => bound += 2,
+                    _ => {}
+                }
+            }
+            for &a in answers {
+                histogram[self.score(row, a) as usize] = 0;
+            }
+            if !useless {
+                candidates.push((bound, row));
+            }
+        }
+        candidates.sort_unstable();
+        if let Some(breadth) = self.breadth {
+            candidates.truncate(breadth.max(1));
+        }
+
+        let mut best = beta;
+        for (bound, row) in candidates {
+            if bound >= best {
+                break;
+            }
+            best = best.min(self.total_after(row, answers, best, memo));
+            if best == best_possible {
+                break;
+            }
+        }
+
+        if best < beta {
+            memo.insert(answers.to_vec(), best);
+        }
+        best
+    }
+
+    // The fewest guesses to find every answer in a group by playing this guess next, with
+    // perfect play after it. The same as best_total, this only promises that the result is at
+    // least `beta` if it's that much or more.
+    fn total_after(
+        &self,
+        row: usize,
+        answers: &[u16],
+        beta: u32,
+        memo: &mut HashMap<Vec<u16>, u32>,
+    ) -> u32 {
+        let mut groups: Vec<Vec<u16>> = vec![Vec::new(); NUM_SCORES];
+        for &a in answers {
+            groups[self.score(row, a) as usize].push(a);
+        }
+        groups.truncate(ALL_GREEN as usize);
+        groups.retain(|group| !group.is_empty());
+
+        // The biggest groups go first, since they're the most likely to show that this guess
+        // can't win, and then the rest don't need working out.
+        groups.sort_unstable_by_key(|group| std::cmp::Reverse(group.len()));
+        let mut total = answers.len() as u32;
+        let mut rest: u32 = groups.iter().map(|group| lower_bound(group.len())).sum();
+        for group in &groups {
+            if total.saturating_add(rest) >= beta {
+                return beta;
+            }
+            rest -= lower_bound(group.len());
+            total += self.best_total(group, beta - total - rest, memo);
+        }
+        total.min(beta)
+    }
+}
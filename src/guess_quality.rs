@@ -0,0 +1,566 @@
+use crate::score::*;
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+// While WordScore represents how a guessed word compares to a single target word,
+// GuessQuality represents how a guessed word compares against an entire list of
+// possible targets.
+//
+// The basic logic is to take a list of candidate guess words, then generate a GuessQuality
+// for each of them against the list of candidate solution words, and then sort/select
+// among the GuessQualities to suggest good guesses.
+#[derive(Clone, Copy)]
+pub struct GuessQuality<'a> {
+    pub has_winning: bool,
+    pub expected_remaining: f64,
+    pub max_remaining: u16,
+    pub score_with_max_remaining: u8,
+
+    // How much the score is expected to tell you, in bits. Each bit halves the number of
+    // possible words, on average.
+    pub entropy: f64,
+
+    // How many of the possible answers this guess would single out, so that you'd know the
+    // answer for certain (or already have it). Divided by the number of possible answers, that's
+    // the chance of winning by the next turn.
+    pub unique_answers: u16,
+
+    // How many different scores the guess can get against the possible answers, which is how
+    // many groups it splits them up into.
+    pub partitions: u16,
+
+    // The chance that this guess is the answer itself: one over the number of possible answers
+    // if it's one of them (or how likely that word is, when the answers are weighted), and 0 if
+    // it isn't.
+    pub win_chance: f64,
+
+    pub guess: &'a str,
+}
+
+// A guess's quality, along with the histogram it came from: how many of the possible answers
+// get each score. Rankings don't keep these, since there are thousands of guesses and a
+// histogram is several times the size of everything else put together, but for looking closely
+// at one guess it saves scoring it all over again.
+#[derive(Clone, Copy)]
+pub struct GuessDetails<'a> {
+    pub quality: GuessQuality<'a>,
+    pub histogram: [u16; NUM_SCORES],
+}
+
+// Score a single candidate guess word against the list of remaining words, by the given rule for
+// repeated letters.
+pub fn estimate_guess_quality<'a>(
+    guess: &'a str,
+    targets: &[&str],
+    policy: DuplicatePolicy,
+) -> GuessQuality<'a> {
+    estimate_guess_quality_with(guess, &PreparedTargets::new(targets, policy), None)
+}
+
+// The same, with the histogram as well.
+pub fn guess_details<'a>(
+    guess: &'a str,
+    targets: &[&str],
+    policy: DuplicatePolicy,
+) -> GuessDetails<'a> {
+    guess_details_with(guess, targets, None, policy)
+}
+
+// With weights for how likely each target is, in the same order as the targets (see
+// quality_from_scores). The histogram still counts words, however likely they are.
+pub(crate) fn guess_details_with<'a>(
+    guess: &'a str,
+    targets: &[&str],
+    weights: Option<&[f64]>,
+    policy: DuplicatePolicy,
+) -> GuessDetails<'a> {
+    let mut histogram = [0u16; NUM_SCORES];
+    for target in targets {
+        histogram[policy.score(guess, target) as usize] += 1;
+    }
+    let quality = match weights {
+        None => quality_from_histogram(guess, &histogram, targets.len()),
+        Some(_) => quality_from_scores(
+            guess,
+            targets.iter().map(|target| policy.score(guess, target)),
+            targets.len(),
+            weights,
+        ),
+    };
+    GuessDetails { quality, histogram }
+}
+
+// The targets, converted once up front for scoring against lots of guesses. English words get
+// turned into TargetBlocks, which takes the length checks out of the innermost loop entirely,
+// and lays them out to be scored lots at a time. Lists with other letters in them are kept as
+// they are, and scored the slow way. Either way, they remember the rule they're scored by.
+enum PreparedTargets<'t> {
+    Blocks(TargetBlocks),
+    Words(&'t [&'t str], DuplicatePolicy),
+}
+
+impl<'t> PreparedTargets<'t> {
+    fn new(targets: &'t [&'t str], policy: DuplicatePolicy) -> PreparedTargets<'t> {
+        if targets.iter().all(|t| t.is_ascii()) {
+            PreparedTargets::Blocks(TargetBlocks::new(
+                &targets.iter().map(|t| *word_bytes(t)).collect::<Vec<_>>(),
+                policy,
+            ))
+        } else {
+            PreparedTargets::Words(targets, policy)
+        }
+    }
+}
+
+// The targets' weights, if there are any, are how likely each one is to be the answer, in the
+// same order as the targets.
+fn estimate_guess_quality_with<'a>(
+    guess: &'a str,
+    targets: &PreparedTargets,
+    weights: Option<&[f64]>,
+) -> GuessQuality<'a> {
+    match targets {
+        PreparedTargets::Blocks(targets) if guess.is_ascii() => {
+            estimate_guess_quality_blocks(guess, targets, weights)
+        }
+        PreparedTargets::Blocks(targets) => {
+            // A non-English guess against English targets. It can't match any of the odd
+            // letters, but it still has to be scored.
+            let scores = targets.words().map(|target| {
+                targets
+                    .policy()
+                    .score(guess, std::str::from_utf8(&target).unwrap_or_default())
+            });
+            quality_from_scores(guess, scores, targets.len(), weights)
+        }
+        PreparedTargets::Words(targets, policy) => {
+            let scores = targets.iter().map(|target| policy.score(guess, target));
+            quality_from_scores(guess, scores, targets.len(), weights)
+        }
+    }
+}
+
+// The same as estimate_guess_quality, with the targets already converted to TargetBlocks. This
+// is the innermost loop of the ranking, and it doesn't allocate anything: the scores and the
+// histogram live on the stack.
+fn estimate_guess_quality_blocks<'a>(
+    guess: &'a str,
+    targets: &TargetBlocks,
+    weights: Option<&[f64]>,
+) -> GuessQuality<'a> {
+    let guess_bytes = word_bytes(guess);
+    if weights.is_some() {
+        let scores = targets.scores(guess_bytes);
+        return quality_from_scores(guess, scores, targets.len(), weights);
+    }
+
+    let mut histogram: [u16; NUM_SCORES] = [0u16; NUM_SCORES];
+    targets.add_to_histogram(guess_bytes, &mut histogram);
+    quality_from_histogram(guess, &histogram, targets.len())
+}
+
+// The quality of a guess from the score it gets against each target, in the same order as the
+// targets (and their weights).
+//
+// With weights, the average number of words left is worked out from how likely the words in
+// each group are, rather than how many of them there are. Ten words nobody would pick as an
+// answer aren't as bad a group to be left with as three everyday ones, since if the score
+// comes out that way, the answer is almost certainly one of the three. The average is the
+// chance of each group, times the chance of that group again (which is how likely you are to
+// end up in it), summed up -- that's the same as the usual average when every word is equally
+// likely, once it's multiplied back up by the number of words.
+pub(crate) fn quality_from_scores<'a>(
+    guess: &'a str,
+    scores: impl Iterator<Item = WordScore>,
+    num_targets: usize,
+    weights: Option<&[f64]>,
+) -> GuessQuality<'a> {
+    let mut histogram = [0u16; NUM_SCORES];
+    let Some(weights) = weights else {
+        for score in scores {
+            histogram[score as usize] += 1;
+        }
+        return quality_from_histogram(guess, &histogram, num_targets);
+    };
+
+    let mut chances = [0f64; NUM_SCORES];
+    for (score, weight) in scores.zip(weights) {
+        histogram[score as usize] += 1;
+        chances[score as usize] += weight;
+    }
+    let total: f64 = chances.iter().sum();
+    let expected: f64 = chances.iter().map(|c| c * c).sum();
+    GuessQuality {
+        expected_remaining: if total > 0.0 {
+            expected / (total * total) * num_targets as f64
+        } else {
+            0.0
+        },
+        win_chance: if total > 0.0 {
+            chances[ALL_GREEN as usize] / total
+        } else {
+            0.0
+        },
+        ..quality_from_histogram(guess, &histogram, num_targets)
+    }
+}
+
+// Boil down how many targets got each score into the numbers that describe a guess.
+fn quality_from_histogram<'a>(
+    guess: &'a str,
+    histogram: &[u16; NUM_SCORES],
+    num_targets: usize,
+) -> GuessQuality<'a> {
+    histogram_quality(guess, histogram, num_targets).0
+}
+
+// The same, for a histogram of any number of scores, as long as the last one is all green.
+// That's for games with longer words than Wordle, like Nerdle, whose scores don't fit in a
+// WordScore. So the score with the most targets is handed back separately, and it's only filled
+// in on the GuessQuality if it fits.
+pub(crate) fn histogram_quality<'a>(
+    guess: &'a str,
+    histogram: &[u16],
+    num_targets: usize,
+) -> (GuessQuality<'a>, usize) {
+    let mut max_with_score = 0u16;
+    let mut score_with_max = 0usize;
+    let mut expected = 0u64;
+    let mut entropy = 0f64;
+    let mut unique_answers = 0u16;
+    let mut partitions = 0u16;
+    let total = num_targets as f64;
+    for (score, &num_with_score) in histogram.iter().enumerate() {
+        if num_with_score > max_with_score {
+            max_with_score = num_with_score;
+            score_with_max = score;
+        }
+        expected += num_with_score as u64 * num_with_score as u64;
+        if num_with_score > 0 {
+            let p = num_with_score as f64 / total;
+            entropy -= p * p.log2();
+            partitions += 1;
+        }
+        if num_with_score == 1 {
+            unique_answers += 1;
+        }
+    }
+
+    let quality = GuessQuality {
+        has_winning: histogram.last().is_some_and(|&n| n > 0),
+        expected_remaining: expected as f64 / num_targets as f64,
+        max_remaining: max_with_score,
+        score_with_max_remaining: WordScore::try_from(score_with_max).unwrap_or_default(),
+        entropy,
+        unique_answers,
+        partitions,
+        win_chance: histogram.last().map_or(0.0, |&n| n as f64 / total),
+        guess,
+    };
+    (quality, score_with_max)
+}
+
+// The number the suggestions are sorted on first, smaller being better. Primary sort works best
+// when we multiply these together.
+pub fn quality_product(q: &GuessQuality) -> f64 {
+    q.max_remaining as f64 * q.expected_remaining
+}
+
+// The order the guesses get suggested in: best first.
+pub fn compare_guess_quality(a: &GuessQuality, b: &GuessQuality) -> Ordering {
+    // Break ties alphabetically.
+    compare_quality_only(a, b).then_with(|| a.guess.cmp(b.guess))
+}
+
+// The same order, except that guesses that are equally good are put in order of how easy they
+// are to type, rather than alphabetically. Nobody needs this, but it's nice.
+pub fn compare_guess_quality_easy_typing(a: &GuessQuality, b: &GuessQuality) -> Ordering {
+    compare_quality_only(a, b)
+        .then_with(|| typing_effort(a.guess).cmp(&typing_effort(b.guess)))
+        .then_with(|| a.guess.cmp(b.guess))
+}
+
+// A rough count of how awkward a word is to type on a QWERTY keyboard. Letters on the home row
+// are free, the top row costs a little, and the bottom row costs more. Typing two letters in a
+// row with the same hand costs extra too, since alternating hands is faster.
+pub fn typing_effort(word: &str) -> u32 {
+    const TOP_ROW: &str = "qwertyuiop";
+    const BOTTOM_ROW: &str = "zxcvbnm";
+    const LEFT_HAND: &str = "qwertasdfgzxcvb";
+
+    let mut effort = 0;
+    let mut last_hand = None;
+    for letter in word.chars() {
+        if TOP_ROW.contains(letter) {
+            effort += 1;
+        } else if BOTTOM_ROW.contains(letter) {
+            effort += 2;
+        }
+
+        let hand = LEFT_HAND.contains(letter);
+        if last_hand == Some(hand) {
+            effort += 1;
+        }
+        last_hand = Some(hand);
+    }
+    effort
+}
+
+// Everything about the order except the tie-break between words that are equally good.
+fn compare_quality_only(a: &GuessQuality, b: &GuessQuality) -> Ordering {
+    let o = quality_product(a).partial_cmp(&quality_product(b));
+    if matches!(o, Some(Ordering::Greater | Ordering::Less)) {
+        return o.unwrap();
+    }
+
+    // Break ties by favoring things that might win!
+    let o = b.has_winning.cmp(&a.has_winning);
+    if matches!(o, Ordering::Greater | Ordering::Less) {
+        return o;
+    }
+
+    // Break ties by favoring things that are guaranteed to cull the most.
+    let o = a.max_remaining.cmp(&b.max_remaining);
+    if matches!(o, Ordering::Greater | Ordering::Less) {
+        return o;
+    }
+
+    // Break ties by favoring things that will cull the most on average.
+    a.expected_remaining
+        .partial_cmp(&b.expected_remaining)
+        .unwrap_or(Ordering::Equal)
+}
+
+// How a guess splits up the targets, in a form where two guesses that split them up exactly the
+// same way are equal, even if the scores themselves are different. Each target gets the number
+// of its group, with the groups numbered in the order they first turn up. Guesses like that are
+// interchangeable: whichever one you play, you'll know exactly as much afterwards.
+pub fn partition_signature(guess: &str, targets: &[&str], policy: DuplicatePolicy) -> Vec<u8> {
+    let mut group_of_score = [u8::MAX; NUM_SCORES];
+    let mut num_groups = 0u8;
+    targets
+        .iter()
+        .map(|target| {
+            let group = &mut group_of_score[policy.score(guess, target) as usize];
+            if *group == u8::MAX {
+                *group = num_groups;
+                num_groups += 1;
+            }
+            *group
+        })
+        .collect()
+}
+
+// The different ideas of what makes a guess the best one. The suggestions are normally sorted by
+// Product, but each of these has its fans.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Metric {
+    // The average number of words left times the worst case. See compare_guess_quality.
+    Product,
+
+    // The most information on average, in bits.
+    Entropy,
+
+    // The fewest words left if the score comes out as badly as it possibly can.
+    WorstCase,
+
+    // The best chance of winning by the next turn: either this guess is the answer, or its score
+    // leaves only one possibility.
+    WinChance,
+}
+
+pub const ALL_METRICS: [Metric; 4] = [
+    Metric::Product,
+    Metric::Entropy,
+    Metric::WorstCase,
+    Metric::WinChance,
+];
+
+impl Metric {
+    pub fn name(self) -> &'static str {
+        match self {
+            Metric::Product => "product",
+            Metric::Entropy => "entropy",
+            Metric::WorstCase => "worst case",
+            Metric::WinChance => "win chance",
+        }
+    }
+
+    // Order guesses by this metric, best first. Ties fall back to the usual ordering.
+    pub fn compare(self, a: &GuessQuality, b: &GuessQuality) -> Ordering {
+        let o = match self {
+            Metric::Product => Ordering::Equal,
+            Metric::Entropy => b.entropy.total_cmp(&a.entropy),
+            Metric::WorstCase => a.max_remaining.cmp(&b.max_remaining),
+            Metric::WinChance => b
+                .unique_answers
+                .cmp(&a.unique_answers)
+                .then(b.has_winning.cmp(&a.has_winning)),
+        };
+        o.then_with(|| compare_guess_quality(a, b))
+    }
+
+    // The best guess in a list by this metric.
+    pub fn best<'l, 'a>(self, list: &'l [GuessQuality<'a>]) -> Option<&'l GuessQuality<'a>> {
+        list.iter().min_by(|a, b| self.compare(a, b))
+    }
+}
+
+// Check the quality of every guess against the full set of targets, and sort the qualities
+// so the most useful guesses come first.
+pub fn rank_guesses<'a>(
+    guesses: &[&'a str],
+    targets: &[&str],
+    policy: DuplicatePolicy,
+) -> Vec<GuessQuality<'a>> {
+    let mut all_guesses_scored = score_all_guesses(guesses, targets, policy);
+    all_guesses_scored.sort_by(compare_guess_quality);
+    all_guesses_scored
+}
+
+// Check the quality of every guess, in the same order as the guesses.
+//
+// Letters that aren't in any of the targets always score gray, no matter where they are, so
+// they may as well all be the same letter. Two guesses that only differ in those letters --
+// "mount" and "fount" once there's no M or F left in any target -- get exactly the same scores
+// against every target, and so they have exactly the same quality. So guesses are grouped up by
+// what they look like with those letters blanked out, and each group only gets scored once.
+// Late in a game most of the alphabet is ruled out, and the groups get big.
+pub(crate) fn score_all_guesses<'a>(
+    guesses: &[&'a str],
+    targets: &[&str],
+    policy: DuplicatePolicy,
+) -> Vec<GuessQuality<'a>> {
+    score_all_guesses_by(guesses, targets, None, policy, |_| None)
+}
+
+// The same, but with weights for how likely each target is (see quality_from_scores), and a
+// quicker way of working out some of the guesses' qualities, like looking their scores up in a
+// ScoreTable. Guesses it doesn't have anything for get scored as usual.
+pub(crate) fn score_all_guesses_by<'a>(
+    guesses: &[&'a str],
+    targets: &[&str],
+    weights: Option<&[f64]>,
+    policy: DuplicatePolicy,
+    quicker: impl Fn(&'a str) -> Option<GuessQuality<'a>> + Sync + Send,
+) -> Vec<GuessQuality<'a>> {
+    let quality_of = |w: &'a str, prepared: &PreparedTargets| {
+        quicker(w).unwrap_or_else(|| estimate_guess_quality_with(w, prepared, weights))
+    };
+
+    // Convert the targets once up front, rather than checking their lengths again for every
+    // guess.
+    let prepared = PreparedTargets::new(targets, policy);
+
+    // Only English words get grouped, since that's what the fast scoring works on anyway.
+    let present = match &prepared {
+        PreparedTargets::Blocks(targets) => {
+            let mut present = [false; 256];
+            for target in targets.words() {
+                for c in target {
+                    present[c as usize] = true;
+                }
+            }
+            present
+        }
+        PreparedTargets::Words(..) => {
+            return guesses
+                .into_par_iter()
+                .map(|w| quality_of(w, &prepared))
+                .collect();
+        }
+    };
+
+    let mut group_of_guess = Vec::with_capacity(guesses.len());
+    let mut groups: HashMap<WordBytes, usize> = HashMap::new();
+    let mut representatives: Vec<&'a str> = Vec::new();
+    for &guess in guesses {
+        if !guess.is_ascii() {
+            group_of_guess.push(representatives.len());
+            representatives.push(guess);
+            continue;
+        }
+        let mut blanked = *word_bytes(guess);
+        for c in blanked.iter_mut() {
+            if !present[*c as usize] {
+                *c = 0;
+            }
+        }
+        let group = *groups.entry(blanked).or_insert_with(|| {
+            representatives.push(guess);
+            representatives.len() - 1
+        });
+        group_of_guess.push(group);
+    }
+
+    let qualities: Vec<GuessQuality> = representatives
+        .into_par_iter() // why is this so much faster than .par_iter()?
+        .map(|w| quality_of(w, &prepared))
+        .collect();
+
+    guesses
+        .iter()
+        .zip(group_of_guess)
+        .map(|(&guess, group)| GuessQuality {
+            guess,
+            ..qualities[group]
+        })
+        .collect()
+}
+
+// The guesses that can't be beaten on both the average and the worst case at once: for each of
+// these, every other guess is either worse on average, or worse in the worst case. Everything
+// else is dominated by one of these, so this is the whole menu of sensible trade-offs between
+// playing it safe and gambling on a good average. They come back from best average (and worst
+// worst case) to best worst case (and worst average).
+pub fn pareto_frontier<'a>(list: &[GuessQuality<'a>]) -> Vec<GuessQuality<'a>> {
+    let mut sorted = list.to_vec();
+    sorted.sort_by(|a, b| {
+        a.expected_remaining
+            .total_cmp(&b.expected_remaining)
+            .then(a.max_remaining.cmp(&b.max_remaining))
+            .then(a.guess.cmp(b.guess))
+    });
+
+    // Going from best average to worst, a guess is on the frontier if its worst case beats
+    // everything before it. Exact ties with the last one on the frontier are kept too.
+    let mut frontier: Vec<GuessQuality<'a>> = Vec::new();
+    for q in sorted {
+        let keep = match frontier.last() {
+            None => true,
+            Some(last) => {
+                q.max_remaining < last.max_remaining
+                    || (q.max_remaining == last.max_remaining
+                        && q.expected_remaining == last.expected_remaining)
+            }
+        };
+        if keep {
+            frontier.push(q);
+        }
+    }
+    frontier
+}
+
+// Just the top entry from rank_guesses, without building and sorting the whole list. This is
+// what the simulator wants for every move it makes.
+pub fn best_guess<'a>(
+    guesses: &[&'a str],
+    targets: &[&str],
+    policy: DuplicatePolicy,
+) -> Option<GuessQuality<'a>> {
+    best_guess_by(guesses, targets, policy, compare_guess_quality)
+}
+
+// The same as best_guess, but with some other idea of what "best" means. The guess that
+// compares as the least is the one returned.
+pub fn best_guess_by<'a>(
+    guesses: &[&'a str],
+    targets: &[&str],
+    policy: DuplicatePolicy,
+    compare: impl Fn(&GuessQuality, &GuessQuality) -> Ordering + Sync + Send,
+) -> Option<GuessQuality<'a>> {
+    score_all_guesses(guesses, targets, policy)
+        .into_par_iter()
+        .min_by(|a, b| compare(a, b))
+}
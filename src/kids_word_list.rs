@@ -0,0 +1,39 @@
+// A small list of simple, everyday words for younger players: animals, food, things around the
+// house, and so on. It was picked by hand to avoid anything obscure, tricky to spell, or not
+// suitable for kids. With --kids, both the possible answers and the suggested guesses are
+// limited to these words.
+
+pub const KIDS_WORD_LIST: &[&str] = &[
+    "apple", "bagel", "baker", "basil", "beach", "beard", "bears", "bench", "berry", "birds",
+    "black", "blank", "block", "bloom", "board", "boats", "bones", "books", "boots", "brain",
+    "bread", "brick", "bride", "brown", "brush", "build", "bunch", "bunny", "cabin", "camel",
+    "candy", "catch", "chair", "chalk", "chase", "cheek", "cheer", "chess", "chest", "chick",
+    "child", "chirp", "class", "clean", "clear", "climb", "clock", "close", "cloud", "clown",
+    "coast", "cocoa", "color", "couch", "count", "cover", "crabs", "crack", "crane", "crash",
+    "crawl", "cream", "crown", "crumb", "daddy", "daisy", "dance", "dizzy", "dolls", "donut",
+    "dream", "dress", "drink", "drive", "ducks", "eager", "eagle", "early", "earth", "eight",
+    "empty", "fable", "fairy", "fancy", "feast", "fence", "fever", "field", "first", "fishy",
+    "flags", "flame", "flash", "float", "flock", "floor", "flour", "flute", "foggy", "forks",
+    "found", "fresh", "frogs", "frost", "fruit", "funny", "fuzzy", "gamer", "ghost", "giant",
+    "glade", "glass", "globe", "glove", "goats", "goose", "grade", "grape", "grass", "great",
+    "green", "grill", "grins", "group", "happy", "heart", "heavy", "hippo", "honey", "horse",
+    "hotel", "house", "hunch", "igloo", "jelly", "jolly", "juice", "juicy", "jumbo", "kayak",
+    "kitty", "koala", "laugh", "lemon", "lever", "light", "lions", "loyal", "lucky", "lunch",
+    "magic", "mango", "maple", "march", "melon", "merry", "milky", "model", "mommy", "moose",
+    "mouse", "mouth", "music", "nests", "night", "noise", "north", "nurse", "ocean", "olive",
+    "onion", "otter", "owner", "paint", "panda", "paper", "party", "pasta", "peach", "pearl",
+    "pedal", "penny", "piano", "pilot", "pizza", "plant", "plate", "plaza", "plums", "porch",
+    "pouch", "prize", "proud", "puffy", "pupil", "puppy", "purse", "queen", "quest", "quick",
+    "quiet", "quilt", "radio", "raven", "ready", "relax", "rhyme", "river", "roast", "robin",
+    "robot", "rocks", "round", "royal", "ruler", "saint", "salad", "sandy", "scale", "scarf",
+    "scout", "seeds", "shape", "share", "sheep", "sheet", "shell", "shine", "shiny", "shirt",
+    "shoes", "shore", "short", "sight", "silly", "skate", "skies", "skill", "skirt", "skunk",
+    "sleep", "slide", "smell", "smile", "snack", "snail", "snake", "snowy", "socks", "sound",
+    "space", "spell", "spicy", "spine", "spoon", "sport", "squad", "stack", "stamp", "stars",
+    "stick", "stone", "storm", "story", "straw", "sunny", "super", "sweet", "swift", "swing",
+    "table", "teach", "teeth", "thank", "thumb", "tiger", "toast", "today", "tooth", "towel",
+    "tower", "track", "trail", "train", "treat", "trees", "truck", "trunk", "tulip", "tummy",
+    "twins", "uncle", "under", "unity", "value", "wagon", "water", "waves", "whale", "wheel",
+    "white", "witch", "woman", "woods", "world", "write", "yacht", "yield", "young", "youth",
+    "yummy", "zebra",
+];
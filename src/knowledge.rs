@@ -0,0 +1,180 @@
+use crate::score::*;
+
+// What the guesses so far have shown about a single letter, the same as the colors on the
+// on-screen keyboard in the real game. These are in order of how much we know, so a letter only
+// ever moves up the list.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum LetterStatus {
+    // Not guessed yet.
+    Untested,
+
+    // Guessed, and not in the word.
+    Excluded,
+
+    // In the word, but we haven't found where yet.
+    Present,
+
+    // In the word, and we've found at least one place it goes.
+    Confirmed,
+}
+
+// The status of every letter, built up from the guesses and scores in a game, along with what's
+// known about each place in the word.
+#[derive(Clone)]
+pub struct Knowledge {
+    letters: [LetterStatus; 26],
+
+    // The letter found in each place, from a green.
+    found: [Option<u8>; WORD_LENGTH],
+
+    // The letters known not to be in each place, one bit per letter. A yellow rules its letter
+    // out of that place, and so does a gray.
+    ruled_out: [u32; WORD_LENGTH],
+
+    // The fewest copies of each letter the word can have, from the most yellows and greens any
+    // one guess got for it.
+    min_counts: [u8; 26],
+}
+
+impl Default for Knowledge {
+    fn default() -> Self {
+        Knowledge {
+            letters: [LetterStatus::Untested; 26],
+            found: [None; WORD_LENGTH],
+            ruled_out: [0; WORD_LENGTH],
+            min_counts: [0; 26],
+        }
+    }
+}
+
+impl Knowledge {
+    pub fn new() -> Knowledge {
+        Knowledge::default()
+    }
+
+    // The status of a letter. Anything that isn't a letter is always untested.
+    pub fn status(&self, letter: char) -> LetterStatus {
+        let letter = letter.to_ascii_lowercase();
+        if letter.is_ascii_lowercase() {
+            self.letters[(letter as u8 - b'a') as usize]
+        } else {
+            LetterStatus::Untested
+        }
+    }
+
+    // The letter found in one place in the word, if it's been found.
+    pub fn found_at(&self, position: usize) -> Option<char> {
+        self.found[position].map(|letter| letter as char)
+    }
+
+    // Whether a letter is known not to be in one place in the word.
+    pub fn ruled_out_at(&self, position: usize, letter: char) -> bool {
+        let letter = letter.to_ascii_lowercase();
+        letter.is_ascii_lowercase() && self.ruled_out[position] & (1 << (letter as u8 - b'a')) != 0
+    }
+
+    // How many copies of a letter the word has to have, at the least.
+    pub fn min_count(&self, letter: char) -> usize {
+        let letter = letter.to_ascii_lowercase();
+        if letter.is_ascii_lowercase() {
+            self.min_counts[(letter as u8 - b'a') as usize] as usize
+        } else {
+            0
+        }
+    }
+
+    // What's wrong with a guess under the real game's hard mode rules, if anything: every green
+    // has to be played in its place again, and every letter that's been found has to be used,
+    // as many times as it's been found. Gray letters are allowed, and so are yellows in places
+    // they've already been ruled out of -- the game doesn't check those, even though they can't
+    // be right.
+    pub fn hard_mode_violation(&self, guess: &str) -> Option<String> {
+        let letters = guess.as_bytes();
+        for (position, found) in self.found.iter().enumerate() {
+            if let Some(letter) = *found {
+                if letters.get(position) != Some(&letter) {
+                    return Some(format!(
+                        "letter {} has to be '{}'",
+                        position + 1,
+                        (letter as char).to_ascii_uppercase()
+                    ));
+                }
+            }
+        }
+        for (index, &min) in self.min_counts.iter().enumerate() {
+            let letter = b'a' + index as u8;
+            let count = letters.iter().filter(|&&l| l == letter).count();
+            if count < min as usize {
+                return Some(match min {
+                    1 => format!("it has to use '{}'", (letter as char).to_ascii_uppercase()),
+                    _ => format!(
+                        "it has to use '{}' {} times",
+                        (letter as char).to_ascii_uppercase(),
+                        min
+                    ),
+                });
+            }
+        }
+        None
+    }
+
+    // The only parts of the knowledge that hard mode's rules look at: the letters found in each
+    // place, and how many copies of each letter have to be used. Two games with the same rules
+    // allow exactly the same guesses next.
+    pub fn hard_mode_rules(&self) -> ([Option<u8>; WORD_LENGTH], [u8; 26]) {
+        (self.found, self.min_counts)
+    }
+
+    // Update the letters from one guess and the score it got.
+    //
+    // The tricky part is repeated letters. If you guess "geese" against "those", the first two
+    // E's are gray, but that only means there aren't three E's -- the last one is green. So a
+    // gray letter only counts as excluded if no other copy of it in the same guess scored.
+    // (With DuplicatePolicy::AllYellow that can't happen: a gray always means there are none.)
+    pub fn record(&mut self, guess: &str, score: WordScore, policy: DuplicatePolicy) {
+        let codes = format_score(score);
+        let scored: Vec<(u8, u8)> = guess.bytes().zip(codes.bytes()).collect();
+        let mut counts = [0u8; 26];
+        for (position, &(letter, code)) in scored.iter().enumerate() {
+            if !letter.is_ascii_lowercase() {
+                continue;
+            }
+
+            let index = (letter - b'a') as usize;
+            if code == b'G' {
+                self.found[position] = Some(letter);
+            } else {
+                self.ruled_out[position] |= 1 << index;
+            }
+            if code != b'.' {
+                counts[index] += 1;
+            }
+
+            let status = match code {
+                b'G' => LetterStatus::Confirmed,
+                b'y' => LetterStatus::Present,
+                _ if scored.iter().any(|&(l, c)| l == letter && c != b'.') => continue,
+                _ => LetterStatus::Excluded,
+            };
+
+            let slot = &mut self.letters[index];
+            *slot = (*slot).max(status);
+        }
+
+        // When every copy of a letter is colored, they don't say how many there are, only that
+        // there's at least one, plus one for each different place it's been found in.
+        if policy == DuplicatePolicy::AllYellow {
+            for (index, count) in counts.iter_mut().enumerate() {
+                let greens = scored
+                    .iter()
+                    .filter(|&&(l, c)| l == b'a' + index as u8 && c == b'G')
+                    .count() as u8;
+                *count = (*count).min(greens.max(1));
+            }
+        }
+
+        for (min, count) in self.min_counts.iter_mut().zip(counts) {
+            *min = (*min).max(count);
+        }
+    }
+}
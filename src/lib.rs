@@ -0,0 +1,56 @@
+pub mod antiwordle;
+pub mod crossword;
+pub mod difficulty;
+pub mod error;
+pub mod exact;
+pub mod guess_quality;
+#[cfg(feature = "embedded-lists")]
+pub mod kids_word_list;
+pub mod knowledge;
+pub mod lists;
+pub mod lookahead;
+pub mod multi_board;
+pub mod nerdle;
+pub mod peaks;
+pub mod policy;
+pub mod reverse;
+pub mod score;
+pub mod score_table;
+#[cfg(feature = "embedded-lists")]
+pub mod scrabble_word_list;
+pub mod simulate;
+pub mod solver;
+pub mod state_code;
+pub mod strategy;
+pub mod word_filter;
+#[cfg(feature = "embedded-lists")]
+pub mod word_frequency_list;
+#[cfg(feature = "embedded-lists")]
+pub mod word_tables;
+#[cfg(feature = "embedded-lists")]
+pub mod wordle_solutions;
+
+pub use antiwordle::*;
+pub use crossword::*;
+pub use difficulty::*;
+pub use error::*;
+pub use exact::*;
+pub use guess_quality::*;
+pub use knowledge::*;
+pub use lists::*;
+pub use lookahead::*;
+pub use multi_board::*;
+pub use nerdle::*;
+pub use peaks::*;
+pub use policy::*;
+pub use reverse::*;
+pub use score::*;
+pub use score_table::*;
+pub use simulate::*;
+pub use solver::*;
+pub use state_code::*;
+pub use strategy::*;
+pub use word_filter::*;
+
+#[cfg(feature = "bot")]
+pub mod bot;
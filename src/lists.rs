@@ -0,0 +1,59 @@
+use std::sync::OnceLock;
+
+// The two lists besides the guesses and the answers: how common each word is, and the simple
+// words for kids' games. Normally these are built into the program, but others can be swapped in
+// at startup, before anything has used them. A build without the embedded lists (see the
+// embedded-lists feature) has to be given them this way, or do without.
+
+#[cfg(feature = "embedded-lists")]
+const BUILT_IN_FREQUENCIES: &[(&str, u32)] = crate::word_frequency_list::WORD_FREQUENCY_LIST;
+#[cfg(not(feature = "embedded-lists"))]
+const BUILT_IN_FREQUENCIES: &[(&str, u32)] = &[];
+
+#[cfg(feature = "embedded-lists")]
+const BUILT_IN_KIDS_WORDS: &[&str] = crate::kids_word_list::KIDS_WORD_LIST;
+#[cfg(not(feature = "embedded-lists"))]
+const BUILT_IN_KIDS_WORDS: &[&str] = &[];
+
+// Each list, and whether it's the built-in one, so that the tables build.rs worked out from the
+// built-in lists are only used with them.
+static WORD_FREQUENCIES: OnceLock<(&'static [(&'static str, u32)], bool)> = OnceLock::new();
+static KIDS_WORDS: OnceLock<(&'static [&'static str], bool)> = OnceLock::new();
+
+// Use this frequency list instead of the built-in one: words and their counts, most common
+// first. This only works before anything has looked at the frequencies, so it returns false if
+// it was too late.
+pub fn set_word_frequencies(list: &'static [(&'static str, u32)]) -> bool {
+    WORD_FREQUENCIES.set((list, false)).is_ok()
+}
+
+// The same for the kids' list.
+pub fn set_kids_words(list: &'static [&'static str]) -> bool {
+    KIDS_WORDS.set((list, false)).is_ok()
+}
+
+fn frequencies() -> (&'static [(&'static str, u32)], bool) {
+    *WORD_FREQUENCIES.get_or_init(|| (BUILT_IN_FREQUENCIES, cfg!(feature = "embedded-lists")))
+}
+
+fn kids() -> (&'static [&'static str], bool) {
+    *KIDS_WORDS.get_or_init(|| (BUILT_IN_KIDS_WORDS, cfg!(feature = "embedded-lists")))
+}
+
+// How common words are, most common first. Without the embedded lists, and without one having
+// been set, this is empty.
+pub fn word_frequencies() -> &'static [(&'static str, u32)] {
+    frequencies().0
+}
+
+pub fn kids_words() -> &'static [&'static str] {
+    kids().0
+}
+
+pub fn frequencies_are_built_in() -> bool {
+    frequencies().1
+}
+
+pub fn kids_words_are_built_in() -> bool {
+    kids().1
+}
@@ -0,0 +1,106 @@
+use crate::exact::*;
+use crate::guess_quality::*;
+use crate::score::*;
+use crate::solver::*;
+use rayon::prelude::*;
+
+// The ranking only looks one guess ahead: how many words each guess leaves. That's quick, and
+// usually right, but a guess that leaves a few more words can still leave ones that are easier
+// to split up with the next guess. These look further ahead, for just the most promising few
+// guesses, since they cost a whole ranking (or much more) for each one.
+
+// A guess, and how it does when the game's played out further than the ranking looks.
+#[derive(Clone, Copy)]
+pub struct LookaheadValue {
+    pub quality: GuessQuality<'static>,
+
+    // With two_ply, the average number of possible answers still to be found after this guess
+    // and the best one after it. With exact_values, the average number of guesses it takes to
+    // find the answer with perfect play, counting this one.
+    pub value: f64,
+}
+
+// How many words are still to be found, on average, after a guess that splits the possible
+// answers up like this. Finding the answer counts as none left, rather than one.
+fn unfound_after(histogram: &[u16; NUM_SCORES], num_targets: usize) -> f64 {
+    let sum: u64 = histogram[..ALL_GREEN as usize]
+        .iter()
+        .map(|&n| n as u64 * n as u64)
+        .sum();
+    sum as f64 / num_targets.max(1) as f64
+}
+
+// Look two guesses ahead: for each score this guess could get, the best guess after it (the
+// same one the suggestions would put first), and how many words that would leave. Words are
+// counted, so with --weighted this still treats every answer as equally likely.
+pub fn two_ply(solver: &Solver, guess: GuessQuality<'static>) -> LookaheadValue {
+    let targets = solver.remaining_targets();
+    let histogram = solver.guess_details(guess.guess).histogram;
+    let mut total = 0.0;
+    for (score, &count) in histogram.iter().enumerate() {
+        total += count as f64
+            * match count {
+                0 => continue,
+                _ if score == ALL_GREEN as usize => 0.0,
+                1 => 0.0,
+                // Guess one of the two: either it's right, or the other one's left.
+                2 => 0.5,
+                _ => {
+                    let mut next = solver.clone();
+                    match next.apply_guess(guess.guess, score as WordScore) {
+                        Ok(()) => match next.best_guess() {
+                            Some(follow_up) => unfound_after(
+                                &next.guess_details(follow_up.guess).histogram,
+                                count as usize,
+                            ),
+                            None => count as f64,
+                        },
+                        Err(_) => count as f64,
+                    }
+                }
+            };
+    }
+    LookaheadValue {
+        quality: guess,
+        value: total / targets.len().max(1) as f64,
+    }
+}
+
+// The first few guesses of a ranking, looked at two guesses ahead, best first. Ties keep the
+// ranking's order.
+pub fn refine_two_ply(
+    solver: &Solver,
+    ranked: &[GuessQuality<'static>],
+    top: usize,
+) -> Vec<LookaheadValue> {
+    let mut values = ranked
+        .par_iter()
+        .take(top)
+        .map(|&q| two_ply(solver, q))
+        .collect::<Vec<_>>();
+    values.sort_by(|a, b| a.value.total_cmp(&b.value));
+    values
+}
+
+// The first few guesses of a ranking, with exactly how many guesses each takes on average with
+// perfect play after it, best first. This is the same search as the exact subcommand, so it's
+// only practical once there aren't too many possible answers left, and it doesn't do hard mode.
+pub fn exact_values(
+    solver: &Solver,
+    ranked: &[GuessQuality<'static>],
+    top: usize,
+) -> Vec<LookaheadValue> {
+    let search = ExactSearch::new(solver, None);
+    let mut values = ranked
+        .par_iter()
+        .take(top)
+        .filter_map(|&q| {
+            Some(LookaheadValue {
+                quality: q,
+                value: search.opener_value(q.guess)?.average_guesses(),
+            })
+        })
+        .collect::<Vec<_>>();
+    values.sort_by(|a, b| a.value.total_cmp(&b.value));
+    values
+}
@@ -0,0 +1,248 @@
+use crate::*;
+use std::cmp::Ordering;
+
+// Games like Quordle play several Wordle boards at once: every guess goes on every board, and
+// each board scores it against its own answer. A board is done once its answer has been guessed,
+// and you only get so many guesses to finish all of them.
+//
+// Ranking guesses for each board on its own doesn't work, since the boards all get the same
+// guess and share the same budget of guesses. A guess that's a little worse for one board but
+// much better for the other three is the one to play. So guesses get ranked by how many words
+// they're expected to leave across all of the boards put together.
+
+// Quordle gives you nine guesses for four boards.
+pub const QUORDLE_BOARDS: usize = 4;
+pub const QUORDLE_MAX_GUESSES: usize = 9;
+
+// How a guess does across every board that hasn't been solved yet.
+pub struct MultiBoardQuality<'a> {
+    pub guess: &'a str,
+
+    // The expected number of words left, added up over the unsolved boards.
+    pub total_expected_remaining: f64,
+
+    // The most words any one board could be left with.
+    pub worst_board_remaining: u16,
+
+    // How many boards this guess could be the answer on.
+    pub winning_boards: usize,
+
+    // How the guess does on each unsolved board on its own, in board order.
+    pub boards: Vec<GuessQuality<'a>>,
+}
+
+// Best first: the smallest total, then the smallest worst board, then the most boards it could
+// win, then alphabetically.
+pub fn compare_multi_board_quality(a: &MultiBoardQuality, b: &MultiBoardQuality) -> Ordering {
+    a.total_expected_remaining
+        .partial_cmp(&b.total_expected_remaining)
+        .unwrap_or(Ordering::Equal)
+        .then_with(|| a.worst_board_remaining.cmp(&b.worst_board_remaining))
+        .then_with(|| b.winning_boards.cmp(&a.winning_boards))
+        .then_with(|| a.guess.cmp(b.guess))
+}
+
+// Rank the guesses against several sets of targets at once, one set per unsolved board.
+pub fn rank_guesses_multi_board<'a>(
+    guesses: &[&'a str],
+    boards: &[&[&str]],
+    policy: DuplicatePolicy,
+) -> Vec<MultiBoardQuality<'a>> {
+    // At the start of a game every board has the same targets, so those only get scored once.
+    let mut per_board: Vec<Vec<GuessQuality>> = Vec::with_capacity(boards.len());
+    for (i, targets) in boards.iter().enumerate() {
+        let qualities = match boards[..i].iter().position(|earlier| earlier == targets) {
+            Some(earlier) => per_board[earlier].clone(),
+            None => score_all_guesses(guesses, targets, policy),
+        };
+        per_board.push(qualities);
+    }
+
+    let mut ranked = guesses
+        .iter()
+        .enumerate()
+        .map(|(i, &guess)| {
+            let boards = per_board
+                .iter()
+                .map(|qualities| qualities[i])
+                .collect::<Vec<_>>();
+            MultiBoardQuality {
+                guess,
+                total_expected_remaining: boards.iter().map(|q| q.expected_remaining).sum(),
+                worst_board_remaining: boards.iter().map(|q| q.max_remaining).max().unwrap_or(0),
+                winning_boards: boards.iter().filter(|q| q.has_winning).count(),
+                boards,
+            }
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_by(compare_multi_board_quality);
+    ranked
+}
+
+// When a guess that could finish a board is within this fraction of the best guess overall,
+// it's worth playing instead: it does nearly as much for the other boards, and might also get a
+// board out of the way.
+// That's only for boards that are nearly finished, with this many possible answers or fewer.
+const CLOSE_OUT_MARGIN: f64 = 0.15;
+const CLOSE_OUT_MAX_ANSWERS: usize = 4;
+
+// What to do next in a multi-board game: keep narrowing down every board at once, or go for
+// the answer on one of them.
+pub enum Plan<'a> {
+    // Play the best guess overall.
+    Probe(&'a str),
+
+    // Play one of the possible answers on this board.
+    CloseOut {
+        board: usize,
+        guess: &'a str,
+        why: CloseOutReason,
+    },
+}
+
+pub enum CloseOutReason {
+    // There are no more guesses left than boards, so every guess has to be able to win one.
+    NoSpareGuesses,
+
+    // The board's answer is certain, and there's at most one guess to spare, so it's safest to
+    // get it out of the way.
+    FewSpareGuesses,
+
+    // Going for the board is nearly as good for the rest of them as the best guess.
+    AsGoodAsProbe,
+}
+
+// The state of a multi-board game: a Solver for each board, and which boards are done.
+#[derive(Clone)]
+pub struct MultiBoard {
+    // Every word that can be guessed, less the ones already played.
+    guesses: Vec<&'static str>,
+    boards: Vec<Solver>,
+    solved: Vec<bool>,
+    num_guesses: usize,
+}
+
+impl MultiBoard {
+    pub fn new(target_list: TargetList, lists: WordLists, num_boards: usize) -> MultiBoard {
+        let board = Solver::with_word_lists(target_list, false, lists);
+        MultiBoard {
+            guesses: board.valid_guesses().to_vec(),
+            boards: vec![board; num_boards],
+            solved: vec![false; num_boards],
+            num_guesses: 0,
+        }
+    }
+
+    // Score every board by some other rule for repeated letters, the same as
+    // Solver::set_duplicate_policy. This has to be set before any guesses are played.
+    pub fn set_duplicate_policy(&mut self, policy: DuplicatePolicy) {
+        for board in &mut self.boards {
+            board.set_duplicate_policy(policy);
+        }
+    }
+
+    pub fn duplicate_policy(&self) -> DuplicatePolicy {
+        self.boards
+            .first()
+            .map_or(DuplicatePolicy::Standard, Solver::duplicate_policy)
+    }
+
+    pub fn boards(&self) -> &[Solver] {
+        &self.boards
+    }
+
+    pub fn is_solved(&self, board: usize) -> bool {
+        self.solved[board]
+    }
+
+    pub fn all_solved(&self) -> bool {
+        self.solved.iter().all(|&solved| solved)
+    }
+
+    // The boards still to be solved, by number.
+    pub fn unsolved(&self) -> Vec<usize> {
+        (0..self.boards.len())
+            .filter(|&i| !self.solved[i])
+            .collect()
+    }
+
+    // How many guesses have been played so far.
+    pub fn num_guesses(&self) -> usize {
+        self.num_guesses
+    }
+
+    // Play a guess, with the score it got on each unsolved board, in board order. If any of the
+    // scores can't be right, nothing changes, and the error says which.
+    pub fn apply_guess(&mut self, guess: &str, scores: &[WordScore]) -> Result<()> {
+        let unsolved = self.unsolved();
+        if scores.len() != unsolved.len() {
+            return Err(Error::WrongNumberOfScores(unsolved.len(), scores.len()));
+        }
+
+        let mut boards = self.boards.clone();
+        for (&board, &score) in unsolved.iter().zip(scores) {
+            boards[board].apply_guess(guess, score)?;
+        }
+        self.boards = boards;
+        for (&board, &score) in unsolved.iter().zip(scores) {
+            self.solved[board] = score == ALL_GREEN;
+        }
+        self.guesses.retain(|w| *w != guess);
+        self.num_guesses += 1;
+        Ok(())
+    }
+
+    // Decide between playing the best guess from `ranked` (which has to be this game's
+    // rank_guesses) and going for the answer on one board, with `guesses_left` guesses to go.
+    // The board to go for is the one with the fewest possible answers, and the guess is
+    // whichever of them ranks best.
+    pub fn plan<'a>(&self, ranked: &[MultiBoardQuality<'a>], guesses_left: usize) -> Plan<'a> {
+        let best = match ranked.first() {
+            Some(best) => best,
+            None => return Plan::Probe(""),
+        };
+        let unsolved = self.unsolved();
+        let board = match unsolved
+            .iter()
+            .copied()
+            .min_by_key(|&b| self.boards[b].remaining_targets().len())
+        {
+            Some(board) => board,
+            None => return Plan::Probe(best.guess),
+        };
+        let answers = self.boards[board].remaining_targets();
+        let closer = match ranked.iter().find(|q| answers.contains(&q.guess)) {
+            Some(closer) => closer,
+            None => return Plan::Probe(best.guess),
+        };
+
+        let spare = guesses_left.saturating_sub(unsolved.len());
+        let why = if spare == 0 {
+            CloseOutReason::NoSpareGuesses
+        } else if spare <= 1 && answers.len() == 1 {
+            CloseOutReason::FewSpareGuesses
+        } else if answers.len() <= CLOSE_OUT_MAX_ANSWERS
+            && closer.total_expected_remaining
+                <= best.total_expected_remaining * (1.0 + CLOSE_OUT_MARGIN)
+        {
+            CloseOutReason::AsGoodAsProbe
+        } else {
+            return Plan::Probe(best.guess);
+        };
+        Plan::CloseOut {
+            board,
+            guess: closer.guess,
+            why,
+        }
+    }
+
+    // Rank every guess across all of the unsolved boards, best first.
+    pub fn rank_guesses(&self) -> Vec<MultiBoardQuality<'static>> {
+        let targets = self
+            .unsolved()
+            .into_iter()
+            .map(|board| self.boards[board].remaining_targets())
+            .collect::<Vec<_>>();
+        rank_guesses_multi_board(&self.guesses, &targets, self.duplicate_policy())
+    }
+}
@@ -0,0 +1,223 @@
+use crate::error::*;
+use crate::guess_quality::*;
+use rayon::prelude::*;
+
+// Nerdle is Wordle with arithmetic: the answer is an equation like "12+35=47", eight symbols
+// long, made of the digits and + - * / =. The scores work the same way as Wordle's, a symbol at
+// a time, so the same histograms and numbers can rank the guesses. What's different is the
+// length -- eight symbols have 3^8 possible scores, far too many for a WordScore -- and that
+// there's no word list. The equations are worked out instead, by trying every one that fits.
+
+pub const NERDLE_LENGTH: usize = 8;
+pub const NERDLE_NUM_SCORES: usize = 6561; // pow(3, NERDLE_LENGTH)
+pub type NerdleScore = u16;
+
+// Every symbol that can appear in an equation.
+pub const NERDLE_SYMBOLS: &str = "0123456789+-*/=";
+
+// Score a guess against a target, a symbol at a time, the same way as score_word_pair: 0 for
+// not found, 1 for the wrong place, 2 for the right place, and the first symbol is the lowest
+// digit in base 3. Both have to be NERDLE_LENGTH symbols long.
+pub fn score_equation_pair(guess: &str, target: &str) -> NerdleScore {
+    score_symbols(&equation_symbols(guess), &equation_symbols(target))
+}
+
+// An equation as the index of each symbol in NERDLE_SYMBOLS, which is what the scoring works on.
+type Symbols = [u8; NERDLE_LENGTH];
+
+fn equation_symbols(equation: &str) -> Symbols {
+    let mut symbols = [0u8; NERDLE_LENGTH];
+    for (symbol, c) in symbols.iter_mut().zip(equation.bytes()) {
+        *symbol = NERDLE_SYMBOLS.bytes().position(|s| s == c).unwrap_or(0) as u8;
+    }
+    symbols
+}
+
+// The place value of each position in a score.
+const PLACE_VALUES: [NerdleScore; NERDLE_LENGTH] = [1, 3, 9, 27, 81, 243, 729, 2187];
+
+// There are only 15 symbols, so rather than searching the target for each yellow, this counts
+// how many of each symbol are left over after the greens, and hands those out.
+fn score_symbols(guess: &Symbols, target: &Symbols) -> NerdleScore {
+    let mut left_over = [0u8; 16];
+    let mut result: NerdleScore = 0;
+
+    // Greens first, so that they can't be used up as yellows.
+    for i in 0..NERDLE_LENGTH {
+        if guess[i] == target[i] {
+            result += 2 * PLACE_VALUES[i];
+        } else {
+            left_over[(target[i] & 15) as usize] += 1;
+        }
+    }
+
+    for i in 0..NERDLE_LENGTH {
+        let count = &mut left_over[(guess[i] & 15) as usize];
+        if guess[i] != target[i] && *count > 0 {
+            *count -= 1;
+            result += PLACE_VALUES[i];
+        }
+    }
+
+    result
+}
+
+// The same as format_score, for a Nerdle score.
+pub fn format_equation_score(mut score: NerdleScore) -> String {
+    (0..NERDLE_LENGTH)
+        .map(|_| {
+            let letter = match score % 3 {
+                0 => '.',
+                1 => 'y',
+                _ => 'G',
+            };
+            score /= 3;
+            letter
+        })
+        .collect()
+}
+
+// The same as parse_score, for a Nerdle score.
+pub fn parse_equation_score(readable: &str) -> Result<NerdleScore> {
+    let values = crate::score::parse_score_values(readable)?;
+    if values.len() != NERDLE_LENGTH {
+        return Err(Error::InvalidEquationScore(readable.trim().to_string()));
+    }
+    Ok(values
+        .iter()
+        .rev()
+        .fold(0, |result, &value| result * 3 + value as NerdleScore))
+}
+
+// Every equation Nerdle could have as an answer, in order. The rules are: a calculation on the
+// left, with at least one + - * or /, and a number on the right. Numbers can't have leading
+// zeros or a minus sign, a zero can't be a number on its own, and the usual order of operations
+// applies, so multiplying and dividing come before adding and subtracting. The answer has to come out to a whole number, though it's
+// fine for a division partway through not to.
+pub fn nerdle_equations() -> Vec<String> {
+    let mut equations = Vec::new();
+    for left_length in 3..NERDLE_LENGTH - 1 {
+        let mut left = String::new();
+        build_left_sides(&mut left, left_length, &mut |left| {
+            if let Some(value) = evaluate(left).filter(|&value| value != 0) {
+                let right = value.to_string();
+                if left.len() + 1 + right.len() == NERDLE_LENGTH {
+                    equations.push(format!("{}={}", left, right));
+                }
+            }
+        });
+    }
+    equations.sort_unstable();
+    equations
+}
+
+// Try every left-hand side of the given length, symbol by symbol, skipping anything that
+// couldn't be a calculation: an operator first or last, two in a row, or a zero at the start of
+// a number.
+fn build_left_sides(left: &mut String, length: usize, found: &mut dyn FnMut(&str)) {
+    if left.len() == length {
+        if left.bytes().any(is_operator) && !left.bytes().last().is_some_and(is_operator) {
+            found(left);
+        }
+        return;
+    }
+
+    let last = left.bytes().last();
+    let number_start = left
+        .rfind(|c: char| is_operator(c as u8))
+        .map_or(0, |i| i + 1);
+    let lone_zero = &left[number_start..] == "0";
+    for symbol in NERDLE_SYMBOLS.bytes().filter(|&s| s != b'=') {
+        let allowed = if is_operator(symbol) {
+            last.is_some_and(|c| !is_operator(c))
+        } else {
+            true
+        } && !lone_zero;
+        if allowed {
+            left.push(symbol as char);
+            build_left_sides(left, length, found);
+            left.pop();
+        }
+    }
+}
+
+fn is_operator(c: u8) -> bool {
+    matches!(c, b'+' | b'-' | b'*' | b'/')
+}
+
+// Work out a calculation exactly, as a fraction, and hand back the answer if it's a whole
+// number that isn't negative.
+fn evaluate(expression: &str) -> Option<i64> {
+    // A sum of terms, where each term is a product (or quotient) of numbers.
+    let (mut sum_num, mut sum_den) = (0i64, 1i64);
+    let (mut term_num, mut term_den) = (1i64, 1i64);
+    let mut sign = 1i64;
+    let mut dividing = false;
+    let mut number = 0i64;
+    for c in expression.bytes().chain(std::iter::once(b'+')) {
+        if c.is_ascii_digit() {
+            number = number * 10 + (c - b'0') as i64;
+            continue;
+        }
+
+        if !dividing {
+            term_num *= number;
+        } else if number == 0 {
+            return None;
+        } else {
+            term_den *= number;
+        }
+        number = 0;
+
+        match c {
+            b'*' => dividing = false,
+            b'/' => dividing = true,
+            _ => {
+                sum_num = sum_num * term_den + sign * term_num * sum_den;
+                sum_den *= term_den;
+                let divisor = gcd(sum_num.abs(), sum_den).max(1);
+                sum_num /= divisor;
+                sum_den /= divisor;
+                (term_num, term_den) = (1, 1);
+                sign = if c == b'-' { -1 } else { 1 };
+                dividing = false;
+            }
+        }
+    }
+    (sum_den == 1 && sum_num >= 0).then_some(sum_num)
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// Rank every guess against the remaining targets, best first, in the same order as
+// rank_guesses. Along with each one is the score that leaves the most targets, since that
+// doesn't fit in the GuessQuality.
+pub fn rank_equations<'a>(
+    guesses: &[&'a str],
+    targets: &[&str],
+) -> Vec<(GuessQuality<'a>, NerdleScore)> {
+    let targets = targets
+        .iter()
+        .map(|t| equation_symbols(t))
+        .collect::<Vec<_>>();
+    let mut ranked = guesses
+        .par_iter()
+        .map(|&guess| {
+            let symbols = equation_symbols(guess);
+            let mut histogram = [0u16; NERDLE_NUM_SCORES];
+            for target in &targets {
+                histogram[score_symbols(&symbols, target) as usize] += 1;
+            }
+            let (quality, worst_score) = histogram_quality(guess, &histogram, targets.len());
+            (quality, worst_score as NerdleScore)
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_by(|a, b| compare_guess_quality(&a.0, &b.0));
+    ranked
+}
@@ -0,0 +1,156 @@
+use crate::error::*;
+use crate::guess_quality::*;
+use crate::score::*;
+use rayon::prelude::*;
+
+// Wordle Peaks is Wordle with a different kind of clue: instead of gray, yellow, and green, each
+// letter says whether the answer's letter in that place comes earlier in the alphabet, later, or
+// is the same. There are still three clues a letter, so a score still fits in a WordScore, and
+// the same histograms rank the guesses. But the letters don't affect each other at all, the way
+// repeated letters do in Wordle, so what's known about the answer is just a range of letters
+// for each place.
+
+// The clue for one letter, as the digit it is in a score.
+const EARLIER: u8 = 0;
+const LATER: u8 = 1;
+const CORRECT: u8 = 2;
+
+// Score a guess against a target for Wordle Peaks. Like score_word_pair, each place is a digit
+// in base 3 with the first letter the lowest: 0 when the target's letter comes earlier in the
+// alphabet than the guess's, 1 when it comes later, and 2 when they're the same.
+pub fn score_peaks_pair(guess: &str, target: &str) -> WordScore {
+    guess
+        .bytes()
+        .zip(target.bytes())
+        .rev()
+        .fold(0, |score, (g, t)| {
+            let clue = match t.cmp(&g) {
+                std::cmp::Ordering::Less => EARLIER,
+                std::cmp::Ordering::Greater => LATER,
+                std::cmp::Ordering::Equal => CORRECT,
+            };
+            score * 3 + clue
+        })
+}
+
+// Show a Peaks score as '<' for earlier, '>' for later, and 'G' for correct, e.g. "<>G<<".
+pub fn format_peaks_score(mut score: WordScore) -> String {
+    (0..WORD_LENGTH)
+        .map(|_| {
+            let clue = match score % 3 {
+                EARLIER => '<',
+                LATER => '>',
+                _ => 'G',
+            };
+            score /= 3;
+            clue
+        })
+        .collect()
+}
+
+// Read back a Peaks score written the way format_peaks_score writes it. Spaces are ignored, and
+// the green can be a lowercase 'g' too.
+pub fn parse_peaks_score(readable: &str) -> Result<WordScore> {
+    let clues = readable
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| match c {
+            '<' => Some(EARLIER),
+            '>' => Some(LATER),
+            'g' | 'G' => Some(CORRECT),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>();
+    match clues {
+        Some(clues) if clues.len() == WORD_LENGTH => {
+            Ok(clues.iter().rev().fold(0, |score, &clue| score * 3 + clue))
+        }
+        _ => Err(Error::InvalidPeaksScore(readable.trim().to_string())),
+    }
+}
+
+// What the clues so far have shown: the range of letters the answer could have in each place.
+// Every clue just narrows one of these, so this is all there is to know.
+#[derive(Clone, Copy)]
+pub struct PeaksRanges {
+    // The first and last letter each place could be, inclusive.
+    ranges: [(u8, u8); WORD_LENGTH],
+}
+
+impl Default for PeaksRanges {
+    fn default() -> Self {
+        PeaksRanges {
+            ranges: [(b'a', b'z'); WORD_LENGTH],
+        }
+    }
+}
+
+impl PeaksRanges {
+    pub fn new() -> PeaksRanges {
+        PeaksRanges::default()
+    }
+
+    // Narrow the ranges with a guess and the score it got. If that leaves some place with no
+    // letters at all, the ranges are left as they were, since the score has to be a typo.
+    pub fn record(&mut self, guess: &str, score: WordScore) -> Result<()> {
+        let mut ranges = self.ranges;
+        let mut clues = score;
+        for ((first, last), letter) in ranges.iter_mut().zip(guess.bytes()) {
+            match clues % 3 {
+                EARLIER => *last = (*last).min(letter.saturating_sub(1)),
+                LATER => *first = (*first).max(letter.saturating_add(1)),
+                _ => (*first, *last) = ((*first).max(letter), (*last).min(letter)),
+            }
+            clues /= 3;
+        }
+        if ranges.iter().any(|(first, last)| first > last) {
+            return Err(Error::EmptyCandidateSet(
+                guess.to_string(),
+                format_peaks_score(score),
+            ));
+        }
+        self.ranges = ranges;
+        Ok(())
+    }
+
+    // Whether a word fits inside every range, which is the same as it getting every score so
+    // far if it were the answer.
+    pub fn allows(&self, word: &str) -> bool {
+        word.len() == WORD_LENGTH
+            && word
+                .bytes()
+                .zip(self.ranges)
+                .all(|(letter, (first, last))| (first..=last).contains(&letter))
+    }
+
+    // The range for each place, written like "a-m", or just the letter once it's known.
+    pub fn describe(&self) -> Vec<String> {
+        self.ranges
+            .iter()
+            .map(|&(first, last)| {
+                if first == last {
+                    (first as char).to_string()
+                } else {
+                    format!("{}-{}", first as char, last as char)
+                }
+            })
+            .collect()
+    }
+}
+
+// Rank every guess against the remaining targets using Peaks scores, best first, in the same
+// order as rank_guesses.
+pub fn rank_peaks_guesses<'a>(guesses: &[&'a str], targets: &[&str]) -> Vec<GuessQuality<'a>> {
+    let mut ranked = guesses
+        .par_iter()
+        .map(|&guess| {
+            let mut histogram = [0u16; NUM_SCORES];
+            for target in targets {
+                histogram[score_peaks_pair(guess, target) as usize] += 1;
+            }
+            histogram_quality(guess, &histogram, targets.len()).0
+        })
+        .collect::<Vec<_>>();
+    ranked.sort_by(compare_guess_quality);
+    ranked
+}
@@ -0,0 +1,193 @@
+use crate::*;
+use rayon::prelude::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+// A policy is a complete plan for playing the game: what to guess first, what to guess next
+// for every score that could come back, and so on. Written out in full, that's a decision tree,
+// but most people's plans are more like "open with CRANE, then do whatever entropy says", so a
+// policy can be any mix of the two. Policy files look like this:
+//
+//     # Comments start with #.
+//     open crane
+//     then entropy
+//     ..y.. sloth
+//     ..y.. .G... pudgy
+//
+// "open" is the first guess. Each line after that is the scores seen so far, in order, and the
+// guess to make after them, so the last line says to play PUDGY when CRANE scored ..y.. and
+// SLOTH scored .G.... Anything the tree doesn't cover is left to the "then" strategy, which is
+// "ranker" if not given.
+pub struct Policy {
+    tree: HashMap<Vec<WordScore>, String>,
+    fallback: Box<dyn Strategy>,
+}
+
+// How a policy did against every possible answer.
+pub struct PolicyEvaluation {
+    pub games: Vec<GameResult>,
+
+    // How many of the policy's decisions never came up, because no answer leads to them. These
+    // are usually typos in the scores.
+    pub unused_decisions: usize,
+
+    // The places where the policy makes a guess that hard mode wouldn't allow, if the solver is
+    // in hard mode.
+    pub hard_mode_violations: Vec<HardModeViolation>,
+}
+
+impl Policy {
+    // A policy that's just an opening word (or not even that) followed by a strategy.
+    pub fn new(opener: Option<&str>, fallback: Box<dyn Strategy>) -> Policy {
+        let mut tree = HashMap::new();
+        if let Some(opener) = opener {
+            tree.insert(Vec::new(), opener.to_lowercase());
+        }
+        Policy { tree, fallback }
+    }
+
+    // Read a policy file, as described above.
+    pub fn parse(text: &str) -> Result<Policy> {
+        let mut policy = Policy::new(None, strategy_by_name("ranker")?);
+        for (number, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let tokens = line.split_whitespace().collect::<Vec<_>>();
+            let bad_line = |why: &str| {
+                Error::InvalidPolicy(format!("line {}: {}: '{}'", number + 1, why, line))
+            };
+
+            match tokens.as_slice() {
+                [] => {}
+                ["then", strategy] => policy.fallback = strategy_by_name(strategy)?,
+                ["open", word] => {
+                    policy.tree.insert(Vec::new(), word.to_lowercase());
+                }
+                [scores @ .., word] => {
+                    let path = scores
+                        .iter()
+                        .map(|score| parse_score(score))
+                        .collect::<Result<Vec<_>>>()
+                        .map_err(|_| bad_line("expected scores followed by a word"))?;
+                    if policy.tree.insert(path, word.to_lowercase()).is_some() {
+                        return Err(bad_line("these scores already have a guess"));
+                    }
+                }
+            }
+        }
+        Ok(policy)
+    }
+
+    // Play the policy against every remaining answer, all at once: make the policy's guess,
+    // split the answers up by the score they'd give it, and carry on with each group. This is
+    // exact, not a sample, so the results are the policy's true average and worst case.
+    pub fn evaluate(&self, start: &Solver) -> Result<PolicyEvaluation> {
+        // Hard mode only suggests words that fit every score so far, but the game itself lets
+        // through plenty more than that, so any word that can be guessed at all is accepted
+        // here, and checked against the real rules afterwards.
+        let allowed = start.valid_guesses().iter().copied().collect();
+        let games = self.play_from(start, &allowed, &[], &[])?;
+
+        // Every decision that got used is a prefix of some game's scores.
+        let mut used = HashSet::new();
+        for game in &games {
+            let mut path = Vec::new();
+            used.insert(path.clone());
+            for guess in &game.guesses[..game.guesses.len() - 1] {
+                path.push(start.score(guess, game.answer));
+                used.insert(path.clone());
+            }
+        }
+        let unused_decisions = self
+            .tree
+            .keys()
+            .filter(|path| !used.contains(*path))
+            .count();
+
+        Ok(PolicyEvaluation {
+            hard_mode_violations: hard_mode_violations(start, &games),
+            games,
+            unused_decisions,
+        })
+    }
+
+    // Play out every game that has gotten as far as `solver`, having seen `path` for scores
+    // after playing `guesses`.
+    fn play_from(
+        &self,
+        solver: &Solver,
+        allowed: &HashSet<&'static str>,
+        path: &[WordScore],
+        guesses: &[&'static str],
+    ) -> Result<Vec<GameResult>> {
+        // A policy that never finds the answer would go on forever, so give up at some point
+        // and call the rest of the games failures.
+        if guesses.len() >= MAX_SIMULATED_GUESSES {
+            return Ok(solver
+                .remaining_targets()
+                .iter()
+                .map(|&answer| GameResult {
+                    answer,
+                    guesses: guesses.to_vec(),
+                })
+                .collect());
+        }
+
+        let guess = match self.tree.get(path) {
+            Some(word) => *allowed.get(word.as_str()).ok_or_else(|| {
+                Error::InvalidPolicy(format!(
+                    "'{}' after {} isn't an allowed guess",
+                    word,
+                    describe_path(path)
+                ))
+            })?,
+            None => {
+                // Seed from the path, so that even the random strategy makes the same choices
+                // every time.
+                let seed = path.iter().fold(0u64, |seed, &s| {
+                    seed.wrapping_mul(NUM_SCORES as u64).wrapping_add(s as u64)
+                });
+                self.fallback
+                    .choose_guess(solver, &mut seeded_rng(Some(seed)))
+            }
+        };
+        let mut guesses = guesses.to_vec();
+        guesses.push(guess);
+
+        let mut by_score: BTreeMap<WordScore, Vec<&'static str>> = BTreeMap::new();
+        for &answer in solver.remaining_targets() {
+            by_score
+                .entry(solver.score(guess, answer))
+                .or_default()
+                .push(answer);
+        }
+
+        let results = by_score
+            .into_par_iter()
+            .map(|(score, answers)| {
+                if answers == [guess] {
+                    return Ok(vec![GameResult {
+                        answer: guess,
+                        guesses: guesses.clone(),
+                    }]);
+                }
+                let mut solver = solver.clone();
+                solver.apply_guess(guess, score)?;
+                let mut path = path.to_vec();
+                path.push(score);
+                self.play_from(&solver, allowed, &path, &guesses)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(results.into_iter().flatten().collect())
+    }
+}
+
+fn describe_path(path: &[WordScore]) -> String {
+    if path.is_empty() {
+        "the start".to_string()
+    } else {
+        path.iter()
+            .map(|&s| format_score(s).to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
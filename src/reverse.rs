@@ -0,0 +1,116 @@
+use crate::knowledge::*;
+use crate::score::*;
+use std::collections::HashMap;
+
+// Working backwards from a friend's share grid: the colors of each of their guesses, and the
+// answer, which you know once you've played the same day's game. Each row of colors only fits
+// some of the words against that answer, and that narrows down what they must have guessed.
+//
+// In normal mode the rows have nothing to do with each other, so each one is just the words
+// that get its colors. In hard mode each guess has to follow the rules set by the ones before
+// it, so a word only counts if there's some way of playing the rows before it that allows it,
+// and some way of playing the rows after it that it allows.
+
+pub struct ReconstructedRow {
+    pub score: WordScore,
+
+    // The words this guess could have been, in the same order as the guesses they came from.
+    pub words: Vec<&'static str>,
+}
+
+pub struct Reconstruction {
+    pub rows: Vec<ReconstructedRow>,
+
+    // How many different sequences of guesses fit the whole grid.
+    pub sequences: u128,
+}
+
+// One way a word could have been played in a row: which state it was played from, which word
+// of the row it was, and which state it led to.
+struct Move {
+    from: usize,
+    word: usize,
+    to: usize,
+}
+
+// Find every guess that fits each row of the grid. In hard mode, the first free_guesses rows
+// don't have to follow its rules, the same as Solver::set_hard_after. The rows are scored by the
+// given rule for repeated letters.
+pub fn reconstruct_guesses(
+    guesses: &[&'static str],
+    answer: &str,
+    scores: &[WordScore],
+    hard: bool,
+    free_guesses: usize,
+    policy: DuplicatePolicy,
+) -> Reconstruction {
+    // The states are what's known after the rows so far, one for each different set of hard
+    // mode rules it could have come to, along with how many ways of playing the rows lead
+    // there. Ways that end up with the same rules allow all the same guesses from then on, so
+    // they only need following once. In normal mode nothing is recorded, so there's only ever
+    // the one state.
+    let mut states: Vec<(Knowledge, u128)> = vec![(Knowledge::new(), 1)];
+    let mut rows = Vec::with_capacity(scores.len());
+    let mut moves: Vec<Vec<Move>> = Vec::with_capacity(scores.len());
+    let mut states_before = Vec::with_capacity(scores.len());
+    for (turn, &score) in scores.iter().enumerate() {
+        let fits = guesses
+            .iter()
+            .copied()
+            .filter(|guess| policy.score(guess, answer) == score)
+            .collect::<Vec<_>>();
+        let checked = hard && turn >= free_guesses;
+
+        let mut index = HashMap::new();
+        let mut next_states: Vec<(Knowledge, u128)> = Vec::new();
+        let mut row_moves = Vec::new();
+        for (from, (knowledge, ways)) in states.iter().enumerate() {
+            for (word, &guess) in fits.iter().enumerate() {
+                if checked && knowledge.hard_mode_violation(guess).is_some() {
+                    continue;
+                }
+                let mut next = knowledge.clone();
+                if hard {
+                    next.record(guess, score, policy);
+                }
+                let to = *index.entry(next.hard_mode_rules()).or_insert_with(|| {
+                    next_states.push((next, 0));
+                    next_states.len() - 1
+                });
+                next_states[to].1 = next_states[to].1.saturating_add(*ways);
+                row_moves.push(Move { from, word, to });
+            }
+        }
+
+        states_before.push(states.len());
+        states = next_states;
+        rows.push(ReconstructedRow { score, words: fits });
+        moves.push(row_moves);
+    }
+
+    // A word that's allowed early on might only lead to dead ends later, so go back up the rows
+    // keeping just the words that lead somewhere useful. Every state at the end of the grid is
+    // useful, and a state before that is if some word leads from it to a useful state.
+    let mut useful = vec![true; states.len()];
+    for ((row, row_moves), &num_states) in rows.iter_mut().zip(&moves).zip(&states_before).rev() {
+        let mut keep = vec![false; row.words.len()];
+        let mut useful_before = vec![false; num_states];
+        for m in row_moves.iter().filter(|m| useful[m.to]) {
+            keep[m.word] = true;
+            useful_before[m.from] = true;
+        }
+        row.words = row
+            .words
+            .iter()
+            .zip(keep)
+            .filter(|&(_, keep)| keep)
+            .map(|(&word, _)| word)
+            .collect();
+        useful = useful_before;
+    }
+
+    let sequences = states
+        .iter()
+        .fold(0u128, |total, (_, ways)| total.saturating_add(*ways));
+    Reconstruction { rows, sequences }
+}
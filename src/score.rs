@@ -0,0 +1,1121 @@
+use crate::error::*;
+
+pub const WORD_LENGTH: usize = 5;
+
+// score_word_pair below is hand-unrolled for five letters.
+const _: () = assert!(WORD_LENGTH == 5);
+
+// We can represent the "score" of a guess versus a target as a single number. There are
+// five letters and three possibilities for each letter, for a total of 3^5 possible
+// ways of scoring a guess against a target. We can think of this as a 5-digit base-three
+// number, where:
+//
+//     0 = letter not in target
+//     1 = right letter in wrong place (yellow)
+//     2 = right letter in right place (green)
+//
+// So if we're guessing "caddy" against a secret word of "abbey", the score would be:
+//
+//    C: 0 = not in solution
+//    A: 1 = wrong place (yellow)
+//    D: 0 = not in solution
+//    D: 0 = not in solution
+//    Y: 2 = right place (green)
+//
+// And reading bottom up, that can be represented as a base-3 number of 20010 (for convenience the
+// first letter is in the lowest digit). Which would turn into a decimal number as:
+//
+//   162 +     [162 == 2 * 3^4 == 2 * 81]
+//     0 +     [  0 == 0 * 3^3 == 0 * 27]
+//     0 +     [  0 == 0 * 3^2 == 0 *  9]
+//     3 +     [  3 == 1 * 3^1 == 1 *  3]
+//     0       [  0 == 0 * 3^0 == 0 *  1]
+// --------
+//   165
+//
+// And so the score for guessing "caddy" against a possible secret word of "abbey" would be 165.
+//
+// Higher-numbered scores aren't better in any way -- the point is we can make a histogram of
+// the possible scores for a single guess against a list of possible solutions, and then
+// rank the guesses based on which one does the best at narrowing the list of possible solutions.
+
+pub const NUM_SCORES: usize = 243; // This is pow(3, WORD_LENGTH). Any good way to make that compile-time?
+pub type WordScore = u8;
+
+// Every letter green: the guess was the answer.
+pub const ALL_GREEN: WordScore = (NUM_SCORES - 1) as WordScore;
+
+// How repeated letters in a guess get colored. Wordle only turns as many copies of a letter
+// yellow or green as there are in the answer, but some copies of the game color every copy of a
+// letter that's in the answer somewhere, however many times it's there.
+//
+// Each Solver has its own, so games with different rules can go on side by side. The scores,
+// the ranking, and the narrowing down all have to agree on it, so everything that scores a
+// guess for a game takes the game's policy.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Hash)]
+pub enum DuplicatePolicy {
+    // Wordle's rule: guessing "geese" against "those", only the last E is colored (green), and
+    // the others are gray.
+    #[default]
+    Standard,
+
+    // Every copy gets colored: guessing "geese" against "those", all three E's are colored,
+    // the first two yellow and the last green.
+    AllYellow,
+}
+
+impl DuplicatePolicy {
+    // The same as score_word_pair, by this rule.
+    pub fn score(self, guess: &str, target: &str) -> WordScore {
+        match self {
+            DuplicatePolicy::Standard => score_word_pair(guess, target),
+            DuplicatePolicy::AllYellow => score_all_yellow(&word_chars(guess), &word_chars(target)),
+        }
+    }
+
+    // The same as score_word_bytes, by this rule.
+    #[inline]
+    pub fn score_bytes(self, guess: &WordBytes, target: &WordBytes) -> WordScore {
+        match self {
+            DuplicatePolicy::Standard => score_word_bytes(guess, target),
+            DuplicatePolicy::AllYellow => score_all_yellow(guess, target),
+        }
+    }
+}
+
+// Readable scores are in a format like ".y.GG", where:
+//   . = letter not found
+//   y = (yellow) letter in wrong place
+//   G = (green) letter in right place
+//
+// When reading them back in, we're a bit more forgiving about it: '-', '_', and 'x' work for
+// letters that weren't found, case doesn't matter, and the digits 0, 1, and 2 work too, so
+// "01022" is the same as ".y.GG". Spaces are ignored, so ". y . G G" is fine. So are the
+// colored squares from the game's share button, including the orange and blue ones it uses in
+// high contrast mode, so a row of a share grid can be pasted in as it is.
+
+// Turn a numeric score into something readable. 165 => .y..G
+//
+// This gets called for every line of suggestions, so it hands back the letters in a fixed-size
+// FormattedScore instead of allocating a String. That derefs to a &str, and prints with {}.
+pub fn format_score(mut score: WordScore) -> FormattedScore {
+    let mut result = [0u8; WORD_LENGTH];
+    for letter in result.iter_mut() {
+        let letter_score = score % 3;
+        *letter = match letter_score {
+            0 => b'.',
+            1 => b'y',
+            _ => b'G', // score % 3 can only be 2 here
+        };
+        score /= 3;
+    }
+
+    FormattedScore(result)
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct FormattedScore([u8; WORD_LENGTH]);
+
+impl std::ops::Deref for FormattedScore {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        // Only ever holds the ASCII characters written by format_score.
+        std::str::from_utf8(&self.0).unwrap_or_default()
+    }
+}
+
+impl std::fmt::Display for FormattedScore {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.pad(self)
+    }
+}
+
+// Try to turn a readable string back into a numeric score. .y..G => 165
+pub fn parse_score(readable: &str) -> Result<WordScore> {
+    let values = parse_score_values(readable)?;
+    if values.len() != WORD_LENGTH {
+        return Err(Error::InvalidScore(readable.trim().to_string()));
+    }
+    Ok(values
+        .iter()
+        .rev()
+        .fold(0, |result, &value| result * 3 + value))
+}
+
+// The letters of a readable score as 0, 1, or 2 each, however many there are.
+pub(crate) fn parse_score_values(readable: &str) -> Result<Vec<u8>> {
+    // Some of the squares come with an invisible marker after them, asking for the colored
+    // version of the character. That gets skipped, the same as spaces.
+    let letters = readable
+        .chars()
+        .filter(|&c| !c.is_whitespace() && c != '\u{fe0f}')
+        .collect::<Vec<_>>();
+
+    // A typo'd letter is a more useful thing to point out than the length, since it's usually
+    // the reason the length is wrong too.
+    let mut values = Vec::with_capacity(letters.len());
+    for (position, &c) in letters.iter().enumerate() {
+        values.push(match c {
+            'g' | 'G' | '2' | '🟩' | '🟧' => 2,
+            'y' | 'Y' | '1' | '🟨' | '🟦' => 1,
+            '.' | '-' | '_' | 'x' | 'X' | '0' | '⬛' | '⬜' => 0,
+            _ => {
+                return Err(Error::InvalidScoreLetter(
+                    readable.trim().to_string(),
+                    c,
+                    position + 1,
+                ));
+            }
+        });
+    }
+
+    Ok(values)
+}
+
+// Calculate the score for a given guess against a given target. Note that this is NOT symmetric.
+// i.e.  score_word_pair("caddy", "abbey") != score_word_pair("abbey", "caddy")
+//
+// Because this function consumes the majority of the runtime, it's been superseded by the
+// hand-optimized version below. Kept around for reference and to validate the correctness
+// of the optimized version.
+pub fn score_word_pair_simple(guess: &str, target: &str) -> WordScore {
+    // A bitfield for the letters of the guess and the target. We
+    // mark these off as they're paired up.
+    let mut guess_used = 0u32;
+    let mut target_used = 0u32;
+
+    // The result. Starts at 0 for no matches; as we find matches
+    // we'll add values in.
+    let mut result: WordScore = 0;
+
+    // Reasonable to use bytes here. We're playing a game about
+    // guessing English words, and the speed of this function is
+    // the main limit in performance.
+    let guess = guess.as_bytes();
+    let target = target.as_bytes();
+
+    // Match up all of the "right letter in right place" pairs FIRST,
+    // and mark them off as so they won't be checked later. If we're
+    // matching "cheer" against "abbey" we want to have the SECOND 'e'
+    // in "cheEr" be scored as a right-letter-right-place match, and
+    // do NOT want the FIRST 'e' to be scored as a right-letter-wrong-place
+    // match.
+    //
+    // When we find a match, add a 2 in the corresponding place in
+    // the score.
+    let mut mult: WordScore = 1;
+    for (i, (&g, &t)) in guess.iter().zip(target.iter()).enumerate() {
+        if g == t {
+            result += 2 * mult;
+            guess_used |= 1 << i;
+            target_used |= 1 << i;
+        }
+        mult *= 3;
+    }
+
+    // Now match the remaining letters, searching for other places.
+    // Here we have to consider all 5*5 pairings. Getting clever
+    // about skipping past things in the iteration is unlikely to
+    // be faster than a simple constant-size loop.
+    //
+    // When we find a match, add a 1 in the corresponding place in
+    // the score.
+    mult = 1;
+    for (i, &g) in guess.iter().enumerate() {
+        if (guess_used & (1 << i)) != 0 {
+            mult *= 3;
+            continue;
+        }
+        for (j, &t) in target.iter().enumerate() {
+            if (target_used & (1 << j)) != 0 {
+                continue;
+            }
+            if g == t {
+                guess_used |= 1 << i;
+                target_used |= 1 << j;
+                result += mult;
+                break;
+            }
+        }
+        mult *= 3;
+    }
+
+    result
+}
+
+// Calculate the score for a given guess against a given target. Note that this is NOT symmetric.
+// i.e.  score_word_pair("caddy", "abbey") != score_word_pair("abbey", "caddy")
+//
+// Both words must be exactly WORD_LENGTH letters. Everything in the word lists is, and this is
+// called for every guess/target pair, so it's checked with an assert rather than returning a
+// Result -- use try_score_word_pair for words that haven't been checked yet.
+//
+// English words go through the fast byte-at-a-time scoring below. Words with letters outside of
+// plain ASCII, like ñ or é, get scored a letter at a time instead, which is slower but correct.
+//
+// This is Wordle's own rule for repeated letters. For a game that might be using the other one,
+// use its DuplicatePolicy's score instead.
+pub fn score_word_pair(guess: &str, target: &str) -> WordScore {
+    if guess.is_ascii() && target.is_ascii() {
+        score_word_bytes(word_bytes(guess), word_bytes(target))
+    } else {
+        score_word_chars(&word_chars(guess), &word_chars(target))
+    }
+}
+
+// How many letters are in a word. For English that's the same as its length in bytes, but not
+// for letters with accents.
+pub fn word_length(word: &str) -> usize {
+    word.chars().count()
+}
+
+// Whether something is a word we can play with: the right number of letters, and nothing else.
+pub fn is_valid_word(word: &str) -> bool {
+    word_length(word) == WORD_LENGTH && word.chars().all(char::is_alphabetic)
+}
+
+// Take the accents off of letters, so "árbol" is the same word as "arbol". Spanish and French
+// versions of Wordle work this way: you can type a word without its accents, and an É in the
+// answer turns green for an E in your guess. Ñ and ß are letters of their own, though, not
+// accented versions of N and S, so they're left alone.
+pub fn fold_accents(word: &str) -> String {
+    word.chars()
+        .map(|c| match c {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' => 'a',
+            'ç' => 'c',
+            'è' | 'é' | 'ê' | 'ë' => 'e',
+            'ì' | 'í' | 'î' | 'ï' => 'i',
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' => 'o',
+            'ù' | 'ú' | 'û' | 'ü' => 'u',
+            'ý' | 'ÿ' => 'y',
+            _ => c,
+        })
+        .collect()
+}
+
+// A word as individual letters, for scoring words that aren't plain ASCII. Panics if it's the
+// wrong length, the same as word_bytes.
+fn word_chars(word: &str) -> [char; WORD_LENGTH] {
+    let mut letters = ['\0'; WORD_LENGTH];
+    let mut count = 0;
+    for c in word.chars() {
+        if count == WORD_LENGTH {
+            count += 1;
+            break;
+        }
+        letters[count] = c;
+        count += 1;
+    }
+    if count != WORD_LENGTH {
+        panic!("'{}' is not {} letters long", word, WORD_LENGTH);
+    }
+    letters
+}
+
+// Scoring with DuplicatePolicy::AllYellow: each letter is green in the right place, yellow if
+// it's anywhere else in the target, and gray otherwise, without any counting.
+fn score_all_yellow<T: PartialEq>(
+    guess: &[T; WORD_LENGTH],
+    target: &[T; WORD_LENGTH],
+) -> WordScore {
+    guess.iter().enumerate().rev().fold(0, |result, (i, g)| {
+        let value = if target[i] == *g {
+            2
+        } else if target.contains(g) {
+            1
+        } else {
+            0
+        };
+        result * 3 + value
+    })
+}
+
+// The same scoring as score_word_pair_simple, a letter at a time rather than a byte at a time.
+fn score_word_chars(guess: &[char; WORD_LENGTH], target: &[char; WORD_LENGTH]) -> WordScore {
+    let mut guess_used = 0u32;
+    let mut target_used = 0u32;
+    let mut result: WordScore = 0;
+
+    // Greens first, so that they can't be used up as yellows.
+    let mut mult: WordScore = 1;
+    for i in 0..WORD_LENGTH {
+        if guess[i] == target[i] {
+            result += 2 * mult;
+            guess_used |= 1 << i;
+            target_used |= 1 << i;
+        }
+        mult = mult.wrapping_mul(3);
+    }
+
+    mult = 1;
+    for (i, &g) in guess.iter().enumerate() {
+        if guess_used & (1 << i) == 0 {
+            if let Some(j) =
+                (0..WORD_LENGTH).find(|&j| target_used & (1 << j) == 0 && target[j] == g)
+            {
+                target_used |= 1 << j;
+                result += mult;
+            }
+        }
+        mult = mult.wrapping_mul(3);
+    }
+
+    result
+}
+
+// A word as the raw bytes that the scoring works on. We're playing a game about guessing
+// English words, and the speed of scoring is the main limit in performance.
+pub type WordBytes = [u8; WORD_LENGTH];
+
+// View a word as WordBytes. Panics if it's the wrong length, same as score_word_pair.
+pub fn word_bytes(word: &str) -> &WordBytes {
+    match word.as_bytes().try_into() {
+        Ok(bytes) => bytes,
+        Err(_) => panic!("'{}' is not {} letters long", word, WORD_LENGTH),
+    }
+}
+
+// The guts of score_word_pair, for callers that have already converted their words to
+// WordBytes. The ranking does that once per list, which takes the length checks out of the
+// innermost loop entirely.
+//
+// This version is hand-unrolled, which makes it about 75% faster than the score_word_simple
+// above. It used to read the letters through unsafe pointers, but with fixed-size arrays and
+// constant indexes there's nothing for the compiler to bounds-check, so it's just as fast. It
+// should always generate the same output for the same inputs as the simple version.
+#[inline]
+pub fn score_word_bytes(guess: &WordBytes, target: &WordBytes) -> WordScore {
+    // The result. Starts at 0 for no matches; as we find matches
+    // we'll add values in.
+    let mut result: WordScore = 0;
+
+    // A bitfield for the letters of the guess and the target. We
+    // mark these off as they're paired up.
+    let mut guess_used = 0u32;
+
+    // Match up all of the "right letter in right place" pairs FIRST,
+    // and mark them off as so they won't be checked later. If we're
+    // matching "cheer" against "abbey" we want to have the SECOND 'e'
+    // in "cheEr" be scored as a right-letter-right-place match, and
+    // do NOT want the FIRST 'e' to be scored as a right-letter-wrong-place
+    // match.
+    //
+    // When we find a match, add a 2 in the corresponding place in
+    // the score.
+    if guess[0] == target[0] {
+        result += 2;
+        guess_used |= 1;
+    }
+
+    if guess[1] == target[1] {
+        result += 6;
+        guess_used |= 2;
+    }
+
+    if guess[2] == target[2] {
+        result += 18;
+        guess_used |= 4;
+    }
+
+    if guess[3] == target[3] {
+        result += 54;
+        guess_used |= 8;
+    }
+
+    if guess[4] == target[4] {
+        result += 162;
+        guess_used |= 16;
+    }
+
+    // Now match the remaining letters, searching for other places.
+    // Here we have to consider 5*4 pairings.
+    //
+    // When we find a match, add a 1 in the corresponding place in
+    // the score.
+    let mut target_used = guess_used;
+
+    if (guess_used & 1) == 0 {
+        let g = guess[0];
+        if target[1] == g && (target_used & 2) == 0 {
+            target_used |= 2;
+            result += 1;
+        } else if target[2] == g && (target_used & 4) == 0 {
+            target_used |= 4;
+            result += 1;
+        } else if target[3] == g && (target_used & 8) == 0 {
+            target_used |= 8;
+            result += 1;
+        } else if target[4] == g && (target_used & 16) == 0 {
+            target_used |= 16;
+            result += 1;
+        }
+    }
+
+    if (guess_used & 2) == 0 {
+        let g = guess[1];
+        if target[0] == g && (target_used & 1) == 0 {
+            target_used |= 1;
+            result += 3;
+        } else if target[2] == g && (target_used & 4) == 0 {
+            target_used |= 4;
+            result += 3;
+        } else if target[3] == g && (target_used & 8) == 0 {
+            target_used |= 8;
+            result += 3;
+        } else if target[4] == g && (target_used & 16) == 0 {
+            target_used |= 16;
+            result += 3;
+        }
+    }
+
+    if (guess_used & 4) == 0 {
+        let g = guess[2];
+        if target[0] == g && (target_used & 1) == 0 {
+            target_used |= 1;
+            result += 9;
+        } else if target[1] == g && (target_used & 2) == 0 {
+            target_used |= 2;
+            result += 9;
+        } else if target[3] == g && (target_used & 8) == 0 {
+            target_used |= 8;
+            result += 9;
+        } else if target[4] == g && (target_used & 16) == 0 {
+            target_used |= 16;
+            result += 9;
+        }
+    }
+
+    if (guess_used & 8) == 0 {
+        let g = guess[3];
+        if target[0] == g && (target_used & 1) == 0 {
+            target_used |= 1;
+            result += 27;
+        } else if target[1] == g && (target_used & 2) == 0 {
+            target_used |= 2;
+            result += 27;
+        } else if target[2] == g && (target_used & 4) == 0 {
+            target_used |= 4;
+            result += 27;
+        } else if target[4] == g && (target_used & 16) == 0 {
+            target_used |= 16;
+            result += 27;
+        }
+    }
+
+    if (guess_used & 16) == 0 {
+        let g = guess[4];
+        if (target[0] == g && (target_used & 1) == 0)
+            || (target[1] == g && (target_used & 2) == 0)
+            || (target[2] == g && (target_used & 4) == 0)
+            || (target[3] == g && (target_used & 8) == 0)
+        {
+            result += 81;
+        }
+    }
+
+    debug_assert_eq!(
+        score_word_pair_simple(
+            std::str::from_utf8(guess).unwrap_or_default(),
+            std::str::from_utf8(target).unwrap_or_default()
+        ),
+        result,
+        "Optimized version of score_word_pair generated a different score from the simple version. guess={:?} target={:?}",
+        guess,
+        target
+    );
+
+    result
+}
+
+// How many targets go in each TargetBlock.
+pub const BLOCK_SIZE: usize = 64;
+
+// A block of targets laid out a letter at a time, rather than a word at a time: the first
+// letters of all of them, then all of the second letters, and so on. Scoring a guess against a
+// block then works through each column in a straight line, doing the same thing to every
+// target, which the compiler can turn into vector instructions that score lots of targets at
+// once. Going a word at a time, it's stuck doing one target at a time, with branches all over.
+#[derive(Clone)]
+pub struct TargetBlock {
+    letters: [[u8; BLOCK_SIZE]; WORD_LENGTH],
+
+    // How many of the places in the block have targets in them. The last block of a list is
+    // usually only partly full, and the rest of it is zeroes, which get scored and ignored.
+    len: usize,
+}
+
+// A whole list of targets, as TargetBlocks. English words only, like WordBytes. They're scored
+// by the rule they were made with.
+#[derive(Clone, Default)]
+pub struct TargetBlocks {
+    blocks: Vec<TargetBlock>,
+    len: usize,
+    policy: DuplicatePolicy,
+}
+
+impl TargetBlocks {
+    pub fn new(targets: &[WordBytes], policy: DuplicatePolicy) -> TargetBlocks {
+        let blocks = targets
+            .chunks(BLOCK_SIZE)
+            .map(|chunk| {
+                let mut letters = [[0u8; BLOCK_SIZE]; WORD_LENGTH];
+                for (lane, target) in chunk.iter().enumerate() {
+                    for (column, &letter) in letters.iter_mut().zip(target) {
+                        column[lane] = letter;
+                    }
+                }
+                TargetBlock {
+                    letters,
+                    len: chunk.len(),
+                }
+            })
+            .collect();
+        TargetBlocks {
+            blocks,
+            len: targets.len(),
+            policy,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn policy(&self) -> DuplicatePolicy {
+        self.policy
+    }
+
+    // The targets back out again, in the same order they went in.
+    pub fn words(&self) -> impl Iterator<Item = WordBytes> + '_ {
+        self.blocks.iter().flat_map(|block| {
+            (0..block.len).map(move |lane| {
+                let mut word = [0u8; WORD_LENGTH];
+                for (letter, column) in word.iter_mut().zip(&block.letters) {
+                    *letter = column[lane];
+                }
+                word
+            })
+        })
+    }
+
+    // The guess's score against every target, in the same order as the targets.
+    pub fn scores<'s>(&'s self, guess: &'s WordBytes) -> impl Iterator<Item = WordScore> + 's {
+        self.blocks.iter().flat_map(move |block| {
+            score_block(guess, block, self.policy)
+                .into_iter()
+                .take(block.len)
+        })
+    }
+
+    // Add up how many targets get each score, which is all the ranking needs to know.
+    pub fn add_to_histogram(&self, guess: &WordBytes, histogram: &mut [u16; NUM_SCORES]) {
+        for block in &self.blocks {
+            let scores = score_block(guess, block, self.policy);
+            for &score in &scores[..block.len] {
+                histogram[score as usize] += 1;
+            }
+        }
+    }
+}
+
+// Score a guess against a whole block of targets at once. This is the same scoring as
+// score_word_bytes, worked out a different way so that there aren't any branches that depend on
+// the target: a letter in the guess is yellow if it isn't green, and the target has more copies
+// of it (that aren't green themselves) than there are earlier copies in the guess that aren't
+// green either. Everything that depends only on the guess is worked out once, outside of the
+// loops over the targets.
+fn score_block(
+    guess: &WordBytes,
+    block: &TargetBlock,
+    policy: DuplicatePolicy,
+) -> [WordScore; BLOCK_SIZE] {
+    let targets = &block.letters;
+    let all_yellow = policy == DuplicatePolicy::AllYellow;
+
+    let mut green = [[0u8; BLOCK_SIZE]; WORD_LENGTH];
+    for i in 0..WORD_LENGTH {
+        for lane in 0..BLOCK_SIZE {
+            green[i][lane] = (targets[i][lane] == guess[i]) as u8;
+        }
+    }
+
+    let mut result = [0 as WordScore; BLOCK_SIZE];
+    let mut mult: WordScore = 1;
+    for i in 0..WORD_LENGTH {
+        let g = guess[i];
+
+        // How many copies of the letter the target has to hand out as yellows.
+        let mut available = [0u8; BLOCK_SIZE];
+        for (p, column) in targets.iter().enumerate() {
+            for lane in 0..BLOCK_SIZE {
+                let counts = if all_yellow { 1 } else { 1 - green[p][lane] };
+                available[lane] += (column[lane] == g) as u8 & counts;
+            }
+        }
+
+        // How many of them earlier copies in the guess have already used up. With every copy
+        // colored, none of them are ever used up.
+        let mut used = [0u8; BLOCK_SIZE];
+        if !all_yellow {
+            for j in (0..i).filter(|&j| guess[j] == g) {
+                for lane in 0..BLOCK_SIZE {
+                    used[lane] += 1 - green[j][lane];
+                }
+            }
+        }
+
+        for lane in 0..BLOCK_SIZE {
+            let is_green = green[i][lane];
+            let is_yellow = (1 - is_green) & (used[lane] < available[lane]) as u8;
+            result[lane] += (2 * is_green + is_yellow) * mult;
+        }
+        mult = mult.wrapping_mul(3);
+    }
+
+    if cfg!(debug_assertions) {
+        for (lane, &score) in result.iter().enumerate().take(block.len) {
+            let mut target = [0u8; WORD_LENGTH];
+            for (letter, column) in target.iter_mut().zip(targets) {
+                *letter = column[lane];
+            }
+            debug_assert_eq!(
+                score,
+                policy.score_bytes(guess, &target),
+                "Block scoring generated a different score from score_word_bytes. guess={:?} target={:?}",
+                guess,
+                target
+            );
+        }
+    }
+
+    result
+}
+
+// How many of the words a score ruled out went because of each color in it, to show which part
+// of the score did the work. A word can break the rules of more than one color, so they're
+// checked in order: the greens get the credit for every word that has the wrong letter in a
+// green place, then the yellows for the rest that are missing a yellow letter (or have it right
+// where it was yellow), and the grays for whatever's left, which has a letter it can't have, or
+// too many copies of one.
+#[derive(Clone, Copy, Default)]
+pub struct Elimination {
+    pub greens: usize,
+    pub yellows: usize,
+    pub grays: usize,
+}
+
+pub fn explain_elimination(
+    guess: &str,
+    score: WordScore,
+    removed: &[&str],
+    policy: DuplicatePolicy,
+) -> Elimination {
+    let letters = word_chars(guess);
+    let codes = format_score(score);
+    let codes = codes.as_bytes();
+
+    // How many copies of each yellow letter the word has to have. With AllYellow, every copy
+    // of a letter in the word gets colored, however many the answer has, so it's just one.
+    let needed = |letter: char| {
+        if policy == DuplicatePolicy::AllYellow {
+            1
+        } else {
+            (0..WORD_LENGTH)
+                .filter(|&i| letters[i] == letter && codes[i] != b'.')
+                .count()
+        }
+    };
+
+    let mut elimination = Elimination::default();
+    for word in removed {
+        let word = word_chars(word);
+        let breaks_green = (0..WORD_LENGTH).any(|i| codes[i] == b'G' && word[i] != letters[i]);
+        let breaks_yellow = (0..WORD_LENGTH).any(|i| {
+            codes[i] == b'y'
+                && (word[i] == letters[i]
+                    || word.iter().filter(|&&c| c == letters[i]).count() < needed(letters[i]))
+        });
+        if breaks_green {
+            elimination.greens += 1;
+        } else if breaks_yellow {
+            elimination.yellows += 1;
+        } else {
+            elimination.grays += 1;
+        }
+    }
+    elimination
+}
+
+// Whether any word at all could give this score for this guess, even one that isn't in any of
+// the lists. See score_impossibility.
+pub fn is_possible_score(guess: &str, score: WordScore, policy: DuplicatePolicy) -> bool {
+    score_impossibility(guess, score, policy).is_none()
+}
+
+// Why no word at all could give this score for this guess, or None if some word could. Some
+// scores can't happen: there's no way to get four greens and a yellow, and since yellows are
+// handed out from left to right, a gray letter can't come before a yellow copy of the same letter.
+//
+// Otherwise, it's possible if every yellow can be given its own place in the word: a place that
+// isn't green, and where the guess has a different letter (or that place would be green).
+pub fn score_impossibility(
+    guess: &str,
+    score: WordScore,
+    policy: DuplicatePolicy,
+) -> Option<String> {
+    let letters = word_chars(guess);
+    let codes = format_score(score);
+    let codes = codes.as_bytes();
+    if policy == DuplicatePolicy::AllYellow {
+        return all_yellow_impossibility(&letters, codes);
+    }
+
+    // Yellows before grays, for each letter.
+    for i in 0..WORD_LENGTH {
+        if let Some(j) = (i + 1..WORD_LENGTH)
+            .find(|&j| codes[i] == b'.' && letters[j] == letters[i] && codes[j] == b'y')
+        {
+            return Some(format!(
+                "letter {} is a gray '{}', but letter {} is a yellow one, and the first copy of a letter is always the one that turns yellow",
+                i + 1,
+                letters[i].to_uppercase(),
+                j + 1
+            ));
+        }
+    }
+
+    let yellows = (0..WORD_LENGTH)
+        .filter(|&i| codes[i] == b'y')
+        .collect::<Vec<_>>();
+    let places = (0..WORD_LENGTH)
+        .filter(|&i| codes[i] != b'G')
+        .collect::<Vec<_>>();
+
+    // There are at most five yellows, so trying every assignment is cheap.
+    fn assign(
+        yellows: &[usize],
+        places: &[usize],
+        used: u32,
+        letters: &[char; WORD_LENGTH],
+    ) -> bool {
+        match yellows.split_first() {
+            None => true,
+            Some((&yellow, rest)) => places.iter().enumerate().any(|(k, &place)| {
+                used & (1 << k) == 0
+                    && letters[place] != letters[yellow]
+                    && assign(rest, places, used | (1 << k), letters)
+            }),
+        }
+    }
+    if assign(&yellows, &places, 0, &letters) {
+        return None;
+    }
+
+    // Say which letter doesn't fit, if it's down to one letter. Otherwise, it's the yellows
+    // between them that don't fit.
+    for &yellow in &yellows {
+        let letter = letters[yellow];
+        let num_yellow = yellows.iter().filter(|&&i| letters[i] == letter).count();
+        let room = places.iter().filter(|&&i| letters[i] != letter).count();
+        if num_yellow > room {
+            return Some(format!(
+                "a yellow '{}' means there's one somewhere else in the word, but {}",
+                letter.to_uppercase(),
+                match room {
+                    0 => "every other place is green".to_string(),
+                    1 => "only one other place isn't green".to_string(),
+                    _ => format!("only {} other places aren't green", room),
+                }
+            ));
+        }
+    }
+    Some(format!(
+        "the {} yellow letters each need a place that isn't green, somewhere other than where they are, and there aren't enough of those to go around",
+        yellows.len()
+    ))
+}
+
+// The same as score_impossibility, when every copy of a letter gets colored. Then a gray letter
+// means there are none at all, so no other copy of it can be colored, and a yellow one only
+// needs the letter to be somewhere in the word: in a green, or in some place where the guess
+// has a different letter that wasn't green.
+fn all_yellow_impossibility(letters: &[char; WORD_LENGTH], codes: &[u8]) -> Option<String> {
+    for i in 0..WORD_LENGTH {
+        if codes[i] != b'.' {
+            continue;
+        }
+        if let Some(j) = (0..WORD_LENGTH).find(|&j| letters[j] == letters[i] && codes[j] != b'.') {
+            return Some(format!(
+                "letter {} is a gray '{}', which means there isn't one anywhere, but letter {} is colored",
+                i + 1,
+                letters[i].to_uppercase(),
+                j + 1
+            ));
+        }
+    }
+
+    for i in 0..WORD_LENGTH {
+        let letter = letters[i];
+        let has_room = (0..WORD_LENGTH).any(|j| (codes[j] == b'G') == (letters[j] == letter));
+        if codes[i] == b'y' && !has_room {
+            return Some(format!(
+                "a yellow '{}' means there's one somewhere else in the word, but every place has one that isn't there",
+                letter.to_uppercase()
+            ));
+        }
+    }
+    None
+}
+
+// The same as score_word_pair, but for words that haven't been checked yet. Returns an error
+// instead of panicking if either one is the wrong length.
+pub fn try_score_word_pair(guess: &str, target: &str) -> Result<WordScore> {
+    for word in [guess, target] {
+        if word_length(word) != WORD_LENGTH {
+            return Err(Error::InvalidWordLength(word.to_string()));
+        }
+    }
+    Ok(score_word_pair(guess, target))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICIES: [DuplicatePolicy; 2] = [DuplicatePolicy::Standard, DuplicatePolicy::AllYellow];
+
+    // The block scorer has to give every guess the same score against every target as
+    // score_word_bytes does, by either rule, and the histograms have to add up the same too.
+    // The debug_assert in score_block checks the same thing, but only in debug builds.
+    fn check_blocks(guesses: &[WordBytes], targets: &[WordBytes]) {
+        for policy in POLICIES {
+            let blocks = TargetBlocks::new(targets, policy);
+            assert_eq!(blocks.len(), targets.len());
+            assert!(blocks.words().eq(targets.iter().copied()));
+            for guess in guesses {
+                let mut expected_histogram = [0u16; NUM_SCORES];
+                let mut count = 0;
+                for (target, score) in targets.iter().zip(blocks.scores(guess)) {
+                    let expected = policy.score_bytes(guess, target);
+                    assert_eq!(
+                        score,
+                        expected,
+                        "{:?}: {} against {} scored {} from the blocks, {} from score_word_bytes",
+                        policy,
+                        String::from_utf8_lossy(guess),
+                        String::from_utf8_lossy(target),
+                        format_score(score),
+                        format_score(expected)
+                    );
+                    expected_histogram[expected as usize] += 1;
+                    count += 1;
+                }
+                assert_eq!(count, targets.len());
+
+                let mut histogram = [0u16; NUM_SCORES];
+                blocks.add_to_histogram(guess, &mut histogram);
+                assert_eq!(histogram, expected_histogram);
+            }
+        }
+    }
+
+    // Every word of WORD_LENGTH letters made of just these, which goes through every way the
+    // copies of a letter can line up.
+    fn words_from(letters: &[u8]) -> Vec<WordBytes> {
+        let mut words = vec![[0u8; WORD_LENGTH]];
+        for position in 0..WORD_LENGTH {
+            words = words
+                .iter()
+                .flat_map(|word| {
+                    letters.iter().map(move |&letter| {
+                        let mut word = *word;
+                        word[position] = letter;
+                        word
+                    })
+                })
+                .collect();
+        }
+        words
+    }
+
+    #[test]
+    fn blocks_match_on_repeated_letters() {
+        // 243 words, so the last block is only partly full.
+        let words = words_from(b"abc");
+        check_blocks(&words, &words);
+
+        // The pairs that trip up scorers, from the test vectors.
+        let pairs = [
+            ("cheer", "abbey"),
+            ("geese", "those"),
+            ("speed", "abide"),
+            ("speed", "erase"),
+            ("eerie", "lever"),
+            ("abbey", "kebab"),
+            ("error", "rover"),
+            ("mamma", "maxim"),
+            ("sassy", "assay"),
+            ("fluff", "offal"),
+        ];
+        for (guess, target) in pairs {
+            let (guess, target) = (*word_bytes(guess), *word_bytes(target));
+            check_blocks(&[guess, target], &[guess, target]);
+        }
+    }
+
+    #[cfg(feature = "embedded-lists")]
+    #[test]
+    fn blocks_match_on_the_embedded_lists() {
+        let bytes = |list: &[&str]| list.iter().map(|w| *word_bytes(w)).collect::<Vec<_>>();
+        let solutions = bytes(crate::wordle_solutions::WORDLE_SOLUTION_LIST);
+
+        // A spread of the guesses, answers and not, against every answer. All of them would
+        // take minutes in a debug build.
+        let guesses = crate::scrabble_word_list::SCRABBLE_WORD_LIST
+            .iter()
+            .step_by(29)
+            .map(|w| *word_bytes(w))
+            .collect::<Vec<_>>();
+        check_blocks(&guesses, &solutions);
+    }
+
+    #[test]
+    fn scores_parse_in_every_notation() {
+        // .y..G, the example from parse_score.
+        let expected = 165;
+        for readable in [
+            ".y..G",
+            "-y--g",
+            "_Y__2",
+            "x1X02",
+            "01002",
+            " . y . . G ",
+            "⬛🟨⬛⬛🟩",
+            "⬜🟨⬜⬜🟩",
+            // High contrast mode, and the invisible markers that come with copied squares.
+            "⬛🟦⬛⬛🟧",
+            "⬛\u{fe0f}🟨⬛\u{fe0f}⬛\u{fe0f}🟩",
+        ] {
+            assert_eq!(parse_score(readable).ok(), Some(expected), "{:?}", readable);
+        }
+    }
+
+    #[test]
+    fn scores_parse_with_mixed_notations() {
+        assert_eq!(parse_score("0y-🟩2").ok(), parse_score(".y.GG").ok());
+        assert_eq!(parse_score("G⬜1_g").ok(), parse_score("G.y.G").ok());
+        assert_eq!(parse_score_values("2y🟨.").ok(), Some(vec![2, 1, 1, 0]));
+    }
+
+    #[test]
+    fn scores_with_other_letters_are_rejected() {
+        for (readable, letter, position) in [
+            ("..z..", 'z', 3),
+            ("3....", '3', 1),
+            ("....🟥", '🟥', 5),
+            // A bad letter gets pointed out even when the length is wrong too.
+            ("gyq", 'q', 3),
+            ("g y . . . k", 'k', 6),
+        ] {
+            match parse_score(readable) {
+                Err(Error::InvalidScoreLetter(_, c, p)) => {
+                    assert_eq!((c, p), (letter, position), "{:?}", readable)
+                }
+                other => panic!(
+                    "{:?} parsed as {:?}",
+                    readable,
+                    other.map(|s| format_score(s).to_string())
+                ),
+            }
+        }
+    }
+
+    #[test]
+    fn scores_of_the_wrong_length_are_rejected() {
+        for readable in ["", "   ", "....", "......", "GGGGGG", "🟩🟩"] {
+            assert!(
+                matches!(parse_score(readable), Err(Error::InvalidScore(_))),
+                "{:?}",
+                readable
+            );
+        }
+        assert_eq!(parse_score_values("").ok(), Some(vec![]));
+        assert_eq!(
+            parse_score_values("yy..GG").ok(),
+            Some(vec![1, 1, 0, 0, 2, 2])
+        );
+    }
+
+    #[test]
+    fn impossible_scores_are_rejected() {
+        // The guess, the score, and whether it can happen by each rule.
+        let cases = [
+            // A gray 'e' before a yellow one: by the standard rule the first copy is the one
+            // that gets colored, and by the other rule a gray means there aren't any.
+            ("speed", "...y.", false, false),
+            // A yellow 'e' and a gray one is fine by the standard rule, since there's only the
+            // one 'e' and it's somewhere else.
+            ("speed", "..y..", true, false),
+            // The yellow 'e' has nowhere else to go but where the green ones are. Coloring every
+            // copy, the green 'e's are enough to make it yellow.
+            ("eerie", "GyGGG", false, true),
+            ("crane", "GGGGy", false, false),
+            // Two yellow 'e's with only one place left to put them.
+            ("eerie", "yyGGG", false, true),
+            // These can all happen.
+            ("crane", ".y..G", true, true),
+            ("speed", "GGGGG", true, true),
+            ("speed", ".....", true, true),
+            ("abbey", ".yG..", true, true),
+            ("geese", ".yy.G", true, true),
+        ];
+        for (guess, readable, standard, all_yellow) in cases {
+            let score = parse_score(readable).unwrap();
+            for (policy, possible) in POLICIES.into_iter().zip([standard, all_yellow]) {
+                let reason = score_impossibility(guess, score, policy);
+                assert_eq!(
+                    reason.is_none(),
+                    possible,
+                    "{:?}: {} scored {}: {:?}",
+                    policy,
+                    guess,
+                    readable,
+                    reason
+                );
+                assert_eq!(is_possible_score(guess, score, policy), possible);
+            }
+        }
+    }
+
+    #[test]
+    fn possible_scores_are_exactly_the_ones_some_word_gets() {
+        // Every guess made of three letters, against every word made of those and one more,
+        // which stands in for all the letters that aren't in the guess.
+        let guesses = words_from(b"abc");
+        let targets = words_from(b"abcd");
+        for policy in POLICIES {
+            for guess in &guesses {
+                let mut reached = [false; NUM_SCORES];
+                for target in &targets {
+                    reached[policy.score_bytes(guess, target) as usize] = true;
+                }
+                let guess = std::str::from_utf8(guess).unwrap();
+                for (score, &reached) in reached.iter().enumerate() {
+                    assert_eq!(
+                        score_impossibility(guess, score as WordScore, policy).is_none(),
+                        reached,
+                        "{:?}: {} scored {}",
+                        policy,
+                        guess,
+                        format_score(score as WordScore)
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::guess_quality::*;
+use crate::score::*;
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+// Ranking guesses means scoring every guess against every possible answer, over and over: the
+// simulator does it for every turn of thousands of games, with the same words each time. Given
+// enough memory, it's quicker to score every pair once up front and just look the scores up
+// after that. A full table for the usual lists is about 65 MB, though, which is fine on a
+// desktop and not so fine on a small machine, so how much gets worked out ahead of time depends
+// on how much memory it's allowed.
+
+// How much to work out ahead of time.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Precomputation {
+    // Every guess against every answer.
+    FullTable,
+
+    // Only this many of the guesses against every answer. The rest get scored as they're needed.
+    PartialTable(usize),
+
+    // Score everything as it's needed, the same as without a table.
+    Nothing,
+}
+
+impl Precomputation {
+    // The most that fits in the memory allowed, for a game that starts with this many guesses
+    // and answers.
+    pub fn choose(num_guesses: usize, num_targets: usize, max_bytes: usize) -> Precomputation {
+        let row_bytes = num_targets.max(1) * std::mem::size_of::<WordScore>();
+        let rows = max_bytes / row_bytes;
+        if rows >= num_guesses {
+            Precomputation::FullTable
+        } else if rows > 0 {
+            Precomputation::PartialTable(rows)
+        } else {
+            Precomputation::Nothing
+        }
+    }
+
+    // How many guesses get a row in the table.
+    pub fn rows(self, num_guesses: usize) -> usize {
+        match self {
+            Precomputation::FullTable => num_guesses,
+            Precomputation::PartialTable(rows) => rows.min(num_guesses),
+            Precomputation::Nothing => 0,
+        }
+    }
+}
+
+// The scores for some of the guesses against all of the answers a game started with.
+pub struct ScoreTable {
+    policy: DuplicatePolicy,
+    guess_rows: HashMap<&'static str, usize>,
+    target_columns: HashMap<&'static str, usize>,
+    scores: Vec<WordScore>,
+}
+
+impl ScoreTable {
+    // Score the first `rows` guesses against every target, by the given rule for repeated
+    // letters. The guesses that could also be the answer come first, since they're the ones that
+    // get played the most late in a game, and the only ones left at all in hard mode.
+    pub fn new(
+        guesses: &[&'static str],
+        targets: &[&'static str],
+        rows: usize,
+        policy: DuplicatePolicy,
+    ) -> ScoreTable {
+        let target_columns: HashMap<&'static str, usize> = targets
+            .iter()
+            .enumerate()
+            .map(|(column, &target)| (target, column))
+            .collect();
+        let mut ordered = guesses.to_vec();
+        ordered.sort_by_key(|g| !target_columns.contains_key(g));
+        ordered.truncate(rows);
+
+        // English words get scored the quick way, the same as when ranking.
+        let quick = targets.iter().chain(&ordered).all(|w| w.is_ascii());
+        let mut scores = vec![0; ordered.len() * targets.len()];
+        scores
+            .par_chunks_mut(targets.len().max(1))
+            .zip(ordered.par_iter())
+            .for_each(|(row, &guess)| {
+                for (score, &target) in row.iter_mut().zip(targets) {
+                    *score = if quick {
+                        policy.score_bytes(word_bytes(guess), word_bytes(target))
+                    } else {
+                        policy.score(guess, target)
+                    };
+                }
+            });
+
+        ScoreTable {
+            policy,
+            guess_rows: ordered
+                .iter()
+                .enumerate()
+                .map(|(row, &guess)| (guess, row))
+                .collect(),
+            target_columns,
+            scores,
+        }
+    }
+
+    // The quality of every guess, in the same order as the guesses, the same as
+    // score_all_guesses works out, but looking up whatever scores are in the table. Targets
+    // that aren't in it can't be looked up at all, and then there's nothing.
+    pub(crate) fn score_all_guesses<'a>(
+        &self,
+        guesses: &[&'a str],
+        targets: &[&str],
+        weights: Option<&[f64]>,
+    ) -> Option<Vec<GuessQuality<'a>>> {
+        let columns = targets
+            .iter()
+            .map(|t| self.target_columns.get(t).copied())
+            .collect::<Option<Vec<_>>>()?;
+        let num_columns = self.target_columns.len();
+
+        Some(score_all_guesses_by(
+            guesses,
+            targets,
+            weights,
+            self.policy,
+            |guess| {
+                let row = *self.guess_rows.get(guess)?;
+                let row = &self.scores[row * num_columns..][..num_columns];
+                let scores = columns.iter().map(|&column| row[column]);
+                Some(quality_from_scores(guess, scores, targets.len(), weights))
+            },
+        ))
+    }
+}
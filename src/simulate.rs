@@ -0,0 +1,357 @@
+use crate::guess_quality::*;
+use crate::score::*;
+use crate::solver::*;
+use crate::strategy::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+
+// The simulator plays a complete game against every possible answer, using some strategy to
+// pick each guess, and reports how many guesses it took. Besides the real strategies, there are
+// a few deliberately naive ones to compare them against. Without them an average of "3.6
+// guesses" doesn't mean much -- with them, it's obvious how much a strategy is actually adding,
+// and obvious when a change to it makes things worse.
+
+// Give up on a game after this many guesses. Even the random strategy only ever guesses words
+// that could be the answer, so it always finishes well before this.
+pub const MAX_SIMULATED_GUESSES: usize = 20;
+
+// Wordle itself gives you six guesses. Games that take longer than this count as failures.
+pub const WORDLE_MAX_GUESSES: usize = 6;
+
+// One simulated game: the answer, and the guesses played to find it, in order.
+pub struct GameResult {
+    pub answer: &'static str,
+    pub guesses: Vec<&'static str>,
+}
+
+impl GameResult {
+    pub fn solved(&self) -> bool {
+        self.guesses.last() == Some(&self.answer)
+    }
+}
+
+// A random number generator for anything that makes random choices. With a seed, it makes the
+// same choices every time, so a run can be repeated exactly. Without one, it's seeded from the
+// system.
+pub fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+// Play a full game against every word the solver starts out considering as a possible answer.
+//
+// The games run in parallel, in no particular order, so each one gets its own random number
+// generator. With a seed, game N's generator is seeded from seed + N, and the whole run comes
+// out the same every time.
+pub fn simulate(start: &Solver, strategy: &dyn Strategy, seed: Option<u64>) -> Vec<GameResult> {
+    let games = (0..start.remaining_targets().len()).collect::<Vec<_>>();
+    Simulation::new(start, strategy, seed).play(&games)
+}
+
+// The same games, a few at a time, for runs that are long enough to want saving as they go.
+// The games come out the same whichever order they're played in, and however they're split up.
+pub struct Simulation<'s> {
+    start: &'s Solver,
+    strategy: &'s dyn Strategy,
+    seed: Option<u64>,
+
+    // Deterministic strategies make the same choice every time they see the same guesses and
+    // scores, so we remember those choices. Every game shares the same first guess, and most of
+    // them share a second guess with many others, which saves most of the work.
+    choices: Mutex<HashMap<Vec<(&'static str, WordScore)>, &'static str>>,
+}
+
+impl<'s> Simulation<'s> {
+    pub fn new(start: &'s Solver, strategy: &'s dyn Strategy, seed: Option<u64>) -> Simulation<'s> {
+        Simulation {
+            start,
+            strategy,
+            seed,
+            choices: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // Play some of the games: game N is against the start's Nth remaining target.
+    pub fn play(&self, games: &[usize]) -> Vec<GameResult> {
+        games.par_iter().map(|&game| self.play_game(game)).collect()
+    }
+
+    fn play_game(&self, game: usize) -> GameResult {
+        let answer = self.start.remaining_targets()[game];
+        let mut rng = seeded_rng(self.seed.map(|seed| seed.wrapping_add(game as u64)));
+        let mut solver = self.start.clone();
+        let mut transcript = Vec::new();
+        let mut guesses = Vec::new();
+        while guesses.len() < MAX_SIMULATED_GUESSES {
+            let deterministic = self.strategy.is_deterministic();
+            let cached = if deterministic {
+                self.choices.lock().unwrap().get(&transcript).copied()
+            } else {
+                None
+            };
+            let guess = cached.unwrap_or_else(|| {
+                let guess = self.strategy.choose_guess(&solver, &mut rng);
+                if deterministic {
+                    self.choices
+                        .lock()
+                        .unwrap()
+                        .insert(transcript.clone(), guess);
+                }
+                guess
+            });
+
+            guesses.push(guess);
+            if guess == answer {
+                break;
+            }
+
+            let score = solver.score(guess, answer);
+            solver
+                .apply_guess(guess, score)
+                .expect("the word lists only contain five-letter words");
+            transcript.push((guess, score));
+        }
+
+        GameResult { answer, guesses }
+    }
+}
+
+// Play a strategy against a host that cheats, like Absurdle: there's no answer picked ahead of
+// time, and every guess gets whichever score keeps the most words in play. The game only ends
+// once there's one word left and the strategy guesses it. It's a test of the worst case rather
+// than the average -- a strategy that gambles does badly here, however well it does normally.
+//
+// The host's choices don't depend on anything random, so the only randomness is the strategy's.
+// The answer in the result is the word the host was finally cornered into.
+pub fn play_absurdle(start: &Solver, strategy: &dyn Strategy, seed: Option<u64>) -> GameResult {
+    let mut rng = seeded_rng(seed);
+    let mut solver = start.clone();
+    let mut guesses = Vec::new();
+    while guesses.len() < MAX_SIMULATED_GUESSES {
+        let guess = strategy.choose_guess(&solver, &mut rng);
+        guesses.push(guess);
+
+        let score = absurdle_score(&solver, guess);
+        if score == ALL_GREEN {
+            return GameResult {
+                answer: guess,
+                guesses,
+            };
+        }
+        solver
+            .apply_guess(guess, score)
+            .expect("the word lists only contain five-letter words");
+    }
+
+    // Out of guesses. Whatever's left could still be the answer, so any of them will do.
+    GameResult {
+        answer: solver
+            .remaining_targets()
+            .first()
+            .copied()
+            .unwrap_or_default(),
+        guesses,
+    }
+}
+
+// The score the cheating host gives a guess: the one that leaves the most words. Ties go to the
+// score that gives away the least, with the fewest greens and then the fewest yellows, and then
+// to the lowest-numbered score so it's always the same one.
+fn absurdle_score(solver: &Solver, guess: &str) -> WordScore {
+    let groups =
+        guess_details(guess, solver.remaining_targets(), solver.duplicate_policy()).histogram;
+    let colors = |score: WordScore| {
+        let formatted = format_score(score);
+        (
+            formatted.matches('G').count(),
+            formatted.matches('y').count(),
+        )
+    };
+    (0..=ALL_GREEN)
+        .filter(|&score| groups[score as usize] > 0)
+        .max_by(|&a, &b| {
+            groups[a as usize]
+                .cmp(&groups[b as usize])
+                .then_with(|| colors(b).cmp(&colors(a)))
+                .then_with(|| b.cmp(&a))
+        })
+        .unwrap_or(ALL_GREEN)
+}
+
+// A point in a game where the guess played would have been turned down in hard mode: the
+// guesses and scores up to there, the guess, and what's wrong with it.
+#[derive(Clone, Debug)]
+pub struct HardModeViolation {
+    pub history: Vec<(&'static str, WordScore)>,
+    pub guess: &'static str,
+    pub reason: String,
+}
+
+impl std::fmt::Display for HardModeViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.history.is_empty() {
+            write!(f, "at the start")?;
+        } else {
+            write!(f, "after")?;
+            for (guess, score) in &self.history {
+                write!(f, " {} {}", guess.to_uppercase(), format_score(*score))?;
+            }
+        }
+        write!(f, ", {}: {}", self.guess.to_uppercase(), self.reason)
+    }
+}
+
+// Check that every guess in some finished games could really have been played in hard mode,
+// from where the solver started. A plan with even one guess the game won't accept can't be
+// followed, so it's worth knowing about even if it did well otherwise. Many games go through
+// the same guesses, so each point in the plan is only reported once. There's nothing to report
+// if the solver isn't in hard mode.
+pub fn hard_mode_violations(start: &Solver, games: &[GameResult]) -> Vec<HardModeViolation> {
+    if !start.is_hard() {
+        return Vec::new();
+    }
+
+    let mut seen = HashSet::new();
+    let mut violations = Vec::new();
+    for game in games {
+        let mut knowledge = start.knowledge().clone();
+        let mut history = Vec::new();
+        for &guess in &game.guesses {
+            if start.history().len() + history.len() >= start.hard_after() {
+                if let Some(reason) = knowledge.hard_mode_violation(guess) {
+                    if seen.insert((history.clone(), guess)) {
+                        violations.push(HardModeViolation {
+                            history: history.clone(),
+                            guess,
+                            reason,
+                        });
+                    }
+                }
+            }
+            let score = start.score(guess, game.answer);
+            knowledge.record(guess, score, start.duplicate_policy());
+            history.push((guess, score));
+        }
+    }
+    violations.sort_by(|a, b| a.history.cmp(&b.history).then(a.guess.cmp(b.guess)));
+    violations
+}
+
+// The headline numbers for a batch of simulated games.
+pub struct SimulationSummary {
+    pub games: usize,
+    pub average_guesses: f64,
+    pub worst_guesses: usize,
+    pub failures: usize,
+
+    // How many games took each number of guesses: distribution[3] is the number solved in
+    // three. Unsolved games are counted at MAX_SIMULATED_GUESSES.
+    pub distribution: Vec<usize>,
+}
+
+pub fn summarize(results: &[GameResult]) -> SimulationSummary {
+    let total: usize = results.iter().map(|r| r.guesses.len()).sum();
+    let mut distribution = vec![0; MAX_SIMULATED_GUESSES + 1];
+    for r in results {
+        distribution[r.guesses.len().min(MAX_SIMULATED_GUESSES)] += 1;
+    }
+    SimulationSummary {
+        games: results.len(),
+        average_guesses: total as f64 / results.len().max(1) as f64,
+        worst_guesses: results.iter().map(|r| r.guesses.len()).max().unwrap_or(0),
+        failures: results
+            .iter()
+            .filter(|r| !r.solved() || r.guesses.len() > WORDLE_MAX_GUESSES)
+            .count(),
+        distribution,
+    }
+}
+
+// How two sets of games against the same answers compare, answer by answer. "Wins" are the
+// answers the first set found in fewer guesses.
+pub struct HeadToHead {
+    pub wins: usize,
+    pub losses: usize,
+    pub ties: usize,
+
+    // The first set's average number of guesses minus the second's. Negative means the first
+    // set did better.
+    pub mean_difference: f64,
+    pub standard_error: f64,
+
+    // The chance of a difference at least this big if the two were really just as good, from
+    // a paired t-test on the differences. With thousands of answers, the normal distribution is
+    // as good as the t distribution here.
+    pub t_test_p: f64,
+
+    // The same, from a sign test, which only looks at who won each answer and not by how much.
+    // It doesn't care how the differences are spread out, which the t-test does a little.
+    pub sign_test_p: f64,
+}
+
+// Compare two sets of games, pairing them up by answer. Answers that only one of them played
+// are left out.
+pub fn head_to_head(first: &[GameResult], second: &[GameResult]) -> HeadToHead {
+    let second_guesses: HashMap<&str, usize> = second
+        .iter()
+        .map(|game| (game.answer, game.guesses.len()))
+        .collect();
+    let differences = first
+        .iter()
+        .filter_map(|game| {
+            second_guesses
+                .get(game.answer)
+                .map(|&other| game.guesses.len() as f64 - other as f64)
+        })
+        .collect::<Vec<_>>();
+
+    let n = differences.len().max(1) as f64;
+    let mean_difference = differences.iter().sum::<f64>() / n;
+    let variance = differences
+        .iter()
+        .map(|d| (d - mean_difference).powi(2))
+        .sum::<f64>()
+        / (n - 1.0).max(1.0);
+    let standard_error = (variance / n).sqrt();
+
+    let wins = differences.iter().filter(|&&d| d < 0.0).count();
+    let losses = differences.iter().filter(|&&d| d > 0.0).count();
+    let decided = (wins + losses) as f64;
+
+    HeadToHead {
+        wins,
+        losses,
+        ties: differences.len() - wins - losses,
+        mean_difference,
+        standard_error,
+        t_test_p: two_sided_p(mean_difference, standard_error),
+        sign_test_p: two_sided_p(wins as f64 - decided / 2.0, decided.sqrt() / 2.0),
+    }
+}
+
+// The chance of landing at least this far from zero, either way, on a normal distribution with
+// this standard deviation. Exactly zero is a sure thing, even with no spread at all.
+fn two_sided_p(value: f64, standard_deviation: f64) -> f64 {
+    if value == 0.0 {
+        1.0
+    } else if standard_deviation == 0.0 {
+        0.0
+    } else {
+        erfc(value.abs() / standard_deviation / std::f64::consts::SQRT_2)
+    }
+}
+
+// The complementary error function, from Abramowitz and Stegun's formula 7.1.26, which is good
+// to about seven decimal places. That's far more than a p-value needs.
+fn erfc(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.3275911 * x);
+    let polynomial = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    polynomial * (-x * x).exp()
+}
@@ -0,0 +1,526 @@
+use crate::error::*;
+use crate::guess_quality::*;
+use crate::knowledge::*;
+use crate::lists::*;
+use crate::score::*;
+use crate::score_table::*;
+#[cfg(feature = "embedded-lists")]
+use crate::word_tables::*;
+#[cfg(feature = "embedded-lists")]
+use crate::{scrabble_word_list, wordle_solutions};
+use itertools::Itertools;
+use rayon::prelude::*;
+use std::collections::*;
+use std::sync::Arc;
+
+// Which words are under consideration as possible solutions at the start of a game.
+#[derive(Clone, Copy)]
+pub enum TargetList {
+    // The N most common 5-letter English words that Wordle accepts as guesses.
+    Common(usize),
+
+    // Wordle's actual list of solution words, most common first.
+    Solutions,
+
+    // A short list of simple words for kids, most common first. This also limits the guesses,
+    // so that nothing outside of the list gets suggested.
+    Kids,
+}
+
+// The lists of words a game starts from. Normally these are the ones built into the program, but
+// newer ones can be swapped in, since Wordle's lists change from time to time.
+// Every word in them has to be exactly WORD_LENGTH lowercase letters.
+#[derive(Clone, Copy)]
+pub struct WordLists {
+    // Every word that Wordle accepts as a guess.
+    pub guesses: &'static [&'static str],
+
+    // Wordle's solution words.
+    pub solutions: &'static [&'static str],
+
+    // Whether these are exactly the built-in lists, so that the tables build.rs worked out
+    // from them can be used.
+    pub built_in: bool,
+}
+
+#[cfg(feature = "embedded-lists")]
+pub const BUILT_IN_WORD_LISTS: WordLists = WordLists {
+    guesses: scrabble_word_list::SCRABBLE_WORD_LIST,
+    solutions: wordle_solutions::WORDLE_SOLUTION_LIST,
+    built_in: true,
+};
+
+// The state of one game in progress: which words can still be guessed, and which words could
+// still be the answer. This is everything the interactive loop needs, without any of the
+// reading and printing, so it can be driven by other front ends as well.
+#[derive(Clone)]
+pub struct Solver {
+    hard: bool,
+
+    // How many guesses can be anything before hard mode's rules kick in. Some people play with
+    // a couple of free guesses to start with, and only then have to play by the rules.
+    hard_after: usize,
+
+    valid_guesses: Vec<&'static str>,
+    remaining_targets: Vec<&'static str>,
+    knowledge: Knowledge,
+
+    // Every guess so far, with the score it got.
+    history: Vec<(String, WordScore)>,
+
+    // Scores worked out ahead of time, if there's memory to spare. This is shared between
+    // copies of the solver, since it never changes.
+    score_table: Option<Arc<ScoreTable>>,
+
+    // Whether guesses are judged by how likely the words they'd leave are to be the answer,
+    // rather than just how many of them there are. See quality_from_scores.
+    weighted: bool,
+
+    // How repeated letters in a guess get colored in this game.
+    duplicates: DuplicatePolicy,
+}
+
+impl Solver {
+    #[cfg(feature = "embedded-lists")]
+    pub fn new(target_list: TargetList, hard: bool) -> Solver {
+        Solver::with_word_lists(target_list, hard, BUILT_IN_WORD_LISTS)
+    }
+
+    // The same as new, but starting from some other word lists than the built-in ones.
+    pub fn with_word_lists(target_list: TargetList, hard: bool, lists: WordLists) -> Solver {
+        // These are the words that Wordle considers valid guesses. It appears to be based on a
+        // Scrabble word list. While nearly all of these are in my dictionary, some are so obscure,
+        // so archaic, or so limited to specific technical contexts that no reasonable puzzle
+        // creator would use them for something the general public is expected to solve. But
+        // they're still useful as possible guesses -- even if a word is unlikely to be the used
+        // in writing or as the solution to a puzzle, its pattern of letters may be really effective
+        // at narrowing down the possible solutions.
+        //
+        // If we're playing in "hard mode", we'll shrink this list with each guess, so that you
+        // can only guess words that fit with your previous guesses. For normal mode we'll leave
+        // this entire list for consideration -- a word that won't win can sometimes be really
+        // effective at narrowing the possibilities for the target word.
+        let valid_guesses: Vec<&str> = match target_list {
+            TargetList::Kids => kids_words().to_vec(),
+            _ => lists.guesses.to_vec(),
+        };
+
+        // These are the words that are under consideration as possible solutions. It begins
+        // as a list of valid words that are in common enough usage that they could reasonably
+        // be chosen as the target word. With each guess, we'll cull the list of things that
+        // don't match the score for that guess.
+        //
+        // For the built-in lists, build.rs has already put all of these in order (see
+        // word_tables.rs), so they just get copied out.
+        let remaining_targets: Vec<&str> = match target_list {
+            #[cfg(feature = "embedded-lists")]
+            _ if lists.built_in && frequencies_are_built_in() && kids_words_are_built_in() => {
+                let ids: &[u16] = match target_list {
+                    TargetList::Common(count) => {
+                        &COMMON_GUESS_IDS[..count.min(COMMON_GUESS_IDS.len())]
+                    }
+                    TargetList::Solutions => &SOLUTION_IDS_BY_FREQUENCY,
+                    TargetList::Kids => &KIDS_IDS_BY_FREQUENCY,
+                };
+                ids.iter()
+                    .map(|&id| scrabble_word_list::SCRABBLE_WORD_LIST[id as usize])
+                    .collect()
+            }
+            TargetList::Solutions | TargetList::Kids => {
+                let frequency_hash: HashMap<&str, u32> =
+                    word_frequencies().iter().copied().collect();
+                let word_list = match target_list {
+                    TargetList::Kids => kids_words(),
+                    _ => lists.solutions,
+                };
+                word_list
+                    .iter()
+                    .map(|w| {
+                        (
+                            u32::MAX - frequency_hash.get(w).copied().unwrap_or_default(),
+                            *w,
+                        )
+                    })
+                    .sorted()
+                    .map(|(_freq, word)| word)
+                    .collect::<Vec<&str>>()
+            }
+            // Without a frequency list, there's no telling which words are common, so the
+            // answer list stands in for them, in its own order.
+            TargetList::Common(count) if word_frequencies().is_empty() => {
+                lists.solutions.iter().take(count).copied().collect()
+            }
+            TargetList::Common(count) => {
+                // The word frequency list is based on an analysis of in-the-wild English texts, so it
+                // includes acronyms, proper names, common typos and misspellings, perhaps OCR errors,
+                // etc. We filter it against the list of valid guesses to cull out things that Wordle
+                // doesn't consider words.
+                //
+                // Interesting note: If we filtered in the opposite direction, only about 2/3 of the
+                // "valid" words in the Scrabble word list are common enough to appear in the word
+                // frequency list at all!
+                let valid_guesses_hash: HashSet<&str> =
+                    HashSet::from_iter(valid_guesses.iter().copied());
+
+                // We also set a threshold of frequency, since the whole point of this is to limit the
+                // possible targets to words that are in common-enough usage that they might reasonably
+                // be chosen for a puzzle that the general public is expected to solve. A threshold of
+                // 1/20000 as common as the most-common word gives us just under 6000 words, with the
+                // the least-common being words like "yenta" and "cardy".
+                word_frequencies()
+                    .iter()
+                    .filter_map(|(word, _freq)| {
+                        if valid_guesses_hash.contains(word) {
+                            Some(*word)
+                        } else {
+                            None
+                        }
+                    })
+                    .take(count)
+                    .collect()
+            }
+        };
+
+        Solver {
+            hard,
+            hard_after: 0,
+            valid_guesses,
+            remaining_targets,
+            knowledge: Knowledge::new(),
+            history: Vec::new(),
+            score_table: None,
+            weighted: false,
+            duplicates: DuplicatePolicy::Standard,
+        }
+    }
+
+    // Judge the guesses by how likely the words they'd leave are (using answer_chances), so
+    // that a guess that leaves a handful of obscure words counts as better than one that leaves
+    // a few common ones.
+    pub fn set_weighted(&mut self, weighted: bool) {
+        self.weighted = weighted;
+    }
+
+    pub fn is_weighted(&self) -> bool {
+        self.weighted
+    }
+
+    // Score this game by some other rule for repeated letters than Wordle's. This has to be set
+    // before any guesses are played. Any scores worked out ahead of time went by the old rule,
+    // so they're thrown away, and precompute_scores has to be called again to get them back.
+    pub fn set_duplicate_policy(&mut self, policy: DuplicatePolicy) {
+        if policy != self.duplicates {
+            self.score_table = None;
+        }
+        self.duplicates = policy;
+    }
+
+    pub fn duplicate_policy(&self) -> DuplicatePolicy {
+        self.duplicates
+    }
+
+    // The score a guess gets against a target in this game.
+    pub fn score(&self, guess: &str, target: &str) -> WordScore {
+        self.duplicates.score(guess, target)
+    }
+
+    // Score the guesses against the possible answers up front, as much as `plan` allows, so
+    // that ranking them later is just a matter of looking the scores up. This is worth it for
+    // playing lots of games from the same start, like the simulator does.
+    pub fn precompute_scores(&mut self, plan: Precomputation) {
+        let rows = plan.rows(self.valid_guesses.len());
+        self.score_table = (rows > 0).then(|| {
+            Arc::new(ScoreTable::new(
+                &self.valid_guesses,
+                &self.remaining_targets,
+                rows,
+                self.duplicates,
+            ))
+        });
+    }
+
+    // Let the first few guesses be anything, even in hard mode. This has to be set before any
+    // guesses are played.
+    pub fn set_hard_after(&mut self, free_guesses: usize) {
+        self.hard_after = free_guesses;
+    }
+
+    pub fn is_hard(&self) -> bool {
+        self.hard
+    }
+
+    pub fn hard_after(&self) -> usize {
+        self.hard_after
+    }
+
+    // Whether the next guess has to play by hard mode's rules: hard mode is on, and the free
+    // guesses (if there are any) have been used up.
+    pub fn hard_mode_applies(&self) -> bool {
+        self.hard && self.history.len() >= self.hard_after
+    }
+
+    pub fn valid_guesses(&self) -> &[&'static str] {
+        &self.valid_guesses
+    }
+
+    pub fn remaining_targets(&self) -> &[&'static str] {
+        &self.remaining_targets
+    }
+
+    // What the guesses so far have shown about each letter.
+    pub fn knowledge(&self) -> &Knowledge {
+        &self.knowledge
+    }
+
+    // Every guess so far, with the score it got, in the order they were played.
+    pub fn history(&self) -> &[(String, WordScore)] {
+        &self.history
+    }
+
+    // Whether a word fits every score so far: it would have gotten the same scores if it were
+    // the answer. These are the only guesses hard mode allows.
+    pub fn fits_scores_so_far(&self, word: &str) -> bool {
+        self.history
+            .iter()
+            .all(|(guess, score)| self.score(guess, word) == *score)
+    }
+
+    // Whether a guess would break hard mode's rules if it were played next, and how. This goes
+    // by the real game's rules, which are looser than only playing words that fit the scores
+    // so far (see Knowledge::hard_mode_violation), so anything the solver suggests passes.
+    pub fn hard_mode_violation(&self, guess: &str) -> Option<String> {
+        if self.hard_mode_applies() {
+            self.knowledge.hard_mode_violation(guess)
+        } else {
+            None
+        }
+    }
+
+    // Only suggest guesses that pass some test, e.g. a WordFilter. The possible answers aren't
+    // affected.
+    pub fn filter_guesses(&mut self, keep: impl Fn(&str) -> bool) {
+        self.valid_guesses.retain(|w| keep(w));
+    }
+
+    // Cull the solution space to things that would give this score for this guess.
+    //
+    // If the score can't be right -- either no word at all could get it, or none of the
+    // remaining words would -- the solver is left as it was, and the error says which. That's
+    // almost always a typo in the guess or the score, so the caller can just ask again.
+    pub fn apply_guess(&mut self, guess: &str, score: WordScore) -> Result<()> {
+        if word_length(guess) != WORD_LENGTH {
+            return Err(Error::InvalidWordLength(guess.to_string()));
+        }
+        if let Some(reason) = score_impossibility(guess, score, self.duplicates) {
+            return Err(Error::InconsistentScore(
+                guess.to_string(),
+                format_score(score).to_string(),
+                reason,
+            ));
+        }
+        if !self
+            .remaining_targets
+            .iter()
+            .any(|w| self.score(guess, w) == score)
+        {
+            return Err(Error::EmptyCandidateSet(
+                guess.to_string(),
+                format_score(score).to_string(),
+            ));
+        }
+
+        let duplicates = self.duplicates;
+        self.remaining_targets
+            .retain(|w| duplicates.score(guess, w) == score);
+        self.knowledge.record(guess, score, duplicates);
+        self.history.push((guess.to_string(), score));
+
+        // If we're in hard mode, cull the list of valid guesses as well. Once the free guesses
+        // are over, the ones after have to fit their scores too, not just the scores from then
+        // on.
+        if self.hard_mode_applies() {
+            let history = &self.history;
+            self.valid_guesses.retain(|w| {
+                history
+                    .iter()
+                    .all(|(guess, score)| duplicates.score(guess, w) == *score)
+            });
+        }
+
+        // A word that's already been played can't tell us anything new, since we already know
+        // the score it gets. After an unlucky all-gray score it can still look good on paper,
+        // though, so it's taken out of the running entirely.
+        self.valid_guesses.retain(|w| *w != guess);
+
+        Ok(())
+    }
+
+    // How likely each remaining word is to be the answer, most likely first, assuming that
+    // puzzle setters pick common words more often than rare ones, in proportion to how common
+    // they are. Words that aren't in the frequency list at all get the smallest chance going.
+    pub fn answer_chances(&self) -> Vec<(&'static str, f64)> {
+        self.remaining_targets
+            .iter()
+            .copied()
+            .zip(self.answer_weights())
+            .sorted_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(b.0)))
+            .collect()
+    }
+
+    // A word's count from the built-in frequency list, looked up in the tables build.rs made,
+    // if that's the list in use and the word is one of the built-in guesses.
+    #[cfg(feature = "embedded-lists")]
+    fn built_in_frequency(&self, word: &str) -> Option<u32> {
+        frequencies_are_built_in()
+            .then(|| built_in_frequency(word))
+            .flatten()
+    }
+
+    #[cfg(not(feature = "embedded-lists"))]
+    fn built_in_frequency(&self, _word: &str) -> Option<u32> {
+        None
+    }
+
+    // The same chances, in the same order as remaining_targets.
+    fn answer_weights(&self) -> Vec<f64> {
+        // Words from downloaded lists might not be among the built-in guesses, and only then
+        // does the whole frequency list need searching.
+        let mut frequency_hash: Option<HashMap<&str, u32>> = None;
+        let weights = self
+            .remaining_targets
+            .iter()
+            .map(|w| {
+                let frequency = self.built_in_frequency(w).unwrap_or_else(|| {
+                    frequency_hash
+                        .get_or_insert_with(|| word_frequencies().iter().copied().collect())
+                        .get(w)
+                        .copied()
+                        .unwrap_or(0)
+                });
+                frequency.max(1) as f64
+            })
+            .collect::<Vec<_>>();
+        let total: f64 = weights.iter().sum();
+        weights.into_iter().map(|weight| weight / total).collect()
+    }
+
+    // The quality of every valid guess, using the score table if there is one.
+    fn score_all_guesses(&self) -> Vec<GuessQuality<'static>> {
+        self.score_guesses(&self.valid_guesses)
+    }
+
+    // The quality of each of some guesses, in the same order.
+    fn score_guesses(&self, guesses: &[&'static str]) -> Vec<GuessQuality<'static>> {
+        let weights = self.weighted.then(|| self.answer_weights());
+        let weights = weights.as_deref();
+        self.score_table
+            .as_ref()
+            .and_then(|table| table.score_all_guesses(guesses, &self.remaining_targets, weights))
+            .unwrap_or_else(|| {
+                score_all_guesses_by(
+                    guesses,
+                    &self.remaining_targets,
+                    weights,
+                    self.duplicates,
+                    |_| None,
+                )
+            })
+    }
+
+    // Rank every valid guess against the remaining targets, best first.
+    pub fn rank_guesses(&self) -> Vec<GuessQuality<'static>> {
+        self.rank_some_guesses(&self.valid_guesses)
+    }
+
+    // The same, but only for some of the guesses, when the rest aren't wanted.
+    pub fn rank_some_guesses(&self, guesses: &[&'static str]) -> Vec<GuessQuality<'static>> {
+        let mut ranked = self.score_guesses(guesses);
+        ranked.sort_by(compare_guess_quality);
+        ranked
+    }
+
+    // The guess that would come first in rank_guesses.
+    pub fn best_guess(&self) -> Option<GuessQuality<'static>> {
+        self.best_guess_by(compare_guess_quality)
+    }
+
+    // The best guess by some other ordering. See best_guess_by.
+    pub fn best_guess_by(
+        &self,
+        compare: impl Fn(&GuessQuality, &GuessQuality) -> std::cmp::Ordering + Sync + Send,
+    ) -> Option<GuessQuality<'static>> {
+        self.score_all_guesses()
+            .into_par_iter()
+            .min_by(|a, b| compare(a, b))
+    }
+
+    // Everything about one guess against the remaining targets, histogram and all. The guess
+    // doesn't have to be one of the valid guesses.
+    pub fn guess_details<'a>(&self, guess: &'a str) -> GuessDetails<'a> {
+        let weights = self.weighted.then(|| self.answer_weights());
+        guess_details_with(
+            guess,
+            &self.remaining_targets,
+            weights.as_deref(),
+            self.duplicates,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LISTS: WordLists = WordLists {
+        guesses: &["about", "chant", "crane", "plant", "slant"],
+        solutions: &["about", "chant", "plant", "slant"],
+        built_in: false,
+    };
+
+    // A score that's possible by itself, but not after the ones before it, gets turned away
+    // without touching the game, by either rule.
+    #[test]
+    fn scores_that_conflict_with_earlier_turns_are_rejected() {
+        for policy in [DuplicatePolicy::Standard, DuplicatePolicy::AllYellow] {
+            let mut solver = Solver::with_word_lists(TargetList::Solutions, false, LISTS);
+            solver.set_duplicate_policy(policy);
+            solver
+                .apply_guess("crane", parse_score("..y..").unwrap())
+                .unwrap();
+            assert_eq!(solver.remaining_targets(), ["about"]);
+
+            // The 'a' was yellow in the middle, so it can't be green there, and the 'c' was gray.
+            for (guess, score) in [("plant", "..G.."), ("chant", "G...G")] {
+                let result = solver.apply_guess(guess, parse_score(score).unwrap());
+                assert!(
+                    matches!(result, Err(Error::EmptyCandidateSet(..))),
+                    "{:?}: {} scored {}",
+                    policy,
+                    guess,
+                    score
+                );
+                assert_eq!(solver.remaining_targets(), ["about"]);
+                assert_eq!(solver.history().len(), 1);
+            }
+
+            // One that fits still goes in.
+            solver
+                .apply_guess("plant", parse_score("..y.G").unwrap())
+                .unwrap();
+            assert_eq!(solver.history().len(), 2);
+            assert_eq!(solver.remaining_targets(), ["about"]);
+        }
+    }
+
+    #[test]
+    fn impossible_scores_are_rejected_before_the_candidates() {
+        for policy in [DuplicatePolicy::Standard, DuplicatePolicy::AllYellow] {
+            let mut solver = Solver::with_word_lists(TargetList::Solutions, false, LISTS);
+            solver.set_duplicate_policy(policy);
+            let result = solver.apply_guess("crane", parse_score("GGGGy").unwrap());
+            assert!(matches!(result, Err(Error::InconsistentScore(..))));
+            assert_eq!(solver.remaining_targets().len(), LISTS.solutions.len());
+            assert!(solver.history().is_empty());
+        }
+    }
+}
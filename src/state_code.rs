@@ -0,0 +1,189 @@
+use crate::error::*;
+use crate::score::*;
+
+// A game's guesses and scores as a short code, to paste into a chat so that someone else can
+// pick up the same game without any files changing hands, like "W1-CRANEHB-SLATEAK". After the
+// version, each turn is the guess and then its score in two characters, in base 32: A to Z for
+// 0 to 25, then 2 to 7 for 26 to 31, which is RFC 4648's base32 alphabet. A letter of the guess
+// is its own digit in that alphabet, so the guesses can still be read right off the code, and
+// only the scores look like gibberish. Case and the dashes don't matter when reading one back.
+const VERSION: &str = "W1";
+const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+// The characters in a turn: the letters of the guess, and the two for the score.
+const TURN_LENGTH: usize = WORD_LENGTH + 2;
+
+// Write out the code for these turns. Only guesses made of the letters A to Z fit in a code.
+pub fn encode_state(history: &[(String, WordScore)]) -> Result<String> {
+    let mut code = VERSION.to_string();
+    for (guess, score) in history {
+        if guess.len() != WORD_LENGTH || !guess.bytes().all(|b| b.is_ascii_lowercase()) {
+            return Err(Error::InvalidStateCode(format!(
+                "'{}' has letters that a code can't hold; only A to Z will fit",
+                guess
+            )));
+        }
+        code.push('-');
+        code.push_str(&guess.to_ascii_uppercase());
+        code.push(ALPHABET[*score as usize / 32] as char);
+        code.push(ALPHABET[*score as usize % 32] as char);
+    }
+    Ok(code)
+}
+
+// Read back the turns in a code.
+pub fn decode_state(code: &str) -> Result<Vec<(String, WordScore)>> {
+    let cleaned = code
+        .chars()
+        .filter(|&c| c != '-' && !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_uppercase();
+    let turns = cleaned.strip_prefix(VERSION).ok_or_else(|| {
+        Error::InvalidStateCode(format!("'{}' doesn't start with {}", code.trim(), VERSION))
+    })?;
+    if turns.len() % TURN_LENGTH != 0 || !turns.is_ascii() {
+        return Err(Error::InvalidStateCode(format!(
+            "'{}' isn't the right length; is part of it missing?",
+            code.trim()
+        )));
+    }
+
+    let digit = |c: u8| ALPHABET.iter().position(|&d| d == c);
+    turns
+        .as_bytes()
+        .chunks(TURN_LENGTH)
+        .map(|turn| {
+            let (guess, score) = turn.split_at(WORD_LENGTH);
+            let score = match (digit(score[0]), digit(score[1])) {
+                (Some(high), Some(low)) if high * 32 + low < NUM_SCORES => (high * 32 + low) as u8,
+                _ => {
+                    return Err(Error::InvalidStateCode(format!(
+                        "'{}' isn't a score",
+                        String::from_utf8_lossy(score)
+                    )))
+                }
+            };
+            if !guess.iter().all(|b| b.is_ascii_uppercase()) {
+                return Err(Error::InvalidStateCode(format!(
+                    "'{}' isn't a word",
+                    String::from_utf8_lossy(guess)
+                )));
+            }
+            Ok((String::from_utf8_lossy(guess).to_ascii_lowercase(), score))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solver::*;
+
+    const LISTS: WordLists = WordLists {
+        guesses: &[
+            "about", "crane", "eerie", "geese", "slate", "those", "trees",
+        ],
+        solutions: &["about", "geese", "those", "trees"],
+        built_in: false,
+    };
+
+    // Play a game against the answer under some settings, and check that its code reads back as
+    // the same turns, which leave the same answers when they're played again.
+    fn check_round_trip(hard: bool, policy: DuplicatePolicy, answer: &str, guesses: &[&str]) {
+        let new_solver = || {
+            let mut solver = Solver::with_word_lists(TargetList::Solutions, hard, LISTS);
+            solver.set_duplicate_policy(policy);
+            solver
+        };
+        let mut solver = new_solver();
+        for guess in guesses {
+            solver
+                .apply_guess(guess, solver.score(guess, answer))
+                .unwrap();
+        }
+
+        let code = encode_state(solver.history()).unwrap();
+        let decoded = decode_state(&code).unwrap();
+        assert_eq!(decoded, solver.history(), "{}", code);
+
+        // Codes get typed back in by hand, too.
+        let retyped = code.to_lowercase().replace('-', " ");
+        assert_eq!(decode_state(&retyped).unwrap(), decoded);
+
+        let mut replayed = new_solver();
+        for (guess, score) in &decoded {
+            replayed.apply_guess(guess, *score).unwrap();
+        }
+        assert_eq!(replayed.remaining_targets(), solver.remaining_targets());
+    }
+
+    #[test]
+    fn codes_read_back_as_the_same_game() {
+        for policy in [DuplicatePolicy::Standard, DuplicatePolicy::AllYellow] {
+            for hard in [false, true] {
+                check_round_trip(hard, policy, "geese", &[]);
+                check_round_trip(hard, policy, "geese", &["crane"]);
+                check_round_trip(hard, policy, "those", &["eerie", "trees"]);
+                check_round_trip(hard, policy, "geese", &["trees", "geese"]);
+            }
+        }
+
+        // Every score there is fits in a code.
+        let history = (0..NUM_SCORES as WordScore)
+            .map(|score| ("slate".to_string(), score))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            decode_state(&encode_state(&history).unwrap()).unwrap(),
+            history
+        );
+    }
+
+    #[test]
+    fn broken_codes_are_rejected() {
+        let code = encode_state(&[
+            ("crane".to_string(), parse_score(".y..G").unwrap()),
+            ("slate".to_string(), parse_score("GG...").unwrap()),
+        ])
+        .unwrap();
+        assert!(decode_state(&code).is_ok());
+
+        // Cut short anywhere but between turns.
+        for end in 1..code.len() {
+            let cut = &code[..end];
+            if !cut.ends_with('-') && !code[end..].starts_with('-') {
+                assert!(decode_state(cut).is_err(), "{}", cut);
+            }
+        }
+
+        for broken in [
+            "",
+            "CRANEHB",
+            "W2-CRANEHB",
+            "X1-CRANEHB",
+            // Digits that aren't in the alphabet, and a score past the last one.
+            "W1-CRANE1B",
+            "W1-CRANEH9",
+            "W1-CRANE77",
+            "W1-CRANEHT",
+            // Something other than letters in the guess.
+            "W1-CR4NEHB",
+            "W1-CRÁNEHB",
+            "W1-CRANEHB-SLATE",
+        ] {
+            assert!(
+                matches!(decode_state(broken), Err(Error::InvalidStateCode(_))),
+                "{:?}",
+                broken
+            );
+        }
+
+        // A guess that doesn't fit won't go into a code in the first place.
+        for guess in ["cañon", "cran", "cranes", "CRANE"] {
+            assert!(
+                encode_state(&[(guess.to_string(), 0)]).is_err(),
+                "{}",
+                guess
+            );
+        }
+    }
+}
@@ -0,0 +1,314 @@
+use crate::error::*;
+use crate::guess_quality::*;
+use crate::solver::*;
+use rand::seq::SliceRandom;
+use rand::RngCore;
+
+// A way of picking the next guess. The interactive loop, the simulator, and anything else that
+// needs to play a game all go through this, so a new idea for picking guesses only needs a new
+// implementation here to be playable, simulated, and compared with the others.
+pub trait Strategy: Sync {
+    // The short name used to pick this strategy on the command line.
+    fn name(&self) -> &'static str;
+
+    // A one-line explanation for help text and reports.
+    fn description(&self) -> &'static str;
+
+    // Whether the strategy always picks the same guess for the same game state. The simulator
+    // shares those choices between games instead of working them out again.
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+
+    // Pick the next guess for a game in the given state. There's always at least one possible
+    // answer left. Strategies that make random choices take them from rng.
+    fn choose_guess(&self, solver: &Solver, rng: &mut dyn RngCore) -> &'static str;
+
+    // Put the guesses from rank_guesses in this strategy's order, best first, for the
+    // interactive loop to suggest. A strategy that only ever picks one word gets that word moved
+    // to the top, and the rest stay in the order they were in. The list may only be the top of
+    // the ranking, so the word gets added if it isn't there.
+    fn order_suggestions(
+        &self,
+        solver: &Solver,
+        ranked: &mut Vec<GuessQuality<'static>>,
+        rng: &mut dyn RngCore,
+    ) {
+        let pick = self.choose_guess(solver, rng);
+        match ranked.iter().position(|q| q.guess == pick) {
+            Some(i) => ranked[..=i].rotate_right(1),
+            None => ranked.insert(0, solver.guess_details(pick).quality),
+        }
+    }
+}
+
+// The top suggestion from rank_guesses, same as the interactive loop shows first: the smallest
+// average times worst case.
+pub struct RankerStrategy;
+
+impl Strategy for RankerStrategy {
+    fn name(&self) -> &'static str {
+        "ranker"
+    }
+
+    fn description(&self) -> &'static str {
+        "the suggestions this program normally makes"
+    }
+
+    fn choose_guess(&self, solver: &Solver, _rng: &mut dyn RngCore) -> &'static str {
+        solver
+            .best_guess()
+            .map_or(solver.remaining_targets()[0], |q| q.guess)
+    }
+
+    // They're already in this order.
+    fn order_suggestions(
+        &self,
+        _solver: &Solver,
+        _ranked: &mut Vec<GuessQuality<'static>>,
+        _rng: &mut dyn RngCore,
+    ) {
+    }
+}
+
+// Whichever guess tells you the most on average, measured in bits. This is the approach most
+// other Wordle solvers take.
+pub struct EntropyStrategy;
+
+impl Strategy for EntropyStrategy {
+    fn name(&self) -> &'static str {
+        "entropy"
+    }
+
+    fn description(&self) -> &'static str {
+        "the most information on average"
+    }
+
+    fn choose_guess(&self, solver: &Solver, _rng: &mut dyn RngCore) -> &'static str {
+        solver
+            .best_guess_by(|a, b| Metric::Entropy.compare(a, b))
+            .map_or(solver.remaining_targets()[0], |q| q.guess)
+    }
+
+    fn order_suggestions(
+        &self,
+        _solver: &Solver,
+        ranked: &mut Vec<GuessQuality<'static>>,
+        _rng: &mut dyn RngCore,
+    ) {
+        ranked.sort_by(|a, b| Metric::Entropy.compare(a, b));
+    }
+}
+
+// Whichever guess leaves the fewest possible words if the score comes out as badly as it
+// possibly can.
+pub struct WorstCaseStrategy;
+
+impl Strategy for WorstCaseStrategy {
+    fn name(&self) -> &'static str {
+        "worstcase"
+    }
+
+    fn description(&self) -> &'static str {
+        "the smallest worst case"
+    }
+
+    fn choose_guess(&self, solver: &Solver, _rng: &mut dyn RngCore) -> &'static str {
+        solver
+            .best_guess_by(|a, b| Metric::WorstCase.compare(a, b))
+            .map_or(solver.remaining_targets()[0], |q| q.guess)
+    }
+
+    fn order_suggestions(
+        &self,
+        _solver: &Solver,
+        ranked: &mut Vec<GuessQuality<'static>>,
+        _rng: &mut dyn RngCore,
+    ) {
+        ranked.sort_by(|a, b| Metric::WorstCase.compare(a, b));
+    }
+}
+
+// A random word that could still be the answer. This is roughly what you'd get playing hard
+// mode with no strategy at all.
+pub struct RandomStrategy;
+
+impl Strategy for RandomStrategy {
+    fn name(&self) -> &'static str {
+        "random"
+    }
+
+    fn description(&self) -> &'static str {
+        "a random word that could still be the answer"
+    }
+
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+
+    fn choose_guess(&self, solver: &Solver, rng: &mut dyn RngCore) -> &'static str {
+        let remaining_targets = solver.remaining_targets();
+        remaining_targets
+            .choose(rng)
+            .copied()
+            .unwrap_or(remaining_targets[0])
+    }
+}
+
+// The most common word that could still be the answer.
+pub struct MostFrequentStrategy;
+
+impl Strategy for MostFrequentStrategy {
+    fn name(&self) -> &'static str {
+        "frequent"
+    }
+
+    fn description(&self) -> &'static str {
+        "the most common word that could still be the answer"
+    }
+
+    fn choose_guess(&self, solver: &Solver, _rng: &mut dyn RngCore) -> &'static str {
+        solver.remaining_targets()[0]
+    }
+}
+
+// Whichever guess gets the most green letters on average against the remaining words, ignoring
+// yellows and what the greens tell you.
+pub struct GreensStrategy;
+
+impl Strategy for GreensStrategy {
+    fn name(&self) -> &'static str {
+        "greens"
+    }
+
+    fn description(&self) -> &'static str {
+        "whatever gets the most green letters"
+    }
+
+    fn choose_guess(&self, solver: &Solver, _rng: &mut dyn RngCore) -> &'static str {
+        let remaining_targets = solver.remaining_targets();
+        let mut best_guess = remaining_targets[0];
+        let mut best_greens = 0;
+        for &guess in solver.valid_guesses() {
+            let greens: usize = remaining_targets
+                .iter()
+                .map(|target| {
+                    guess
+                        .bytes()
+                        .zip(target.bytes())
+                        .filter(|(g, t)| g == t)
+                        .count()
+                })
+                .sum();
+            if greens > best_greens {
+                best_greens = greens;
+                best_guess = guess;
+            }
+        }
+        best_guess
+    }
+}
+
+// How much each of the measures of a guess counts, for WeightedStrategy. Each one is on the same
+// scale, the natural log of a number of words, so that the weights can be compared with each
+// other: the average and worst case are logged, the entropy (which is already a log, in bits)
+// is converted over, and could_win is taken off for a guess that might be the answer.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Weights {
+    pub average: f64,
+    pub worst: f64,
+    pub entropy: f64,
+    pub could_win: f64,
+}
+
+impl Weights {
+    // The weights that order guesses the same as the ranker: the log of the average plus the
+    // log of the worst case is the log of the product.
+    pub const RANKER: Weights = Weights {
+        average: 1.0,
+        worst: 1.0,
+        entropy: 0.0,
+        could_win: 0.0,
+    };
+
+    // How bad a guess is by these weights. Smaller is better.
+    pub fn cost(&self, q: &GuessQuality) -> f64 {
+        self.average * q.expected_remaining.max(f64::MIN_POSITIVE).ln()
+            + self.worst * (q.max_remaining.max(1) as f64).ln()
+            - self.entropy * q.entropy * std::f64::consts::LN_2
+            - if q.has_winning { self.could_win } else { 0.0 }
+    }
+}
+
+// The guess with the lowest cost by some weights. This is for tuning: trying lots of weights
+// to see which work best (see the sweep subcommand). Ties go the way the ranker would break them.
+pub struct WeightedStrategy(pub Weights);
+
+impl Strategy for WeightedStrategy {
+    fn name(&self) -> &'static str {
+        "weighted"
+    }
+
+    fn description(&self) -> &'static str {
+        "a weighted mix of the average, worst case, entropy, and chance of winning"
+    }
+
+    fn choose_guess(&self, solver: &Solver, _rng: &mut dyn RngCore) -> &'static str {
+        solver
+            .best_guess_by(|a, b| self.compare(a, b))
+            .map_or(solver.remaining_targets()[0], |q| q.guess)
+    }
+
+    fn order_suggestions(
+        &self,
+        _solver: &Solver,
+        ranked: &mut Vec<GuessQuality<'static>>,
+        _rng: &mut dyn RngCore,
+    ) {
+        ranked.sort_by(|a, b| self.compare(a, b));
+    }
+}
+
+impl WeightedStrategy {
+    fn compare(&self, a: &GuessQuality, b: &GuessQuality) -> std::cmp::Ordering {
+        self.0
+            .cost(a)
+            .total_cmp(&self.0.cost(b))
+            .then_with(|| compare_guess_quality(a, b))
+    }
+}
+
+// Every strategy there is, the real ones first and then the baselines.
+pub fn all_strategies() -> Vec<Box<dyn Strategy>> {
+    vec![
+        Box::new(RankerStrategy),
+        Box::new(EntropyStrategy),
+        Box::new(WorstCaseStrategy),
+        Box::new(RandomStrategy),
+        Box::new(MostFrequentStrategy),
+        Box::new(GreensStrategy),
+    ]
+}
+
+// Just the real strategies, without the baselines.
+pub fn real_strategies() -> Vec<Box<dyn Strategy>> {
+    vec![
+        Box::new(RankerStrategy),
+        Box::new(EntropyStrategy),
+        Box::new(WorstCaseStrategy),
+    ]
+}
+
+// Look up a strategy by the name it goes by on the command line.
+pub fn strategy_by_name(name: &str) -> Result<Box<dyn Strategy>> {
+    let strategies = all_strategies();
+    let names = strategies
+        .iter()
+        .map(|strategy| strategy.name())
+        .collect::<Vec<_>>()
+        .join(", ");
+    strategies
+        .into_iter()
+        .find(|strategy| strategy.name() == name)
+        .ok_or_else(|| Error::UnknownStrategy(name.to_string(), names))
+}
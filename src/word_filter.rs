@@ -0,0 +1,221 @@
+use crate::error::*;
+use crate::lists::*;
+use std::collections::HashSet;
+
+// A little language for personal rules about which words you're willing to play, like
+//
+//     not ends_with(s) and unique_letters
+//     common(3000) or has(qz)
+//
+// The rules are:
+//
+//     unique_letters      no letter appears twice
+//     starts_with(abc)    the word starts with these letters
+//     ends_with(abc)      the word ends with these letters
+//     contains(abc)       these letters appear together somewhere in the word
+//     has(abc)            each of these letters appears somewhere in the word
+//     common(N)           the word is among the N most common English words
+//
+// and they combine with "not", "and", "or", and parentheses. "not" binds tightest, then "and",
+// then "or", so "a or b and not c" means "a or (b and (not c))".
+pub struct WordFilter {
+    expr: Expr,
+}
+
+enum Expr {
+    UniqueLetters,
+    StartsWith(String),
+    EndsWith(String),
+    Contains(String),
+    Has(String),
+    Common(HashSet<&'static str>),
+    Not(Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Or(Box<Expr>, Box<Expr>),
+}
+
+impl WordFilter {
+    pub fn parse(text: &str) -> Result<WordFilter> {
+        let mut parser = Parser {
+            tokens: tokenize(text)?,
+            pos: 0,
+        };
+        let expr = parser.parse_or()?;
+        match parser.next() {
+            None => Ok(WordFilter { expr }),
+            Some(token) => Err(Error::InvalidFilter(format!(
+                "didn't expect '{}' there",
+                token
+            ))),
+        }
+    }
+
+    pub fn matches(&self, word: &str) -> bool {
+        self.expr.matches(word)
+    }
+}
+
+impl Expr {
+    fn matches(&self, word: &str) -> bool {
+        match self {
+            // A letter at a time, not a byte, so that accented letters (two bytes each, often
+            // with the same first one) are told apart properly.
+            Expr::UniqueLetters => {
+                let mut seen = Vec::with_capacity(crate::score::WORD_LENGTH);
+                word.chars().all(|c| {
+                    let fresh = !seen.contains(&c);
+                    seen.push(c);
+                    fresh
+                })
+            }
+            Expr::StartsWith(letters) => word.starts_with(letters.as_str()),
+            Expr::EndsWith(letters) => word.ends_with(letters.as_str()),
+            Expr::Contains(letters) => word.contains(letters.as_str()),
+            Expr::Has(letters) => letters.chars().all(|c| word.contains(c)),
+            Expr::Common(words) => words.contains(word),
+            Expr::Not(e) => !e.matches(word),
+            Expr::And(a, b) => a.matches(word) && b.matches(word),
+            Expr::Or(a, b) => a.matches(word) || b.matches(word),
+        }
+    }
+}
+
+// Words (rule names, operators, and arguments) and the punctuation between them.
+fn tokenize(text: &str) -> Result<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '(' || c == ')' {
+            tokens.push(c.to_string());
+            chars.next();
+        } else if c.is_ascii_alphanumeric() || c == '_' {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if !(c.is_ascii_alphanumeric() || c == '_') {
+                    break;
+                }
+                word.push(c.to_ascii_lowercase());
+                chars.next();
+            }
+            tokens.push(word);
+        } else {
+            return Err(Error::InvalidFilter(format!("didn't expect '{}'", c)));
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<String>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&str> {
+        self.tokens.get(self.pos).map(|t| t.as_str())
+    }
+
+    fn next(&mut self) -> Option<String> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<()> {
+        match self.next() {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(Error::InvalidFilter(format!(
+                "expected '{}' but found '{}'",
+                expected, token
+            ))),
+            None => Err(Error::InvalidFilter(format!(
+                "expected '{}' but the filter ended",
+                expected
+            ))),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr> {
+        let mut expr = self.parse_and()?;
+        while self.peek() == Some("or") {
+            self.next();
+            expr = Expr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr> {
+        let mut expr = self.parse_not()?;
+        while self.peek() == Some("and") {
+            self.next();
+            expr = Expr::And(Box::new(expr), Box::new(self.parse_not()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_not(&mut self) -> Result<Expr> {
+        if self.peek() == Some("not") {
+            self.next();
+            return Ok(Expr::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_rule()
+    }
+
+    fn parse_rule(&mut self) -> Result<Expr> {
+        let token = self
+            .next()
+            .ok_or_else(|| Error::InvalidFilter("the filter ended too soon".to_string()))?;
+
+        if token == "(" {
+            let expr = self.parse_or()?;
+            self.expect(")")?;
+            return Ok(expr);
+        }
+
+        if token == "unique_letters" {
+            return Ok(Expr::UniqueLetters);
+        }
+
+        // Everything else takes an argument in parentheses.
+        let make_rule: fn(String) -> Result<Expr> = match token.as_str() {
+            "starts_with" => |letters| Ok(Expr::StartsWith(letters)),
+            "ends_with" => |letters| Ok(Expr::EndsWith(letters)),
+            "contains" => |letters| Ok(Expr::Contains(letters)),
+            "has" => |letters| Ok(Expr::Has(letters)),
+            "common" => |count| {
+                let count: usize = count.parse().map_err(|_| {
+                    Error::InvalidFilter(format!("common() needs a number, not '{}'", count))
+                })?;
+                Ok(Expr::Common(
+                    word_frequencies()
+                        .iter()
+                        .map(|(word, _freq)| *word)
+                        .filter(|word| crate::score::word_length(word) == crate::score::WORD_LENGTH)
+                        .take(count)
+                        .collect(),
+                ))
+            },
+            _ => {
+                return Err(Error::InvalidFilter(format!(
+                    "unknown rule '{}'; the rules are unique_letters, starts_with(), ends_with(), contains(), has(), and common()",
+                    token
+                )))
+            }
+        };
+
+        self.expect("(")?;
+        let argument = match self.next() {
+            Some(argument) if argument != "(" && argument != ")" => argument,
+            _ => {
+                return Err(Error::InvalidFilter(format!(
+                    "{}() needs something in the parentheses",
+                    token
+                )))
+            }
+        };
+        self.expect(")")?;
+        make_rule(argument)
+    }
+}
@@ -0,0 +1,18 @@
+// Tables worked out from the built-in word lists by build.rs, so that setting up a game is just
+// a matter of copying words out of them. Words are stored as guess IDs: their place in
+// SCRABBLE_WORD_LIST.
+//
+//     COMMON_GUESS_IDS           every guess in the frequency list, most common first
+//     SOLUTION_IDS_BY_FREQUENCY  the Wordle solution list, most common first
+//     KIDS_IDS_BY_FREQUENCY      the kids' list, most common first
+//     GUESS_FREQUENCIES          each guess's count from the frequency list, or 0
+include!(concat!(env!("OUT_DIR"), "/word_tables.rs"));
+
+// A word's count from the frequency list, if it's one of the built-in guesses. The guess list
+// is sorted, so this is a binary search, with no hash table to build first.
+pub fn built_in_frequency(word: &str) -> Option<u32> {
+    crate::scrabble_word_list::SCRABBLE_WORD_LIST
+        .binary_search(&word)
+        .ok()
+        .map(|id| GUESS_FREQUENCIES[id])
+}